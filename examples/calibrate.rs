@@ -0,0 +1,6 @@
+//! Print thresholds tuned for this machine, in the form `Thresholds`
+//! parses.
+
+fn main() {
+    print!("{}", bignum::calibrate::calibrate());
+}
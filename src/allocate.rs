@@ -0,0 +1,64 @@
+//! Proportional Allocation
+//!
+//! Splitting a whole number of cents, seats or units in proportion to
+//! weights, so that the shares always add back up to the total.
+
+use crate::natural::Natural;
+
+/// Split `total` in proportion to `weights` by the largest remainder
+/// method: each share starts as the floor of its exact quota
+/// `total * weight / sum(weights)`, and the units left over go one each to
+/// the largest fractional remainders, earlier weights winning ties. The
+/// shares add up to exactly `total`.
+///
+/// Panics if the weights sum to zero, including when there are none.
+pub fn allocate(total: &Natural, weights: &[Natural]) -> Vec<Natural> {
+    let sum: Natural = weights.iter().sum();
+    assert!(sum != Natural::zero(), "weights sum to zero");
+    let (mut shares, remainders): (Vec<Natural>, Vec<Natural>) = weights.iter()
+        .map(|w| (total * w).div_rem(&sum))
+        .unzip();
+
+    let allocated: Natural = shares.iter().sum();
+    let left = usize::try_from(&(total - allocated)).unwrap();
+    // A stable sort, so equal remainders stay in order
+    let mut order: Vec<usize> = (0..weights.len()).collect();
+    order.sort_by(|a, b| remainders[*b].cmp(&remainders[*a]));
+    for k in &order[..left] {
+        shares[*k].increment();
+    }
+    shares
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn v(values: &[u32]) -> Vec<Natural> {
+        values.iter().map(|x| Natural::from(*x)).collect()
+    }
+
+    #[test]
+    fn largest_remainders() {
+        // Quotas 33.3, 33.3 and 33.3: the extra cent goes to the first
+        assert_eq!(allocate(&Natural::from(100u32), &v(&[1, 1, 1])), v(&[34, 33, 33]));
+        // Quotas 4.2, 2.8 and 3.0
+        assert_eq!(allocate(&Natural::from(10u32), &v(&[42, 28, 30])), v(&[4, 3, 3]));
+        assert_eq!(allocate(&Natural::from(7u32), &v(&[0, 5, 0])), v(&[0, 7, 0]));
+        assert_eq!(allocate(&Natural::zero(), &v(&[2, 3])), v(&[0, 0]));
+    }
+
+    #[test]
+    fn sums_to_total() {
+        let total: Natural = "1000000000000000000000000000007".parse().unwrap();
+        let weights = v(&[3, 7, 11, 13, 17, 19, 23]);
+        let shares = allocate(&total, &weights);
+        assert_eq!(shares.iter().sum::<Natural>(), total);
+    }
+
+    #[test]
+    #[should_panic(expected = "weights sum to zero")]
+    fn zero_weights() {
+        allocate(&Natural::one(), &v(&[0, 0]));
+    }
+}
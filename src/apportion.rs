@@ -0,0 +1,135 @@
+//! Seat Apportionment
+//!
+//! The highest averages methods of giving seats to parties, or House
+//! seats to states, in proportion to their votes or populations. Seats go
+//! one at a time to the party with the highest priority, its votes
+//! divided by a divisor that grows with the seats it already holds, and
+//! priorities are compared as exact fractions, so a tie is a real tie.
+//! For the largest remainder (Hamilton) method, see `allocate`.
+
+use crate::natural::Natural;
+use std::cmp::Ordering;
+
+/// The divisor for a party already holding `n` seats.
+#[derive(Clone,Copy,Debug,PartialEq,Eq)]
+pub enum DivisorMethod {
+    /// `n + 1`, which favours large parties
+    DHondt,
+    /// `2n + 1`
+    SainteLague,
+    /// `sqrt(n (n + 1))`, so every party with votes gets a seat before
+    /// any gets a second, as in the US House
+    HuntingtonHill
+}
+
+#[derive(Clone,Debug,PartialEq,Eq)]
+pub struct Apportionment {
+    /// Seats per party, in the order of the votes
+    pub seats: Vec<usize>,
+    /// The parties tied for the last seats when there were too few to go
+    /// round, in which case the earliest of them got the seats. Empty when
+    /// no tie affected the result.
+    pub tied: Vec<usize>
+}
+
+// A priority as the fraction numerator / denominator, where a zero
+// denominator is infinite
+fn priority(method: DivisorMethod, votes: &Natural, n: usize) -> (Natural, Natural) {
+    match method {
+        DivisorMethod::DHondt => (votes.clone(), Natural::from(n + 1)),
+        DivisorMethod::SainteLague => (votes.clone(), Natural::from(2 * n + 1)),
+        DivisorMethod::HuntingtonHill if *votes == Natural::zero() => (Natural::zero(), Natural::one()),
+        DivisorMethod::HuntingtonHill => (votes * votes, Natural::from(n) * (n + 1))
+    }
+}
+
+// Infinite priorities, of parties without a seat under Huntington-Hill,
+// still rank by votes among themselves
+fn compare(a: &(Natural, Natural), b: &(Natural, Natural)) -> Ordering {
+    if a.1 == Natural::zero() && b.1 == Natural::zero() {
+        return a.0.cmp(&b.0);
+    }
+    (&a.0 * &b.1).cmp(&(&b.0 * &a.1))
+}
+
+/// Give `seats` seats to the parties with these `votes`.
+///
+/// Panics if there are seats to give but no votes.
+pub fn apportion(votes: &[Natural], seats: usize, method: DivisorMethod) -> Apportionment {
+    let mut result = Apportionment{ seats: vec![0; votes.len()], tied: vec![] };
+    if seats == 0 {
+        return result;
+    }
+    assert!(votes.iter().any(|v| *v != Natural::zero()), "no votes cast");
+
+    let mut priorities: Vec<(Natural, Natural)> = votes.iter().map(|v| priority(method, v, 0)).collect();
+    let mut left = seats;
+    while left > 0 {
+        let best = priorities.iter().max_by(|a, b| compare(a, b)).unwrap();
+        let leaders: Vec<usize> = (0..votes.len())
+            .filter(|k| compare(&priorities[*k], best) == Ordering::Equal)
+            .collect();
+        // Too few seats for everyone tied: the earliest get them
+        if leaders.len() > left {
+            for k in &leaders[..left] {
+                result.seats[*k] += 1;
+            }
+            result.tied = leaders;
+            break;
+        }
+        let k = leaders[0];
+        result.seats[k] += 1;
+        priorities[k] = priority(method, &votes[k], result.seats[k]);
+        left -= 1;
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn v(values: &[u32]) -> Vec<Natural> {
+        values.iter().map(|x| Natural::from(*x)).collect()
+    }
+
+    fn seats(votes: &[u32], seats: usize, method: DivisorMethod) -> Vec<usize> {
+        let result = apportion(&v(votes), seats, method);
+        assert!(result.tied.is_empty());
+        result.seats
+    }
+
+    #[test]
+    fn methods() {
+        let votes = [100000, 80000, 30000, 20000];
+        assert_eq!(seats(&votes, 8, DivisorMethod::DHondt), vec![4, 3, 1, 0]);
+        assert_eq!(seats(&votes, 8, DivisorMethod::SainteLague), vec![3, 3, 1, 1]);
+        assert_eq!(seats(&votes, 8, DivisorMethod::HuntingtonHill), vec![3, 3, 1, 1]);
+        assert_eq!(seats(&[53000, 24000, 23000], 7, DivisorMethod::DHondt), vec![4, 2, 1]);
+        assert_eq!(seats(&[53000, 24000, 23000], 7, DivisorMethod::SainteLague), vec![3, 2, 2]);
+        // Huntington-Hill seats every party first
+        assert_eq!(seats(&[10, 5, 1], 4, DivisorMethod::HuntingtonHill), vec![2, 1, 1]);
+        assert_eq!(seats(&[10, 5, 1], 4, DivisorMethod::SainteLague), vec![3, 1, 0]);
+        assert_eq!(seats(&[10, 5, 1], 4, DivisorMethod::DHondt), vec![3, 1, 0]);
+        assert_eq!(seats(&[10, 0], 3, DivisorMethod::HuntingtonHill), vec![3, 0]);
+        // Too few seats to go round: the first go by votes
+        assert_eq!(seats(&[1, 5, 10], 2, DivisorMethod::HuntingtonHill), vec![0, 1, 1]);
+        assert_eq!(seats(&[], 0, DivisorMethod::DHondt), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn ties() {
+        let result = apportion(&v(&[30, 10, 30]), 1, DivisorMethod::DHondt);
+        assert_eq!(result, Apportionment{ seats: vec![1, 0, 0], tied: vec![0, 2] });
+        // 30/2 ties 15/1 for the second seat, but there are seats for both
+        assert_eq!(seats(&[30, 15], 4, DivisorMethod::DHondt), vec![3, 1]);
+        let result = apportion(&v(&[30, 15]), 2, DivisorMethod::DHondt);
+        assert_eq!(result, Apportionment{ seats: vec![2, 0], tied: vec![0, 1] });
+    }
+
+    #[test]
+    #[should_panic(expected = "no votes cast")]
+    fn no_votes() {
+        apportion(&v(&[0, 0]), 1, DivisorMethod::SainteLague);
+    }
+}
@@ -0,0 +1,195 @@
+//! Balanced Signed-Digit Decimal
+//!
+//! Each digit is in -5..=5, which makes the representation redundant
+//! (5 and 1-5 both mean five) and lets addition run without carry chains:
+//! every output digit depends only on the input digit pairs at its own
+//! position and the two below it, however long the numbers are. Because of
+//! the redundancy, equality compares values, not digits.
+
+use crate::digit::Digit;
+use crate::natural::Natural;
+
+/// A base-10 integer whose digits are in -5..=5, least significant first.
+#[derive(Clone,Debug)]
+pub struct BalancedDecimal {
+    digits: Vec<i8>
+}
+
+impl BalancedDecimal {
+    pub fn digits(&self) -> &[i8] {
+        &self.digits
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.digits.iter().rev().find(|d| **d != 0).is_some_and(|d| *d < 0)
+    }
+
+    // The value as (positive part, negative part), both canonical
+    fn parts(&self) -> (Natural, Natural) {
+        let part = |sign: i8| {
+            let digits = self.digits.iter()
+                .map(|d| if d.signum() == sign { d.unsigned_abs() } else { 0 })
+                .map(|d| Digit::try_from(d).unwrap())
+                .collect();
+            Natural::from_digits(digits)
+        };
+        (part(1), part(-1))
+    }
+
+    /// Convert back to canonical form, or `None` if the value is negative.
+    pub fn to_natural(&self) -> Option<Natural> {
+        let (positive, negative) = self.parts();
+        if positive < negative {
+            return None;
+        }
+        Some(positive - negative)
+    }
+}
+
+/// Equal values are equal however their digits differ.
+impl PartialEq for BalancedDecimal {
+    fn eq(&self, other: &Self) -> bool {
+        let (a, b) = self.parts();
+        let (c, d) = other.parts();
+        a + d == c + b
+    }
+}
+
+impl Eq for BalancedDecimal {}
+
+impl From<&Natural> for BalancedDecimal {
+    fn from(n: &Natural) -> Self {
+        let mut digits = vec![];
+        let mut carry = 0;
+        for d in &n.digits {
+            let v = d.as_u8() as i8 + carry;
+            if v > 5 {
+                digits.push(v - 10);
+                carry = 1;
+            } else {
+                digits.push(v);
+                carry = 0;
+            }
+        }
+        if carry > 0 {
+            digits.push(carry);
+        }
+        Self{ digits }
+    }
+}
+
+impl std::ops::Neg for BalancedDecimal {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self{ digits: self.digits.into_iter().map(|d| -d).collect() }
+    }
+}
+
+impl std::ops::Add for BalancedDecimal {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self::Output {
+        let n = std::cmp::max(self.digits.len(), other.digits.len());
+        let at = |x: &Self, i: usize| x.digits.get(i).copied().unwrap_or(0);
+        let sums: Vec<i8> = (0..n).map(|i| at(&self, i) + at(&other, i)).collect();
+
+        // Split each position sum into a transfer out and an interim digit,
+        // looking one position down to break ties at ±5
+        let mut digits = Vec::with_capacity(n + 1);
+        let mut transfer_in = 0;
+        for i in 0..n {
+            let s = sums[i];
+            let below = if i == 0 { 0 } else { sums[i - 1] };
+            let (transfer, interim) = match s {
+                6..=10 => (1, s - 10),
+                -10..=-6 => (-1, s + 10),
+                5 if below >= 5 => (1, -5),
+                -5 if below <= -5 => (-1, 5),
+                _ => (0, s)
+            };
+            digits.push(interim + transfer_in);
+            transfer_in = transfer;
+        }
+        if transfer_in != 0 {
+            digits.push(transfer_in);
+        }
+        Self{ digits }
+    }
+}
+
+impl std::ops::Sub for BalancedDecimal {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self::Output {
+        self + (-other)
+    }
+}
+
+impl std::fmt::Display for BalancedDecimal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (positive, negative) = self.parts();
+        if positive < negative {
+            write!(f, "-{}", negative - positive)
+        } else {
+            write!(f, "{}", positive - negative)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn b(s: &str) -> BalancedDecimal {
+        BalancedDecimal::from(&s.parse::<Natural>().unwrap())
+    }
+
+    #[test]
+    fn conversion() {
+        assert_eq!(b("1999").digits(), &[-1, 0, 0, 2]);
+        assert_eq!(b("1999").to_natural(), Some("1999".parse().unwrap()));
+        assert_eq!(b("0").to_natural(), Some(Natural::zero()));
+    }
+
+    #[test]
+    fn add_matches_natural() {
+        let mut x: u64 = 1;
+        for _ in 0..200 {
+            x = x.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            let (p, q) = (x >> 40, (x >> 8) & 0xffffff);
+            let sum = b(&p.to_string()) + b(&q.to_string());
+            assert!(sum.digits().iter().all(|d| (-5..=5).contains(d)));
+            assert_eq!(sum.to_string(), (p + q).to_string());
+        }
+    }
+
+    #[test]
+    fn ties() {
+        // Every position sums to exactly 5
+        let sum = b("55555") + b("0");
+        assert!(sum.digits().iter().all(|d| (-5..=5).contains(d)));
+        assert_eq!(sum.to_string(), "55555");
+        let again = sum.clone() + sum;
+        assert_eq!(again.to_string(), "111110");
+    }
+
+    #[test]
+    fn equality() {
+        let five = b("10") - b("5");
+        assert_eq!(five.digits(), &[-5, 1]);
+        assert_eq!(b("5").digits(), &[5]);
+        assert_eq!(five, b("5"));
+        assert_ne!(five, b("4"));
+        assert_eq!(b("5") - b("5"), b("0"));
+        assert_eq!(b("12") - b("345"), b("0") - b("333"));
+    }
+
+    #[test]
+    fn negative() {
+        let d = b("12") - b("345");
+        assert!(d.is_negative());
+        assert_eq!(d.to_natural(), None);
+        assert_eq!(d.to_string(), "-333");
+    }
+}
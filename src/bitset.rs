@@ -0,0 +1,207 @@
+//! Bit Sets
+//!
+//! A `BitSet` is a set of small natural numbers held as the bits of a
+//! binary number, so the set `{0, 2, 5}` is 2^0 + 2^2 + 2^5 = 37. It
+//! converts to and from `Natural` in that form, for code that treats a
+//! number as a characteristic vector, but keeps its bits in machine words
+//! in between so that membership and set operations are cheap.
+
+use crate::natural::Natural;
+
+#[derive(Clone,Debug,Default,PartialEq,Eq,Hash)]
+pub struct BitSet {
+    // Little-endian, without trailing zero words, so zero has none
+    words: Vec<u64>
+}
+
+impl BitSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn trim(&mut self) {
+        while self.words.last() == Some(&0) {
+            self.words.pop();
+        }
+    }
+
+    pub fn contains(&self, bit: usize) -> bool {
+        self.words.get(bit / 64).is_some_and(|w| w >> (bit % 64) & 1 == 1)
+    }
+
+    /// Set `bit`, returning whether it was clear.
+    pub fn insert(&mut self, bit: usize) -> bool {
+        if self.words.len() <= bit / 64 {
+            self.words.resize(bit / 64 + 1, 0);
+        }
+        let was_clear = !self.contains(bit);
+        self.words[bit / 64] |= 1 << (bit % 64);
+        was_clear
+    }
+
+    /// Clear `bit`, returning whether it was set.
+    pub fn remove(&mut self, bit: usize) -> bool {
+        let was_set = self.contains(bit);
+        if was_set {
+            self.words[bit / 64] &= !(1 << (bit % 64));
+            self.trim();
+        }
+        was_set
+    }
+
+    /// The number of set bits.
+    pub fn len(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    /// The set bits in increasing order.
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words.iter().enumerate().flat_map(|(k, w)| {
+            (0..64).filter(move |i| w >> i & 1 == 1).map(move |i| 64 * k + i)
+        })
+    }
+
+    /// The number whose binary digits are this set.
+    pub fn to_natural(&self) -> Natural {
+        let groups: Vec<u32> = self.words.iter()
+            .flat_map(|w| (0..4).map(move |i| (w >> (16 * i)) as u32 & 0xffff))
+            .collect();
+        Natural::from_power_of_two_le(&groups, 16)
+    }
+
+    fn combine(&self, other: &Self, f: impl Fn(u64, u64) -> u64) -> Self {
+        let len = self.words.len().max(other.words.len());
+        let word = |words: &[u64], k: usize| words.get(k).copied().unwrap_or(0);
+        let mut set = Self{ words: (0..len).map(|k| f(word(&self.words, k), word(&other.words, k))).collect() };
+        set.trim();
+        set
+    }
+}
+
+/// The set of the binary digits of `n` that are one.
+impl From<&Natural> for BitSet {
+    fn from(n: &Natural) -> Self {
+        let groups = n.to_power_of_two_le(16);
+        let words = groups.chunks(4)
+            .map(|chunk| chunk.iter().rev().fold(0, |w, g| (w << 16) | u64::from(*g)))
+            .collect();
+        Self{ words }
+    }
+}
+
+impl From<&BitSet> for Natural {
+    fn from(set: &BitSet) -> Self {
+        set.to_natural()
+    }
+}
+
+impl FromIterator<usize> for BitSet {
+    fn from_iter<I: IntoIterator<Item = usize>>(bits: I) -> Self {
+        let mut set = Self::new();
+        for bit in bits {
+            set.insert(bit);
+        }
+        set
+    }
+}
+
+/// Intersection.
+impl std::ops::BitAnd for &BitSet {
+    type Output = BitSet;
+
+    fn bitand(self, other: &BitSet) -> BitSet {
+        self.combine(other, |a, b| a & b)
+    }
+}
+
+/// Union.
+impl std::ops::BitOr for &BitSet {
+    type Output = BitSet;
+
+    fn bitor(self, other: &BitSet) -> BitSet {
+        self.combine(other, |a, b| a | b)
+    }
+}
+
+/// Symmetric difference.
+impl std::ops::BitXor for &BitSet {
+    type Output = BitSet;
+
+    fn bitxor(self, other: &BitSet) -> BitSet {
+        self.combine(other, |a, b| a ^ b)
+    }
+}
+
+/// Difference.
+impl std::ops::Sub for &BitSet {
+    type Output = BitSet;
+
+    fn sub(self, other: &BitSet) -> BitSet {
+        self.combine(other, |a, b| a & !b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn n(s: &str) -> Natural {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn membership() {
+        let mut set = BitSet::new();
+        assert!(set.insert(5));
+        assert!(!set.insert(5));
+        assert!(set.insert(200));
+        assert!(set.contains(5) && set.contains(200) && !set.contains(6) && !set.contains(1000));
+        assert_eq!(set.len(), 2);
+        assert!(set.remove(200));
+        assert!(!set.remove(200));
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![5]);
+        assert!(set.remove(5));
+        assert!(set.is_empty());
+        assert_eq!(set, BitSet::new());
+    }
+
+    #[test]
+    fn naturals() {
+        let set: BitSet = [0, 2, 5].into_iter().collect();
+        assert_eq!(set.to_natural(), n("37"));
+        assert_eq!(BitSet::from(&n("37")), set);
+        let big = n("2").pow(300) + n("2").pow(64) + Natural::one();
+        let set = BitSet::from(&big);
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![0, 64, 300]);
+        assert_eq!(Natural::from(&set), big);
+        assert!(BitSet::from(&Natural::zero()).is_empty());
+        assert_eq!(BitSet::new().to_natural(), Natural::zero());
+    }
+
+    #[test]
+    fn set_operations() {
+        let a: BitSet = [1, 2, 3, 100].into_iter().collect();
+        let b: BitSet = [3, 4, 100].into_iter().collect();
+        assert_eq!((&a & &b).iter().collect::<Vec<_>>(), vec![3, 100]);
+        assert_eq!((&a | &b).iter().collect::<Vec<_>>(), vec![1, 2, 3, 4, 100]);
+        assert_eq!((&a ^ &b).iter().collect::<Vec<_>>(), vec![1, 2, 4]);
+        assert_eq!((&a - &b).iter().collect::<Vec<_>>(), vec![1, 2]);
+        assert!((&b - &b).is_empty());
+    }
+
+    #[test]
+    fn sieve() {
+        let mut composite = BitSet::new();
+        for i in 2..50 {
+            if !composite.contains(i) {
+                (2 * i..50).step_by(i).for_each(|j| { composite.insert(j); });
+            }
+        }
+        let primes: Vec<usize> = (2..50).filter(|i| !composite.contains(*i)).collect();
+        assert_eq!(primes, vec![2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47]);
+    }
+}
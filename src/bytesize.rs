@@ -0,0 +1,106 @@
+//! Byte Counts in Binary Units
+
+use crate::natural::Natural;
+
+const UNITS: [&str; 10] = ["KiB", "MiB", "GiB", "TiB", "PiB", "EiB", "ZiB", "YiB", "RiB", "QiB"];
+
+/// A byte count split as `whole * 1024^power + remainder`, with `whole`
+/// below 1024 and `remainder` below `1024^power`.
+#[derive(Clone,Debug,PartialEq,Eq)]
+pub struct ByteSize {
+    pub whole: Natural,
+    pub power: usize,
+    pub remainder: Natural
+}
+
+impl ByteSize {
+    pub fn unit(&self) -> String {
+        match self.power {
+            0 => "B".to_string(),
+            p if p <= UNITS.len() => UNITS[p - 1].to_string(),
+            p => format!("× 1024^{} B", p)
+        }
+    }
+
+    // First decimal digit of remainder / 1024^power, truncated
+    pub(crate) fn tenths(&self) -> u32 {
+        let mut tenths = self.remainder.mul_small(10);
+        for _ in 0..self.power {
+            tenths = tenths.div_rem_small(1024).0;
+        }
+        tenths.div_rem_small(10).1
+    }
+}
+
+impl From<&Natural> for ByteSize {
+    fn from(bytes: &Natural) -> Self {
+        let step = Natural::from(1024u32);
+        let mut whole = bytes.clone();
+        let mut power = 0;
+        let mut place = Natural::one();
+        let mut remainder = Natural::zero();
+        while whole >= step {
+            let (quotient, r) = whole.div_rem_small(1024);
+            remainder += place.mul_small(r);
+            place = place.mul_small(1024);
+            whole = quotient;
+            power += 1;
+        }
+        Self{ whole, power, remainder }
+    }
+}
+
+impl std::fmt::Display for ByteSize {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.tenths() {
+            0 => write!(f, "{} {}", self.whole, self.unit()),
+            t => write!(f, "{}.{} {}", self.whole, t, self.unit())
+        }
+    }
+}
+
+impl Natural {
+    pub fn to_byte_size(&self) -> ByteSize {
+        self.into()
+    }
+
+    /// Render a byte count such as "1.5 MiB", truncated to one decimal place.
+    pub fn format_bytes(&self) -> String {
+        self.to_byte_size().to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn n(s: &str) -> Natural {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn small() {
+        assert_eq!(n("1023").format_bytes(), "1023 B");
+    }
+
+    #[test]
+    fn units() {
+        assert_eq!(n("1536").format_bytes(), "1.5 KiB");
+        assert_eq!(n("1073741824").format_bytes(), "1 GiB");
+    }
+
+    #[test]
+    fn exact_remainder() {
+        let size = n("1572865").to_byte_size();
+        assert_eq!(size.whole, n("1"));
+        assert_eq!(size.power, 2);
+        assert_eq!(size.remainder, n("524289"));
+    }
+
+    #[test]
+    fn beyond_quebibytes() {
+        // 2^120 bytes is 1024^12
+        let bytes = n("1329227995784915872903807060280344576");
+        assert_eq!(bytes.format_bytes(), "1 × 1024^12 B");
+    }
+}
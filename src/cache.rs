@@ -0,0 +1,230 @@
+//! Memoization of Expensive Results
+//!
+//! Results are keyed by an operation name and the input's stable
+//! fingerprint, and the input is stored alongside the output so that a
+//! fingerprint collision is detected rather than served.
+
+use crate::natural::Natural;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+#[derive(Clone,Debug,PartialEq,Eq,Hash)]
+pub struct Key {
+    pub operation: String,
+    pub fingerprint: u64
+}
+
+#[derive(Clone,Debug,PartialEq,Eq)]
+pub struct Entry {
+    pub input: Natural,
+    pub output: Natural
+}
+
+/// Somewhere to keep cached entries.
+pub trait Store {
+    /// Look up an entry; any failure to read counts as a miss.
+    fn load(&mut self, key: &Key) -> Option<Entry>;
+    fn save(&mut self, key: &Key, entry: &Entry) -> std::io::Result<()>;
+}
+
+/// An in-memory store that evicts the least recently used entry.
+#[derive(Debug)]
+pub struct MemoryLru {
+    capacity: usize,
+    clock: u64,
+    entries: HashMap<Key, (Entry, u64)>
+}
+
+impl MemoryLru {
+    pub fn new(capacity: usize) -> Self {
+        Self{ capacity, clock: 0, entries: HashMap::new() }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    fn tick(&mut self) -> u64 {
+        self.clock += 1;
+        self.clock
+    }
+}
+
+impl Store for MemoryLru {
+    fn load(&mut self, key: &Key) -> Option<Entry> {
+        let now = self.tick();
+        let (entry, used) = self.entries.get_mut(key)?;
+        *used = now;
+        Some(entry.clone())
+    }
+
+    fn save(&mut self, key: &Key, entry: &Entry) -> std::io::Result<()> {
+        if self.capacity == 0 {
+            return Ok(());
+        }
+        if self.entries.len() >= self.capacity && !self.entries.contains_key(key) {
+            let oldest = self.entries.iter()
+                .min_by_key(|(_, (_, used))| *used)
+                .map(|(k, _)| k.clone());
+            if let Some(oldest) = oldest {
+                self.entries.remove(&oldest);
+            }
+        }
+        let now = self.tick();
+        self.entries.insert(key.clone(), (entry.clone(), now));
+        Ok(())
+    }
+}
+
+/// A store keeping one text file per entry in a directory.
+#[derive(Clone,Debug)]
+pub struct DirectoryStore {
+    root: PathBuf
+}
+
+impl DirectoryStore {
+    /// Use `root` as the cache directory, creating it if needed.
+    pub fn new<P: Into<PathBuf>>(root: P) -> std::io::Result<Self> {
+        let root = root.into();
+        std::fs::create_dir_all(&root)?;
+        Ok(Self{ root })
+    }
+
+    // The operation is hex encoded, since any escaping of names that are
+    // not valid file names would make some distinct operations collide
+    fn path(&self, key: &Key) -> PathBuf {
+        let operation: String = key.operation.bytes().map(|b| format!("{:02x}", b)).collect();
+        self.root.join(format!("{}-{:016x}", operation, key.fingerprint))
+    }
+}
+
+/// Each file holds the operation, the input and the output, one per line;
+/// a file for another operation is a miss.
+impl Store for DirectoryStore {
+    fn load(&mut self, key: &Key) -> Option<Entry> {
+        let text = std::fs::read_to_string(self.path(key)).ok()?;
+        let mut lines = text.lines();
+        if lines.next()? != key.operation {
+            return None;
+        }
+        let input = lines.next()?.parse().ok()?;
+        let output = lines.next()?.parse().ok()?;
+        Some(Entry{ input, output })
+    }
+
+    fn save(&mut self, key: &Key, entry: &Entry) -> std::io::Result<()> {
+        std::fs::write(self.path(key), format!("{}\n{}\n{}\n", key.operation, entry.input, entry.output))
+    }
+}
+
+pub struct Cache<S: Store> {
+    store: S
+}
+
+impl<S: Store> Cache<S> {
+    pub fn new(store: S) -> Self {
+        Self{ store }
+    }
+
+    pub fn store(&self) -> &S {
+        &self.store
+    }
+
+    pub fn into_inner(self) -> S {
+        self.store
+    }
+
+    /// Return the cached result of `operation` on `input`, or compute it
+    /// with `f` and remember it.
+    ///
+    /// Caching is best-effort: a store that fails to save does not stop the
+    /// computed value from being returned.
+    pub fn get_or_compute<F>(&mut self, operation: &str, input: &Natural, f: F) -> Natural
+        where F: FnOnce(&Natural) -> Natural
+    {
+        let key = Key{ operation: operation.to_string(), fingerprint: input.fingerprint() };
+        if let Some(entry) = self.store.load(&key) {
+            if entry.input == *input {
+                return entry.output;
+            }
+        }
+
+        let output = f(input);
+        let _ = self.store.save(&key, &Entry{ input: input.clone(), output: output.clone() });
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn n(s: &str) -> Natural {
+        s.parse().unwrap()
+    }
+
+    fn square(x: &Natural) -> Natural {
+        x.clone() * x.clone()
+    }
+
+    #[test]
+    fn memoizes() {
+        let mut cache = Cache::new(MemoryLru::new(8));
+        let mut calls = 0;
+        for _ in 0..3 {
+            let y = cache.get_or_compute("square", &n("12"), |x| { calls += 1; square(x) });
+            assert_eq!(y, n("144"));
+        }
+        assert_eq!(calls, 1);
+    }
+
+    #[test]
+    fn evicts_least_recent() {
+        let mut cache = Cache::new(MemoryLru::new(2));
+        cache.get_or_compute("square", &n("1"), square);
+        cache.get_or_compute("square", &n("2"), square);
+        cache.get_or_compute("square", &n("1"), square);
+        cache.get_or_compute("square", &n("3"), square);
+        let mut store = cache.into_inner();
+        assert_eq!(store.len(), 2);
+        let key = |s: &str| Key{ operation: "square".to_string(), fingerprint: n(s).fingerprint() };
+        assert!(store.load(&key("1")).is_some());
+        assert!(store.load(&key("2")).is_none());
+    }
+
+    #[test]
+    fn ignores_collisions() {
+        let mut store = MemoryLru::new(2);
+        let key = Key{ operation: "square".to_string(), fingerprint: n("5").fingerprint() };
+        store.save(&key, &Entry{ input: n("6"), output: n("36") }).unwrap();
+        let mut cache = Cache::new(store);
+        assert_eq!(cache.get_or_compute("square", &n("5"), square), n("25"));
+    }
+
+    #[test]
+    fn directory() {
+        let root = std::env::temp_dir().join(format!("bignum-cache-{}", std::process::id()));
+        let mut cache = Cache::new(DirectoryStore::new(&root).unwrap());
+        cache.get_or_compute("square", &n("99"), square);
+
+        let mut reopened = Cache::new(DirectoryStore::new(&root).unwrap());
+        let y = reopened.get_or_compute("square", &n("99"), |_| panic!("not cached"));
+        assert_eq!(y, n("9801"));
+        std::fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn directory_operations_stay_apart() {
+        let root = std::env::temp_dir().join(format!("bignum-cache-ops-{}", std::process::id()));
+        let mut cache = Cache::new(DirectoryStore::new(&root).unwrap());
+        assert_eq!(cache.get_or_compute("a.b", &n("7"), square), n("49"));
+        assert_eq!(cache.get_or_compute("a_b", &n("7"), |x| x.clone() + n("1")), n("8"));
+        assert_eq!(cache.get_or_compute("a.b", &n("7"), |_| panic!("not cached")), n("49"));
+        assert_eq!(cache.get_or_compute("a_b", &n("7"), |_| panic!("not cached")), n("8"));
+        std::fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,75 @@
+//! Threshold Calibration
+//!
+//! Times the competing algorithms on this machine and finds where each
+//! faster one starts to win. Run `cargo run --release --example calibrate`
+//! and load its output with `str::parse::<Thresholds>`.
+
+use crate::natural::Natural;
+use crate::thresholds::Thresholds;
+use std::time::{Duration, Instant};
+
+const RUNS: usize = 5;
+const LENGTHS: [usize; 12] = [8, 12, 16, 24, 32, 48, 64, 96, 128, 192, 256, 384];
+
+// A length-digit number of non-repeating digits
+fn operand(length: usize, seed: u64) -> Natural {
+    let mut state = seed;
+    let digits = (0..length)
+        .map(|_| {
+            state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            (1 + ((state >> 33) % 9) as u8).try_into().unwrap()
+        })
+        .collect();
+    Natural::from_digits(digits)
+}
+
+// The best of several runs, which is the least disturbed by noise
+fn fastest(f: impl Fn() -> Natural) -> Duration {
+    (0..RUNS)
+        .map(|_| {
+            let start = Instant::now();
+            std::hint::black_box(f());
+            start.elapsed()
+        })
+        .min()
+        .unwrap()
+}
+
+/// The shortest length at which one level of Karatsuba beats schoolbook
+/// multiplication, or the longest length tried if it never does.
+pub fn karatsuba() -> usize {
+    for length in LENGTHS {
+        let (a, b) = (operand(length, 1), operand(length, 2));
+        let schoolbook = fastest(|| a.mul_schoolbook(&b));
+        // Splitting once, with schoolbook for the halves
+        let karatsuba = fastest(|| a.mul_karatsuba(&b, length));
+        if karatsuba < schoolbook {
+            return length;
+        }
+    }
+    LENGTHS[LENGTHS.len() - 1]
+}
+
+/// Thresholds tuned for this machine. Takes well under a second in a
+/// release build, so the half-GCD crossover, thousands of digits out,
+/// keeps its default.
+pub fn calibrate() -> Thresholds {
+    Thresholds{ karatsuba: karatsuba(), ..Thresholds::default() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn in_range() {
+        let t = calibrate();
+        assert!(LENGTHS.contains(&t.karatsuba));
+    }
+
+    #[test]
+    fn operands() {
+        assert_eq!(operand(40, 7).to_string().len(), 40);
+        assert_ne!(operand(40, 7), operand(40, 8));
+    }
+}
@@ -0,0 +1,102 @@
+//! Compact Difficulty Targets
+//!
+//! Bitcoin block headers store their proof-of-work target in 32 bits as
+//! `nBits`: a one-byte length in bytes, then a three-byte mantissa holding
+//! the most significant bytes, whose top bit is a sign that valid targets
+//! never set. Encoding a value keeps only those three bytes, so it
+//! truncates, exactly as Bitcoin Core's `GetCompact` does.
+
+use crate::error::CompactTargetError;
+use crate::natural::Natural;
+
+const SIGN: u32 = 0x0080_0000;
+
+impl Natural {
+    pub fn from_compact_target(bits: u32) -> Result<Natural, CompactTargetError> {
+        let size = (bits >> 24) as usize;
+        let mut mantissa = bits & 0x007f_ffff;
+        // A short size drops the low bytes of the mantissa before the sign
+        // is looked at, as in Bitcoin Core, so 0x01803456 is zero
+        if size <= 3 {
+            mantissa >>= 8 * (3 - size);
+        }
+        if mantissa != 0 && bits & SIGN != 0 {
+            return Err(CompactTargetError::Negative);
+        }
+        let mut bytes = mantissa.to_be_bytes()[1..].to_vec();
+        if size > 3 {
+            bytes.resize(size, 0);
+        }
+        Ok(Natural::from_bytes_be(&bytes))
+    }
+
+    pub fn to_compact_target(&self) -> Result<u32, CompactTargetError> {
+        let mut bytes = self.to_bytes_be();
+        let mut size = bytes.len();
+        bytes.resize(3, 0);
+        let mut mantissa = bytes.iter().fold(0_u32, |acc, b| (acc << 8) | u32::from(*b));
+        // Move a set top bit out of the way of the sign
+        if mantissa & SIGN != 0 {
+            mantissa >>= 8;
+            size += 1;
+        }
+        let size = u8::try_from(size).map_err(|_| CompactTargetError::Overflow)?;
+        Ok((u32::from(size) << 24) | mantissa)
+    }
+
+    /// Whether this value, typically a block hash read as a big-endian
+    /// number, is at or below the target that `bits` encodes.
+    pub fn meets_compact_target(&self, bits: u32) -> Result<bool, CompactTargetError> {
+        Ok(*self <= Natural::from_compact_target(bits)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex(s: &str) -> Natural {
+        let bytes: Vec<u8> = (0..s.len()).step_by(2)
+            .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+            .collect();
+        Natural::from_bytes_be(&bytes)
+    }
+
+    // The cases from Bitcoin Core's arith_uint256 tests
+    #[test]
+    fn bitcoin_core_vectors() {
+        let cases = [
+            (0x0000_0000, "00", 0x0000_0000),
+            (0x0012_3456, "00", 0x0000_0000),
+            (0x0100_3456, "00", 0x0000_0000),
+            (0x0180_3456, "00", 0x0000_0000),
+            (0x0200_0056, "00", 0x0000_0000),
+            (0x0112_3456, "12", 0x0112_0000),
+            (0x0212_3456, "1234", 0x0212_3400),
+            (0x0312_3456, "123456", 0x0312_3456),
+            (0x0412_3456, "12345600", 0x0412_3456),
+            (0x0500_9234, "92340000", 0x0500_9234)
+        ];
+        for (bits, target, round_trip) in cases {
+            let value = Natural::from_compact_target(bits).unwrap();
+            assert_eq!(value, hex(target));
+            assert_eq!(value.to_compact_target(), Ok(round_trip));
+        }
+    }
+
+    #[test]
+    fn genesis_block() {
+        let target = Natural::from_compact_target(0x1d00_ffff).unwrap();
+        assert_eq!(target, hex(&format!("ffff{}", "00".repeat(26))));
+        let hash = hex("000000000019d6689c085ae165831e934ff763ae46a2a6c172b3f1b60a8ce26f");
+        assert_eq!(hash.meets_compact_target(0x1d00_ffff), Ok(true));
+        assert_eq!(target.clone().add_small(1).meets_compact_target(0x1d00_ffff), Ok(false));
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(Natural::from_compact_target(0x0492_3456), Err(CompactTargetError::Negative));
+        assert_eq!(Natural::from_compact_target(0x01fe_dcba), Err(CompactTargetError::Negative));
+        assert_eq!(Natural::power_of_ten(700).to_compact_target(), Err(CompactTargetError::Overflow));
+    }
+}
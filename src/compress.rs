@@ -0,0 +1,144 @@
+//! Run-length Compressed Storage
+//!
+//! A `CompressedNatural` stores its digits as runs of a repeated digit, so
+//! values near a power of ten take space in proportion to their distance
+//! from it rather than their length: 10^n + d is the digits of d and one
+//! run of zeros, and 10^n - d is one run of nines and the digits of its
+//! complement. Arithmetic decompresses, computes on `Natural` and
+//! compresses the result again.
+
+use crate::digit::Digit;
+use crate::natural::Natural;
+
+#[derive(Clone,Debug,PartialEq,Eq)]
+pub struct CompressedNatural {
+    // (digit, count) from the least significant end, adjacent runs always
+    // different and no trailing run of zeros, so zero has no runs
+    runs: Vec<(Digit, usize)>
+}
+
+impl CompressedNatural {
+    /// The (digit, run length) pairs, least significant first.
+    pub fn runs(&self) -> &[(Digit, usize)] {
+        &self.runs
+    }
+
+    /// The number of decimal digits represented.
+    pub fn digit_count(&self) -> usize {
+        self.runs.iter().map(|r| r.1).sum()
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.runs.is_empty()
+    }
+
+    pub fn to_natural(&self) -> Natural {
+        let mut digits = Vec::with_capacity(self.digit_count());
+        for (digit, count) in &self.runs {
+            digits.extend(std::iter::repeat_n(*digit, *count));
+        }
+        Natural::from_digits(digits)
+    }
+}
+
+impl From<&Natural> for CompressedNatural {
+    fn from(n: &Natural) -> Self {
+        let mut runs: Vec<(Digit, usize)> = vec![];
+        for d in &n.digits[..n.significant_len()] {
+            match runs.last_mut() {
+                Some((digit, count)) if digit == d => *count += 1,
+                _ => runs.push((*d, 1))
+            }
+        }
+        Self{ runs }
+    }
+}
+
+impl From<CompressedNatural> for Natural {
+    fn from(c: CompressedNatural) -> Self {
+        c.to_natural()
+    }
+}
+
+impl std::fmt::Display for CompressedNatural {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_natural())
+    }
+}
+
+impl PartialOrd for CompressedNatural {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for CompressedNatural {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.to_natural().cmp(&other.to_natural())
+    }
+}
+
+impl std::ops::Add for CompressedNatural {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self::Output {
+        Self::from(&(self.to_natural() + other.to_natural()))
+    }
+}
+
+impl std::ops::Sub for CompressedNatural {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self::Output {
+        Self::from(&(self.to_natural() - other.to_natural()))
+    }
+}
+
+impl std::ops::Mul for CompressedNatural {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self::Output {
+        Self::from(&(self.to_natural() * other.to_natural()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn n(s: &str) -> Natural {
+        s.parse().unwrap()
+    }
+
+    fn near(exponent: usize) -> Natural {
+        Natural::power_of_ten(exponent)
+    }
+
+    #[test]
+    fn runs() {
+        let c = CompressedNatural::from(&(near(1000) + n("7")));
+        assert_eq!(c.runs(), &[(Digit::Seven, 1), (Digit::Zero, 999), (Digit::One, 1)]);
+        assert_eq!(c.digit_count(), 1001);
+        let c = CompressedNatural::from(&(near(1000) - n("12")));
+        assert_eq!(c.runs(), &[(Digit::Eight, 2), (Digit::Nine, 998)]);
+    }
+
+    #[test]
+    fn round_trip() {
+        for s in ["0", "7", "1000000000000", "999999999999999990000001"] {
+            let x = n(s);
+            assert_eq!(CompressedNatural::from(&x).to_natural(), x);
+        }
+        assert!(CompressedNatural::from(&Natural::zero()).is_zero());
+    }
+
+    #[test]
+    fn arithmetic() {
+        let a = CompressedNatural::from(&near(50));
+        let b = CompressedNatural::from(&n("3"));
+        assert_eq!((a.clone() - b.clone()).runs(), &[(Digit::Seven, 1), (Digit::Nine, 49)]);
+        assert_eq!((a.clone() + b.clone()).to_natural(), near(50) + n("3"));
+        assert_eq!((a.clone() * b.clone()).runs(), &[(Digit::Zero, 50), (Digit::Three, 1)]);
+        assert!(b < a);
+    }
+}
@@ -0,0 +1,190 @@
+//! Congruences
+//!
+//! Solving `a x ≡ b (mod m)`, and rational reconstruction: recovering a
+//! fraction `p / q` from its image `p q^-1 mod m`, as exact linear algebra
+//! and Chinese remainder methods need once they have worked modulo a large
+//! enough `m`. Both are Euclid's algorithm underneath. Hensel lifting
+//! turns a root of a polynomial mod `p` into one mod `p^k`, the first `k`
+//! digits of a p-adic root.
+
+use crate::integer::{Integer, Sign};
+use crate::natural::{half_gcd, Natural};
+use crate::rational::Rational;
+use crate::thresholds::Thresholds;
+
+/// The solutions of `a x ≡ b (mod m)` as `(x, n)`: every `x + k n`, with
+/// `x` in `0..n`. When `g = gcd(a, m)` is more than one, `n` is `m / g`.
+/// `None` if there are no solutions, which is when `g` does not divide
+/// `b` or `m` is zero.
+pub fn solve_linear_congruence(a: &Natural, b: &Natural, m: &Natural) -> Option<(Natural, Natural)> {
+    if *m == Natural::zero() {
+        return None;
+    }
+    let g = a.gcd(m);
+    let (b, r) = b.div_rem(&g);
+    if r != Natural::zero() {
+        return None;
+    }
+    let n = m / &g;
+    let inverse = (a / &g).mod_inverse(&n)?;
+    Some(((b * inverse).div_rem(&n).1, n))
+}
+
+/// The fraction `p / q` with `p q^-1 ≡ value (mod modulus)` and both `|p|`
+/// and `q` at most `sqrt(modulus / 2)`, which is unique when it exists.
+/// `None` if there is no such fraction, in which case a larger modulus is
+/// needed.
+///
+/// Panics if `modulus` is zero.
+pub fn rational_reconstruct(value: &Natural, modulus: &Natural) -> Option<Rational> {
+    reconstruct_with_cutoff(value, modulus, Thresholds::current().half_gcd)
+}
+
+fn reconstruct_with_cutoff(value: &Natural, modulus: &Natural, cutoff: usize) -> Option<Rational> {
+    // Within the bound is 2 x^2 <= modulus, which needs no square root,
+    // and no squaring either while x has too many digits
+    let within = |x: &Natural| x.digits.len() * 2 <= modulus.digits.len() + 1 && x * x * 2u32 <= *modulus;
+    let (mut r0, mut r1) = (modulus.clone(), value.div_rem(modulus).1);
+    let (mut t0, mut t1) = (Integer::zero(), Integer::one());
+    if r1.digits.len() >= cutoff {
+        // Every remainder within the bound is below 10^s, so half-GCD steps
+        // stop at or before the first, leaving Euclid a digit or two to go.
+        // The cofactors are the matrix's first row, signed by the parity
+        let s = modulus.digits.len().div_ceil(2);
+        let (steps, (a, b)) = half_gcd(r0, r1, s);
+        let odd = steps.steps() % 2 == 1;
+        let [m00, m01, ..] = steps.matrix;
+        let sign = |odd| if odd { Sign::Negative } else { Sign::Positive };
+        (r0, r1) = (a, b);
+        (t0, t1) = (Integer::new(sign(!odd), m01), Integer::new(sign(odd), m00));
+    }
+    while !within(&r1) {
+        let (q, r) = r0.div_rem(&r1);
+        (r0, r1) = (r1, r);
+        (t0, t1) = (t1.clone(), t0 - Integer::from(q) * t1);
+    }
+    if t1 == Integer::zero() || !within(t1.magnitude()) || r1.gcd(t1.magnitude()) != Natural::one() {
+        return None;
+    }
+    let p = Integer::from(r1).with_sign(t1.sign());
+    Some(Rational::new(p, t1.abs()))
+}
+
+// f(x) mod m, by Horner's rule with every partial value reduced
+fn evaluate(coefficients: &[Integer], x: &Natural, m: &Natural) -> Natural {
+    let m = Integer::from(m.clone());
+    let x = Integer::from(x.clone());
+    coefficients.iter().rev()
+        .fold(Integer::zero(), |acc, c| (acc * x.clone() + c.clone()).rem_euclid(&m))
+        .abs()
+}
+
+/// The root of `f` mod `p^k` that is congruent to `root` mod `p`, where
+/// `f_coeffs[i]` is the coefficient of `x^i`. Newton's method doubles the
+/// number of correct p-adic digits with each step.
+///
+/// `None` unless `root` is a simple root mod `p`: `f(root) ≡ 0` but
+/// `f'(root) ≢ 0`. A root where the derivative vanishes may lift to many
+/// roots or to none.
+pub fn hensel_lift(f_coeffs: &[Integer], root: &Natural, p: &Natural, k: usize) -> Option<Natural> {
+    let derivative: Vec<Integer> = f_coeffs.iter().enumerate().skip(1)
+        .map(|(i, c)| c.clone() * Integer::from(Natural::from(i)))
+        .collect();
+    let mut r = root.div_rem(p).1;
+    if evaluate(f_coeffs, &r, p) != Natural::zero() {
+        return None;
+    }
+    evaluate(&derivative, &r, p).mod_inverse(p)?;
+    let mut digits = 1;
+    while digits < k {
+        digits = (digits * 2).min(k);
+        let m = p.pow(digits);
+        // f'(r) is a unit mod p, so it is one mod every power of p
+        let inverse = evaluate(&derivative, &r, &m).mod_inverse(&m)?;
+        let step = evaluate(f_coeffs, &r, &m) * inverse % &m;
+        r = (r + &m - step) % &m;
+    }
+    Some(r)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn n(s: &str) -> Natural {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn linear_congruences() {
+        assert_eq!(solve_linear_congruence(&n("3"), &n("2"), &n("7")), Some((n("3"), n("7"))));
+        // gcd(14, 100) = 2, leaving 7x = 15 (mod 50)
+        assert_eq!(solve_linear_congruence(&n("14"), &n("30"), &n("100")), Some((n("45"), n("50"))));
+        assert_eq!(solve_linear_congruence(&n("2"), &n("1"), &n("4")), None);
+        assert_eq!(solve_linear_congruence(&n("0"), &n("0"), &n("5")), Some((n("0"), n("1"))));
+        assert_eq!(solve_linear_congruence(&n("0"), &n("3"), &n("5")), None);
+        assert_eq!(solve_linear_congruence(&n("3"), &n("2"), &n("0")), None);
+        let m = n("170141183460469231731687303715884105727");
+        let (x, modulus) = solve_linear_congruence(&n("123456789123456789"), &n("42"), &m).unwrap();
+        assert_eq!(modulus, m);
+        assert_eq!(x * n("123456789123456789") % m, n("42"));
+    }
+
+    #[test]
+    fn reconstruction() {
+        let q = |s: &str| s.parse::<Rational>().unwrap();
+        // 2/3 and -1/2 mod 101
+        assert_eq!(rational_reconstruct(&n("68"), &n("101")), Some(q("2/3")));
+        assert_eq!(rational_reconstruct(&n("50"), &n("101")), Some(q("-1/2")));
+        assert_eq!(rational_reconstruct(&n("0"), &n("101")), Some(Rational::zero()));
+        assert_eq!(rational_reconstruct(&n("102"), &n("101")), Some(Rational::one()));
+        // 8/1 is past the bound of sqrt(101 / 2), and nothing else fits
+        assert_eq!(rational_reconstruct(&n("8"), &n("101")), None);
+        // 123456789/987654321, or 13717421/109739369 in lowest terms, mod
+        // 2^127 - 1
+        let image = n("121411321024963674534298188953559720328");
+        let p = n("170141183460469231731687303715884105727");
+        assert_eq!(rational_reconstruct(&image, &p), Some(q("13717421/109739369")));
+    }
+
+    #[test]
+    fn reconstruction_by_half_gcd() {
+        let p = Natural::from(7u32).pow(600);
+        let q = Natural::from(3u32).pow(800);
+        let m = Natural::from(10u32).pow(2000);
+        let image = p.clone() * q.mod_inverse(&m).unwrap() % &m;
+        let expected = Some(Rational::new(Integer::from(p), q));
+        assert_eq!(reconstruct_with_cutoff(&image, &m, 0), expected);
+        assert_eq!(reconstruct_with_cutoff(&(&m - &image), &m, 1000), expected.map(|x| -x));
+        // Most images have some fraction within the bound
+        let other = &image + Natural::one();
+        assert_eq!(reconstruct_with_cutoff(&other, &m, 0), reconstruct_with_cutoff(&other, &m, usize::MAX));
+    }
+
+    #[test]
+    fn hensel_lifting() {
+        let i = |x: i32| Integer::new(if x < 0 { Sign::Negative } else { Sign::Positive }, Natural::from(x.unsigned_abs()));
+        // x^2 - 2, whose roots mod 7 are 3 and 4: the square roots of 2 in
+        // the 7-adic integers
+        let f = [i(-2), i(0), i(1)];
+        let seven = n("7");
+        assert_eq!(hensel_lift(&f, &n("3"), &seven, 1), Some(n("3")));
+        assert_eq!(hensel_lift(&f, &n("3"), &seven, 2), Some(n("10")));
+        let m = seven.pow(40);
+        for root in ["3", "4", "11"] {
+            let r = hensel_lift(&f, &n(root), &seven, 40).unwrap();
+            assert!(r < m);
+            assert_eq!(&r % &seven, n(root) % &seven);
+            assert_eq!(&r * &r % &m, n("2"));
+        }
+        let (a, b) = (hensel_lift(&f, &n("3"), &seven, 40).unwrap(), hensel_lift(&f, &n("4"), &seven, 40).unwrap());
+        assert_eq!(a + b, m);
+        // 2 is not a root, and x^2 has the repeated root 0
+        assert_eq!(hensel_lift(&f, &n("2"), &seven, 5), None);
+        assert_eq!(hensel_lift(&[i(0), i(0), i(1)], &n("0"), &seven, 5), None);
+        // A cube root of 10 mod 11^20, from x^3 - 10 and the root -1 mod 11
+        let cube = hensel_lift(&[i(-10), i(0), i(0), i(1)], &n("10"), &n("11"), 20).unwrap();
+        let m = n("11").pow(20);
+        assert_eq!(cube.pow(3) % &m, n("10"));
+    }
+}
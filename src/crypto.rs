@@ -0,0 +1,141 @@
+//! Cryptography Demos
+//!
+//! Random primes and textbook RSA, for teaching and for testing the
+//! arithmetic on realistic workloads. Nothing here is fit to protect real
+//! data: the RSA has no padding, and `SplitMix64` is not a cryptographic
+//! random source.
+
+use crate::natural::Natural;
+use crate::random::RandomSource;
+
+// Extra Miller–Rabin rounds for random candidates, for a false positive
+// rate below 4^-32
+const ROUNDS: usize = 32;
+
+/// The size of prime for `random_prime` to find.
+#[derive(Clone,Copy,Debug,PartialEq,Eq)]
+pub enum PrimeSize {
+    /// Exactly this many bits, the highest of them set
+    Bits(usize),
+    /// Exactly this many decimal digits, the first of them nonzero
+    Digits(usize)
+}
+
+// A uniformly random candidate of the given size, odd unless it is tiny
+fn candidate(size: PrimeSize, rng: &mut impl RandomSource) -> Natural {
+    match size {
+        PrimeSize::Bits(bits) => {
+            let mut bytes: Vec<u8> = (0..bits.div_ceil(8)).map(|_| rng.next_u64() as u8).collect();
+            let top = bytes.len() - 1;
+            let high = (bits - 1) % 8;
+            bytes[top] &= (1u16 << (high + 1)).wrapping_sub(1) as u8;
+            bytes[top] |= 1 << high;
+            if bits > 2 {
+                bytes[0] |= 1;
+            }
+            Natural::from_bytes_le(&bytes)
+        }
+        PrimeSize::Digits(count) => {
+            let mut digits: Vec<u8> = (0..count).map(|_| (rng.next_u64() % 10) as u8).collect();
+            digits[count - 1] = 1 + (rng.next_u64() % 9) as u8;
+            if count > 1 {
+                digits[0] |= 1;
+            }
+            Natural::from_digits(digits.into_iter().map(|d| d.try_into().unwrap()).collect())
+        }
+    }
+}
+
+/// A random prime of the given size, drawing candidates from `rng` until
+/// one passes `is_probable_prime` with 32 extra rounds.
+///
+/// Panics for sizes with no primes: fewer than two bits or one digit.
+pub fn random_prime(size: PrimeSize, rng: &mut impl RandomSource) -> Natural {
+    match size {
+        PrimeSize::Bits(bits) => assert!(bits >= 2, "no primes below 2 bits"),
+        PrimeSize::Digits(count) => assert!(count >= 1, "no primes with no digits")
+    }
+    loop {
+        let n = candidate(size, rng);
+        if n.is_probable_prime(ROUNDS) {
+            return n;
+        }
+    }
+}
+
+/// A textbook RSA key pair with the public exponent 65537.
+#[derive(Clone,Debug,PartialEq,Eq)]
+pub struct RsaKeyPair {
+    pub modulus: Natural,
+    pub public_exponent: Natural,
+    pub private_exponent: Natural
+}
+
+impl RsaKeyPair {
+    /// A key pair whose modulus is the product of two distinct random
+    /// primes of `bits / 2` bits each.
+    ///
+    /// Panics if `bits` is below 4.
+    pub fn generate(bits: usize, rng: &mut impl RandomSource) -> Self {
+        assert!(bits >= 4, "no RSA moduli below 4 bits");
+        let public_exponent = Natural::from(65537u32);
+        loop {
+            let p = random_prime(PrimeSize::Bits(bits / 2), rng);
+            let q = random_prime(PrimeSize::Bits(bits - bits / 2), rng);
+            if p == q {
+                continue;
+            }
+            let phi = (&p - Natural::one()) * (&q - Natural::one());
+            // The exponent is prime, so this fails only when it divides phi
+            if let Some(private_exponent) = public_exponent.mod_inverse(&phi) {
+                return Self{ modulus: p * q, public_exponent, private_exponent };
+            }
+        }
+    }
+
+    /// Panics if `message` is not below the modulus.
+    pub fn encrypt(&self, message: &Natural) -> Natural {
+        assert!(*message < self.modulus, "message is not below the modulus");
+        message.mod_pow(&self.public_exponent, &self.modulus)
+    }
+
+    pub fn decrypt(&self, ciphertext: &Natural) -> Natural {
+        ciphertext.mod_pow(&self.private_exponent, &self.modulus)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::random::SplitMix64;
+
+    #[test]
+    fn random_primes() {
+        let mut rng = SplitMix64::new(266);
+        for bits in [2, 3, 8, 64] {
+            let p = random_prime(PrimeSize::Bits(bits), &mut rng);
+            assert!(p.is_probable_prime(0));
+            assert_eq!(p.to_power_of_two_le(1).len(), bits);
+        }
+        for digits in [1, 2, 20] {
+            let p = random_prime(PrimeSize::Digits(digits), &mut rng);
+            assert!(p.is_probable_prime(0));
+            assert_eq!(p.to_string().len(), digits);
+        }
+    }
+
+    #[test]
+    fn rsa_demo() {
+        let mut rng = SplitMix64::new(42);
+        for bits in [4, 80] {
+            let keys = RsaKeyPair::generate(bits, &mut rng);
+            let message = keys.modulus.div_rem_small(3).0;
+            let ciphertext = keys.encrypt(&message);
+            assert_eq!(keys.decrypt(&ciphertext), message);
+        }
+        let keys = RsaKeyPair::generate(80, &mut rng);
+        let message: Natural = "123456789".parse().unwrap();
+        assert_ne!(keys.encrypt(&message), message);
+        assert_eq!(keys.decrypt(&keys.encrypt(&message)), message);
+    }
+}
@@ -0,0 +1,120 @@
+//! Stable Fingerprints
+
+use crate::natural::Natural;
+
+/// A byte-oriented hash function, such as SHA-256 from another crate.
+pub trait Digest {
+    fn update(&mut self, bytes: &[u8]);
+}
+
+/// 64-bit FNV-1a, a small dependency-free `Digest` for fingerprints.
+///
+/// This is not a cryptographic hash.
+#[derive(Clone,Debug)]
+pub struct Fnv1a64 {
+    state: u64
+}
+
+impl Default for Fnv1a64 {
+    fn default() -> Self {
+        Self{ state: 0xcbf29ce484222325 }
+    }
+}
+
+impl Fnv1a64 {
+    pub fn finish(&self) -> u64 {
+        self.state
+    }
+}
+
+impl Digest for Fnv1a64 {
+    fn update(&mut self, bytes: &[u8]) {
+        for b in bytes {
+            self.state ^= u64::from(*b);
+            self.state = self.state.wrapping_mul(0x100000001b3);
+        }
+    }
+}
+
+impl std::hash::Hash for Natural {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        let len = self.significant_len();
+        for d in &self.digits[..len] {
+            state.write_u8(d.as_u8());
+        }
+        state.write_usize(len);
+    }
+}
+
+const CHUNK: usize = 4096;
+
+impl Natural {
+    /// Feed a platform-independent encoding of this value into `hasher`.
+    ///
+    /// The encoding is the digit count as a little-endian u64 followed by
+    /// the decimal digits in ASCII, most significant first, written in
+    /// chunks so huge values never need a full copy.
+    pub fn digest<D: Digest>(&self, hasher: &mut D) {
+        let len = self.significant_len().max(1);
+        hasher.update(&(len as u64).to_le_bytes());
+        let mut buffer = Vec::with_capacity(CHUNK);
+        for d in self.digits[..len].iter().rev() {
+            buffer.push(b'0' + d.as_u8());
+            if buffer.len() == CHUNK {
+                hasher.update(&buffer);
+                buffer.clear();
+            }
+        }
+        hasher.update(&buffer);
+    }
+
+    /// A stable 64-bit FNV-1a fingerprint of this value.
+    pub fn fingerprint(&self) -> u64 {
+        let mut hasher = Fnv1a64::default();
+        self.digest(&mut hasher);
+        hasher.finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Collect(Vec<u8>);
+
+    impl Digest for Collect {
+        fn update(&mut self, bytes: &[u8]) {
+            self.0.extend_from_slice(bytes);
+        }
+    }
+
+    #[test]
+    fn encoding() {
+        let x: Natural = "1024".parse().unwrap();
+        let mut c = Collect(vec![]);
+        x.digest(&mut c);
+        assert_eq!(c.0, b"\x04\0\0\0\0\0\0\x001024");
+    }
+
+    #[test]
+    fn chunked() {
+        let x: Natural = "7".repeat(10000).parse().unwrap();
+        let mut c = Collect(vec![]);
+        x.digest(&mut c);
+        assert_eq!(c.0.len(), 8 + 10000);
+    }
+
+    #[test]
+    fn stable_fingerprint() {
+        let x: Natural = "1099511627776".parse().unwrap();
+        assert_eq!(x.fingerprint(), 0x0a11b9887c487df7);
+    }
+
+    #[test]
+    fn hash_set() {
+        let mut set = std::collections::HashSet::new();
+        set.insert("123".parse::<Natural>().unwrap());
+        assert!(set.contains(&"123".parse::<Natural>().unwrap()));
+        assert!(!set.contains(&"124".parse::<Natural>().unwrap()));
+    }
+}
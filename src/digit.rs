@@ -1,5 +1,7 @@
 //! Single Digit Decimal Arithmetic
 
+use crate::error::TryFromDigitError;
+
 #[derive(Clone,Copy,Debug,Default,PartialEq,Eq,PartialOrd,Ord)]
 pub enum Digit {
     #[default]
@@ -113,7 +115,7 @@ impl std::ops::Mul for Digit {
 }
 
 impl std::convert::TryFrom<char> for Digit {
-    type Error = &'static str;
+    type Error = TryFromDigitError;
 
     fn try_from(v: char) -> Result<Self, Self::Error> {
         match v {
@@ -127,7 +129,7 @@ impl std::convert::TryFrom<char> for Digit {
             '7' => Ok(Self::Seven),
             '8' => Ok(Self::Eight),
             '9' => Ok(Self::Nine),
-            _ => Err("not a digit")
+            _ => Err(TryFromDigitError::InvalidCharacter(v))
         }
     }
 }
@@ -138,8 +140,33 @@ impl std::fmt::Display for Digit {
     }
 }
 
+// The zero of every run of ten decimal digits (general category Nd) in
+// Unicode 15, in order. Each run holds the digits 0 to 9 consecutively.
+const UNICODE_ZEROS: [u32; 68] = [
+    0x30, 0x660, 0x6F0, 0x7C0, 0x966, 0x9E6, 0xA66, 0xAE6, 0xB66, 0xBE6, 0xC66,
+    0xCE6, 0xD66, 0xDE6, 0xE50, 0xED0, 0xF20, 0x1040, 0x1090, 0x17E0, 0x1810,
+    0x1946, 0x19D0, 0x1A80, 0x1A90, 0x1B50, 0x1BB0, 0x1C40, 0x1C50, 0xA620,
+    0xA8D0, 0xA900, 0xA9D0, 0xA9F0, 0xAA50, 0xABF0, 0xFF10, 0x104A0, 0x10D30,
+    0x11066, 0x110F0, 0x11136, 0x111D0, 0x112F0, 0x11450, 0x114D0, 0x11650,
+    0x116C0, 0x11730, 0x118E0, 0x11950, 0x11C50, 0x11D50, 0x11DA0, 0x11F50,
+    0x16A60, 0x16AC0, 0x16B50, 0x1D7CE, 0x1D7D8, 0x1D7E2, 0x1D7EC, 0x1D7F6,
+    0x1E140, 0x1E2F0, 0x1E4F0, 0x1E950, 0x1FBF0
+];
+
 impl Digit {
-    fn as_u8(&self) -> u8 {
+    /// The value of any Unicode decimal digit, such as '٣' (Arabic-Indic),
+    /// '३' (Devanagari) or '３' (full width), as well as ASCII. Other
+    /// numeric characters, such as '½' or '²', are not digits.
+    pub fn from_unicode(c: char) -> Result<Self, TryFromDigitError> {
+        let code = u32::from(c);
+        let run = UNICODE_ZEROS.partition_point(|zero| *zero <= code);
+        match run.checked_sub(1).map(|i| code - UNICODE_ZEROS[i]) {
+            Some(value) if value < 10 => Ok((value as u8).try_into().unwrap()),
+            _ => Err(TryFromDigitError::InvalidCharacter(c))
+        }
+    }
+
+    pub(crate) fn as_u8(&self) -> u8 {
         match self {
             Self::Zero => 0,
             Self::One => 1,
@@ -156,7 +183,7 @@ impl Digit {
 }
 
 impl std::convert::TryFrom<u8> for Digit {
-    type Error = &'static str;
+    type Error = TryFromDigitError;
 
     fn try_from(v: u8) -> Result<Self, Self::Error> {
         match v {
@@ -170,7 +197,7 @@ impl std::convert::TryFrom<u8> for Digit {
             7 => Ok(Self::Seven),
             8 => Ok(Self::Eight),
             9 => Ok(Self::Nine),
-            _ => Err("not a digit")
+            _ => Err(TryFromDigitError::OutOfRange(v))
         }
     }
 }
@@ -187,10 +214,30 @@ mod tests {
 
     #[test]
     fn parse_00000000() {
-        let zero: Digit = (0 as u8).try_into().unwrap();
+        let zero: Digit = 0_u8.try_into().unwrap();
         assert_eq!(zero, Digit::Zero)
     }
 
+    #[test]
+    fn parse_error() {
+        let err = Digit::try_from('x').unwrap_err();
+        assert_eq!(err, TryFromDigitError::InvalidCharacter('x'));
+        assert_eq!(Digit::try_from(10_u8), Err(TryFromDigitError::OutOfRange(10)));
+    }
+
+    #[test]
+    fn unicode_digits() {
+        assert_eq!(Digit::from_unicode('7'), Ok(Digit::Seven));
+        assert_eq!(Digit::from_unicode('\u{663}'), Ok(Digit::Three));
+        assert_eq!(Digit::from_unicode('\u{96F}'), Ok(Digit::Nine));
+        assert_eq!(Digit::from_unicode('\u{FF10}'), Ok(Digit::Zero));
+        assert_eq!(Digit::from_unicode('\u{1D7D9}'), Ok(Digit::One));
+        assert_eq!(Digit::from_unicode('\u{FF1A}'), Err(TryFromDigitError::InvalidCharacter('\u{FF1A}')));
+        assert!(Digit::from_unicode('\u{BD}').is_err());
+        assert!(Digit::from_unicode('/').is_err());
+        assert!(Digit::try_from('\u{663}').is_err());
+    }
+
     #[test]
     fn ordered() {
         assert!(Digit::Zero < Digit::One)
@@ -0,0 +1,157 @@
+//! Spans of Time Beyond `std::time::Duration`
+
+use crate::natural::Natural;
+
+const NANOS_PER_SEC: u32 = 1_000_000_000;
+// A Julian year of 365.25 days, the unit astronomers use
+const HOURS_PER_YEAR: u32 = 8766;
+
+/// A non-negative span of time, counted in nanoseconds.
+#[derive(Clone,Debug,PartialEq,Eq,PartialOrd,Ord)]
+pub struct BigDuration {
+    nanos: Natural
+}
+
+/// A `BigDuration` broken into calendar-free units.
+#[derive(Clone,Debug,PartialEq,Eq)]
+pub struct Components {
+    pub years: Natural,
+    pub days: u32,
+    pub hours: u32,
+    pub minutes: u32,
+    pub seconds: u32,
+    pub nanos: u32
+}
+
+impl BigDuration {
+    pub fn from_nanos(nanos: Natural) -> Self {
+        Self{ nanos }
+    }
+
+    pub fn from_secs(secs: Natural) -> Self {
+        Self{ nanos: secs.mul_small(NANOS_PER_SEC) }
+    }
+
+    pub fn as_nanos(&self) -> &Natural {
+        &self.nanos
+    }
+
+    /// Whole seconds, discarding any fraction.
+    pub fn as_secs(&self) -> Natural {
+        self.nanos.div_rem_small(NANOS_PER_SEC).0
+    }
+
+    /// Split into Julian years (365.25 days), days, hours, minutes, seconds
+    /// and nanoseconds.
+    pub fn components(&self) -> Components {
+        let (secs, nanos) = self.nanos.div_rem_small(NANOS_PER_SEC);
+        let (minutes, seconds) = secs.div_rem_small(60);
+        let (hours, minutes) = minutes.div_rem_small(60);
+        let (years, hours) = hours.div_rem_small(HOURS_PER_YEAR);
+        Components{ years, days: hours / 24, hours: hours % 24, minutes, seconds, nanos }
+    }
+
+    /// Convert to a `std::time::Duration`, if it is short enough to fit.
+    pub fn to_std(&self) -> Option<std::time::Duration> {
+        let (secs, nanos) = self.nanos.div_rem_small(NANOS_PER_SEC);
+        let mut whole: u64 = 0;
+        for d in secs.digits.iter().rev() {
+            whole = whole.checked_mul(10)?.checked_add(u64::from(d.as_u8()))?;
+        }
+        Some(std::time::Duration::new(whole, nanos))
+    }
+}
+
+impl From<std::time::Duration> for BigDuration {
+    fn from(d: std::time::Duration) -> Self {
+        let nanos = Natural::from(d.as_nanos());
+        Self{ nanos }
+    }
+}
+
+impl std::ops::Add for BigDuration {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self::Output {
+        Self{ nanos: self.nanos + other.nanos }
+    }
+}
+
+impl std::ops::AddAssign for BigDuration {
+    fn add_assign(&mut self, other: Self) {
+        self.nanos += other.nanos;
+    }
+}
+
+// Largest unit first, starting from the first nonzero one: "2y 0d 3h 0m 1.5s"
+impl std::fmt::Display for BigDuration {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let c = self.components();
+        let mut started = false;
+        if c.years != Natural::zero() {
+            write!(f, "{}y ", c.years)?;
+            started = true;
+        }
+        for (value, unit) in [(c.days, 'd'), (c.hours, 'h'), (c.minutes, 'm')] {
+            if started || value != 0 {
+                write!(f, "{}{} ", value, unit)?;
+                started = true;
+            }
+        }
+
+        if c.nanos == 0 {
+            write!(f, "{}s", c.seconds)
+        } else {
+            let fraction = format!("{:09}", c.nanos);
+            write!(f, "{}.{}s", c.seconds, fraction.trim_end_matches('0'))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn n(s: &str) -> Natural {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn components() {
+        // 1 Julian year, 2 days, 3 hours, 4 minutes and 5.000000006 seconds
+        let secs = 31557600 + 2 * 86400 + 3 * 3600 + 4 * 60 + 5;
+        let d = BigDuration::from_nanos(n(&format!("{}000000006", secs)));
+        let c = d.components();
+        assert_eq!(c.years, n("1"));
+        assert_eq!((c.days, c.hours, c.minutes, c.seconds, c.nanos), (2, 3, 4, 5, 6));
+        assert_eq!(d.to_string(), "1y 2d 3h 4m 5.000000006s");
+    }
+
+    #[test]
+    fn display_short() {
+        let d = BigDuration::from(std::time::Duration::from_millis(61500));
+        assert_eq!(d.to_string(), "1m 1.5s");
+        assert_eq!(BigDuration::from_nanos(Natural::zero()).to_string(), "0s");
+    }
+
+    #[test]
+    fn std_round_trip() {
+        let std = std::time::Duration::new(123456789, 987654321);
+        assert_eq!(BigDuration::from(std).to_std(), Some(std));
+    }
+
+    #[test]
+    fn beyond_std() {
+        let googol = Natural::power_of_ten(100);
+        let d = BigDuration::from_secs(googol.clone());
+        assert_eq!(d.to_std(), None);
+        assert_eq!(d.as_secs(), googol);
+    }
+
+    #[test]
+    fn add() {
+        let a = BigDuration::from_secs(n("30"));
+        let b = BigDuration::from_secs(n("45"));
+        assert_eq!((a + b).to_string(), "1m 15s");
+    }
+}
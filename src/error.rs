@@ -0,0 +1,403 @@
+//! Error Types
+//!
+//! Every error the crate returns is defined here. The enums are
+//! `#[non_exhaustive]` so that new failure modes can be added without
+//! breaking downstream matches. An error that only a feature module
+//! returns is built only with that feature.
+
+/// Why a `Digit` could not be built from a character or integer.
+#[derive(Clone,Copy,Debug,PartialEq,Eq)]
+#[non_exhaustive]
+pub enum TryFromDigitError {
+    InvalidCharacter(char),
+    OutOfRange(u8)
+}
+
+impl std::fmt::Display for TryFromDigitError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidCharacter(c) => write!(f, "{:?} is not a decimal digit", c),
+            Self::OutOfRange(v) => write!(f, "{} is not a single decimal digit", v)
+        }
+    }
+}
+
+impl std::error::Error for TryFromDigitError {}
+
+/// Why a `Natural` could not be converted to a primitive integer.
+#[derive(Clone,Copy,Debug,PartialEq,Eq)]
+#[non_exhaustive]
+pub enum TryFromNaturalError {
+    TooLarge
+}
+
+impl std::fmt::Display for TryFromNaturalError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::TooLarge => write!(f, "number too large for the target integer type")
+        }
+    }
+}
+
+impl std::error::Error for TryFromNaturalError {}
+
+/// Why base-10^9 chunks could not be read as a `Natural`.
+#[derive(Clone,Copy,Debug,PartialEq,Eq)]
+#[non_exhaustive]
+pub enum FromChunksError {
+    /// A chunk was 10^9 or more
+    OutOfRange(u32)
+}
+
+impl std::fmt::Display for FromChunksError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::OutOfRange(chunk) => write!(f, "chunk {} is not below 10^9", chunk)
+        }
+    }
+}
+
+impl std::error::Error for FromChunksError {}
+
+/// Why a string could not be parsed as a `Natural`.
+#[derive(Clone,Copy,Debug,PartialEq,Eq)]
+#[non_exhaustive]
+pub enum ParseNaturalError {
+    Empty,
+    InvalidCharacter(char),
+    /// Longer than the limit, which is given in bytes
+    TooLong(usize)
+}
+
+impl std::fmt::Display for ParseNaturalError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Empty => write!(f, "cannot parse a number from an empty string"),
+            Self::InvalidCharacter(c) => write!(f, "invalid character {:?} in number", c),
+            Self::TooLong(limit) => write!(f, "number is longer than the limit of {} bytes", limit)
+        }
+    }
+}
+
+impl std::error::Error for ParseNaturalError {}
+
+/// Why a string could not be parsed as a `Rational`.
+#[cfg(feature = "rational")]
+#[derive(Clone,Copy,Debug,PartialEq,Eq)]
+#[non_exhaustive]
+pub enum ParseRationalError {
+    /// The numerator or denominator is not a valid number
+    Invalid(ParseNaturalError),
+    ZeroDenominator
+}
+
+#[cfg(feature = "rational")]
+impl std::fmt::Display for ParseRationalError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Invalid(e) => write!(f, "{}", e),
+            Self::ZeroDenominator => write!(f, "fraction has a zero denominator")
+        }
+    }
+}
+
+#[cfg(feature = "rational")]
+impl std::error::Error for ParseRationalError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Invalid(e) => Some(e),
+            Self::ZeroDenominator => None
+        }
+    }
+}
+
+/// Why a test vector could not be evaluated.
+#[cfg(feature = "testvectors")]
+#[derive(Clone,Copy,Debug,PartialEq,Eq)]
+#[non_exhaustive]
+pub enum TestVectorError {
+    /// An operand is not a valid number
+    Invalid(ParseNaturalError),
+    /// An exponent, degree, index or radix is too large, or the radix is
+    /// not in `2..=36`
+    OutOfRange,
+    /// The operation takes a different number of operands
+    Operands
+}
+
+#[cfg(feature = "testvectors")]
+impl std::fmt::Display for TestVectorError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Invalid(e) => write!(f, "{}", e),
+            Self::OutOfRange => write!(f, "operand out of range"),
+            Self::Operands => write!(f, "wrong number of operands")
+        }
+    }
+}
+
+#[cfg(feature = "testvectors")]
+impl std::error::Error for TestVectorError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Invalid(e) => Some(e),
+            _ => None
+        }
+    }
+}
+
+/// Why a set of moduli cannot form a residue number system.
+#[cfg(feature = "rns")]
+#[derive(Clone,Copy,Debug,PartialEq,Eq)]
+#[non_exhaustive]
+pub enum RnsBasisError {
+    Empty,
+    TooSmall(u32),
+    NotCoprime(u32, u32)
+}
+
+#[cfg(feature = "rns")]
+impl std::fmt::Display for RnsBasisError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Empty => write!(f, "a residue number system needs at least one modulus"),
+            Self::TooSmall(m) => write!(f, "modulus {} is smaller than 2", m),
+            Self::NotCoprime(a, b) => write!(f, "moduli {} and {} are not coprime", a, b)
+        }
+    }
+}
+
+#[cfg(feature = "rns")]
+impl std::error::Error for RnsBasisError {}
+
+/// Why a variable-length integer could not be decoded.
+#[cfg(feature = "varint")]
+#[derive(Clone,Copy,Debug,PartialEq,Eq)]
+#[non_exhaustive]
+pub enum VarintError {
+    /// The input ended before a byte without the continuation bit
+    Truncated
+}
+
+#[cfg(feature = "varint")]
+impl std::fmt::Display for VarintError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Truncated => write!(f, "variable-length integer is truncated")
+        }
+    }
+}
+
+#[cfg(feature = "varint")]
+impl std::error::Error for VarintError {}
+
+/// Why a length-prefixed wire encoding could not be read or written.
+#[cfg(feature = "wire")]
+#[derive(Clone,Copy,Debug,PartialEq,Eq)]
+#[non_exhaustive]
+pub enum WireError {
+    /// The input ended before the length prefix or the bytes it promised
+    Truncated,
+    /// An SSH mpint had its sign bit set
+    Negative,
+    /// An SSH mpint had a redundant leading byte
+    NonMinimal,
+    /// The value does not fit in the length prefix
+    TooLong
+}
+
+#[cfg(feature = "wire")]
+impl std::fmt::Display for WireError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Truncated => write!(f, "length-prefixed integer is truncated"),
+            Self::Negative => write!(f, "mpint is negative"),
+            Self::NonMinimal => write!(f, "mpint has a redundant leading byte"),
+            Self::TooLong => write!(f, "integer is too long for its length prefix")
+        }
+    }
+}
+
+#[cfg(feature = "wire")]
+impl std::error::Error for WireError {}
+
+/// Why a base64url string could not be decoded.
+#[cfg(feature = "jwk")]
+#[derive(Clone,Copy,Debug,PartialEq,Eq)]
+#[non_exhaustive]
+pub enum Base64Error {
+    /// A character outside the URL-safe alphabet, including padding
+    InvalidCharacter(char),
+    /// A length that no whole number of bytes encodes to
+    InvalidLength
+}
+
+#[cfg(feature = "jwk")]
+impl std::fmt::Display for Base64Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidCharacter(c) => write!(f, "{:?} is not a base64url character", c),
+            Self::InvalidLength => write!(f, "base64url string has an impossible length")
+        }
+    }
+}
+
+#[cfg(feature = "jwk")]
+impl std::error::Error for Base64Error {}
+
+/// Why a value has no compact target encoding, or an encoding no value.
+#[cfg(feature = "compact")]
+#[derive(Clone,Copy,Debug,PartialEq,Eq)]
+#[non_exhaustive]
+pub enum CompactTargetError {
+    /// The sign bit was set on a nonzero mantissa
+    Negative,
+    /// The value needs more than 255 bytes, beyond the exponent's range
+    Overflow
+}
+
+#[cfg(feature = "compact")]
+impl std::fmt::Display for CompactTargetError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Negative => write!(f, "compact target is negative"),
+            Self::Overflow => write!(f, "value is too large for a compact target")
+        }
+    }
+}
+
+#[cfg(feature = "compact")]
+impl std::error::Error for CompactTargetError {}
+
+/// Why an RLP item could not be decoded as a natural number.
+#[cfg(feature = "rlp")]
+#[derive(Clone,Copy,Debug,PartialEq,Eq)]
+#[non_exhaustive]
+pub enum RlpError {
+    /// The input ended before the prefix or the bytes it promised
+    Truncated,
+    /// The item is a list rather than a byte string
+    List,
+    /// The value has leading zero bytes, or a longer prefix than needed
+    NonCanonical
+}
+
+#[cfg(feature = "rlp")]
+impl std::fmt::Display for RlpError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Truncated => write!(f, "RLP item is truncated"),
+            Self::List => write!(f, "RLP item is a list, not an integer"),
+            Self::NonCanonical => write!(f, "RLP integer is not minimally encoded")
+        }
+    }
+}
+
+#[cfg(feature = "rlp")]
+impl std::error::Error for RlpError {}
+
+/// Why sharded work could not be parsed or merged.
+#[cfg(feature = "shard")]
+#[derive(Clone,Copy,Debug,PartialEq,Eq)]
+#[non_exhaustive]
+pub enum ShardError {
+    /// There were no results to merge
+    Empty,
+    /// The results came from different kinds of computation
+    Mismatched,
+    /// A line is not a work unit or result
+    InvalidFormat
+}
+
+#[cfg(feature = "shard")]
+impl std::fmt::Display for ShardError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Empty => write!(f, "no results to merge"),
+            Self::Mismatched => write!(f, "results come from different computations"),
+            Self::InvalidFormat => write!(f, "not a valid work unit or result")
+        }
+    }
+}
+
+#[cfg(feature = "shard")]
+impl std::error::Error for ShardError {}
+
+/// Why a thresholds configuration could not be parsed.
+#[derive(Clone,Debug,PartialEq,Eq)]
+#[non_exhaustive]
+pub enum ParseThresholdsError {
+    /// The line, counting from one, is not `name = digits`
+    InvalidLine(usize),
+    UnknownName(String)
+}
+
+impl std::fmt::Display for ParseThresholdsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidLine(line) => write!(f, "line {} is not of the form `name = digits`", line),
+            Self::UnknownName(name) => write!(f, "unknown threshold {:?}", name)
+        }
+    }
+}
+
+impl std::error::Error for ParseThresholdsError {}
+
+/// Why bytes could not be decoded as a packed BCD integer.
+#[cfg(feature = "packed")]
+#[derive(Clone,Copy,Debug,PartialEq,Eq)]
+#[non_exhaustive]
+pub enum PackedBcdError {
+    /// There were no bytes, so no sign nibble
+    Empty,
+    /// A digit nibble was 0xa to 0xf
+    InvalidDigit(u8),
+    /// The last nibble was 0x0 to 0x9
+    InvalidSign(u8)
+}
+
+#[cfg(feature = "packed")]
+impl std::fmt::Display for PackedBcdError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Empty => write!(f, "packed BCD field is empty"),
+            Self::InvalidDigit(nibble) => write!(f, "nibble {:#x} is not a packed BCD digit", nibble),
+            Self::InvalidSign(nibble) => write!(f, "nibble {:#x} is not a packed BCD sign", nibble)
+        }
+    }
+}
+
+#[cfg(feature = "packed")]
+impl std::error::Error for PackedBcdError {}
+
+/// Why a spreadsheet string could not be read as a number.
+#[cfg(feature = "spreadsheet")]
+#[derive(Clone,Copy,Debug,PartialEq,Eq)]
+#[non_exhaustive]
+pub enum ParseSpreadsheetError {
+    /// There were no digits
+    Empty,
+    InvalidCharacter(char),
+    /// Thousands separators were not between groups of three digits
+    Grouping,
+    /// An integer was wanted, but the fraction is not zero
+    NotInteger,
+    /// There were more digits than `Limits` allows
+    TooLong
+}
+
+#[cfg(feature = "spreadsheet")]
+impl std::fmt::Display for ParseSpreadsheetError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Empty => write!(f, "number has no digits"),
+            Self::InvalidCharacter(c) => write!(f, "unexpected character {:?} in number", c),
+            Self::Grouping => write!(f, "digits are not grouped in threes"),
+            Self::NotInteger => write!(f, "number has a nonzero fraction"),
+            Self::TooLong => write!(f, "number has too many digits")
+        }
+    }
+}
+
+#[cfg(feature = "spreadsheet")]
+impl std::error::Error for ParseSpreadsheetError {}
@@ -0,0 +1,147 @@
+//! EVM Modular Arithmetic
+//!
+//! The `ADDMOD` and `MULMOD` opcodes and the `MODEXP` precompile (EIP-198)
+//! as the Ethereum Virtual Machine defines them, for simulating contracts.
+//! The sum and product are taken in full before reducing, never wrapped
+//! at 256 bits, and a zero modulus gives zero rather than a fault.
+
+use crate::natural::Natural;
+
+/// `(a + b) mod n`, or zero when `n` is zero.
+pub fn addmod(a: &Natural, b: &Natural, n: &Natural) -> Natural {
+    if *n == Natural::zero() {
+        return Natural::zero();
+    }
+    (a + b).div_rem(n).1
+}
+
+/// `(a * b) mod n`, or zero when `n` is zero.
+pub fn mulmod(a: &Natural, b: &Natural, n: &Natural) -> Natural {
+    if *n == Natural::zero() {
+        return Natural::zero();
+    }
+    (a * b).div_rem(n).1
+}
+
+/// `base^exp mod modulus`, or zero when `modulus` is zero. Zero to the
+/// zeroth is one, as everywhere else in the crate.
+pub fn modexp(base: &Natural, exp: &Natural, modulus: &Natural) -> Natural {
+    if *modulus == Natural::zero() {
+        return Natural::zero();
+    }
+    base.mod_pow(exp, modulus)
+}
+
+/// The longest base, exponent or modulus `modexp_precompile` accepts, in
+/// bytes. Each length comes from untrusted call data, and the EVM charges
+/// gas far beyond any real block's limit long before this.
+pub const MAX_OPERAND_LEN: usize = 1 << 20;
+
+// The `len` bytes of `input` from `offset` that are present, and how many
+// past its end read as zeros
+fn operand(input: &[u8], offset: usize, len: usize) -> (Natural, usize) {
+    let present = input.get(offset..).unwrap_or(&[]);
+    let present = &present[..len.min(present.len())];
+    (Natural::from_bytes_be(present), len - present.len())
+}
+
+// The operand with its missing zero bytes shifted in, never materialized
+fn shifted((value, missing): (Natural, usize)) -> Natural {
+    match missing {
+        0 => value,
+        _ if value == Natural::zero() => value,
+        missing => value * Natural::from(256u32).pow(missing)
+    }
+}
+
+fn length(input: &[u8], offset: usize) -> Option<usize> {
+    usize::try_from(&shifted(operand(input, offset, 32))).ok().filter(|len| *len <= MAX_OPERAND_LEN)
+}
+
+/// The `MODEXP` precompile on its raw call data: three 32-byte big-endian
+/// lengths, then the base, exponent and modulus in that many bytes each,
+/// with missing bytes read as zero. The output is the result in exactly
+/// as many bytes as the modulus.
+///
+/// Returns `None` if a length is over `MAX_OPERAND_LEN`, where the EVM
+/// would run out of gas.
+pub fn modexp_precompile(input: &[u8]) -> Option<Vec<u8>> {
+    let mod_len = length(input, 64)?;
+    if mod_len == 0 {
+        return Some(vec![]);
+    }
+    let base_len = length(input, 0)?;
+    let exp_len = length(input, 32)?;
+    // Bytes missing from the base or exponent leave the modulus all zeros,
+    // so this is caught before either is built
+    let modulus = shifted(operand(input, 96 + base_len + exp_len, mod_len));
+    if modulus == Natural::zero() {
+        return Some(vec![0; mod_len]);
+    }
+    let base = shifted(operand(input, 96, base_len));
+    let exp = shifted(operand(input, 96 + base_len, exp_len));
+
+    let result = modexp(&base, &exp, &modulus).to_bytes_be();
+    let mut output = vec![0; mod_len - result.len()];
+    output.extend(result);
+    Some(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn n(s: &str) -> Natural {
+        Natural::from_str_radix(s, 16).unwrap()
+    }
+
+    fn hex(s: &str) -> Vec<u8> {
+        (0..s.len()).step_by(2).map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap()).collect()
+    }
+
+    const MAX: &str = "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff";
+
+    #[test]
+    fn opcodes() {
+        assert_eq!(addmod(&n("a"), &n("a"), &n("8")), n("4"));
+        assert_eq!(mulmod(&n("a"), &n("a"), &n("8")), n("4"));
+        // Sums and products of 256-bit words do not wrap
+        assert_eq!(addmod(&n(MAX), &n("2"), &n("2")), n("1"));
+        assert_eq!(mulmod(&n(MAX), &n(MAX), &n("c")), n("9"));
+        assert_eq!(addmod(&n("5"), &n("6"), &Natural::zero()), Natural::zero());
+        assert_eq!(mulmod(&n("5"), &n("6"), &Natural::zero()), Natural::zero());
+        assert_eq!(modexp(&n("3"), &n("5"), &Natural::zero()), Natural::zero());
+        assert_eq!(modexp(&Natural::zero(), &Natural::zero(), &n("7")), Natural::one());
+        assert_eq!(modexp(&n("3"), &Natural::zero(), &Natural::one()), Natural::zero());
+    }
+
+    #[test]
+    fn precompile() {
+        let p = "fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f";
+        let p_minus_1 = "fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2e";
+        let len = |x: u8| format!("{:064x}", x);
+        // The examples in EIP-198: Fermat's little theorem mod the
+        // secp256k1 prime, and the same with an empty base
+        let fermat = format!("{}{}{}03{}{}", len(1), len(32), len(32), p_minus_1, p);
+        let mut one = vec![0; 31];
+        one.push(1);
+        assert_eq!(modexp_precompile(&hex(&fermat)), Some(one));
+        let empty_base = format!("{}{}{}{}{}", len(0), len(32), len(32), p_minus_1, p);
+        assert_eq!(modexp_precompile(&hex(&empty_base)), Some(vec![0; 32]));
+        // Truncated call data reads as zeros: 2^3 mod 0x0100 is 0x0008
+        let short = format!("{}{}{}020301", len(1), len(1), len(2));
+        assert_eq!(modexp_precompile(&hex(&short)), Some(vec![0, 8]));
+        // A zero modulus still fills its length with zeros
+        let zero = format!("{}{}{}0203", len(1), len(1), len(3));
+        assert_eq!(modexp_precompile(&hex(&zero)), Some(vec![0, 0, 0]));
+        assert_eq!(modexp_precompile(&[]), Some(vec![]));
+        assert_eq!(modexp_precompile(&hex(&format!("{}{}{}", len(0), len(0), MAX))), None);
+        // Lengths that fit in a usize but would need terabytes
+        let huge = format!("{:064x}", 1u64 << 40);
+        assert_eq!(modexp_precompile(&hex(&format!("{}{}{}", huge, len(1), len(1)))), None);
+        assert_eq!(modexp_precompile(&hex(&format!("{}{}{}", len(1), len(1), huge))), None);
+        // At the cap, with most of the base missing
+        let limit = format!("{:064x}", MAX_OPERAND_LEN);
+        assert_eq!(modexp_precompile(&hex(&format!("{}{}{}0203", limit, len(1), len(1)))), Some(vec![0]));
+    }
+}
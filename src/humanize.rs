@@ -0,0 +1,106 @@
+//! Order-of-magnitude Summaries
+
+use crate::digit::Digit;
+use crate::natural::Natural;
+use crate::words::English;
+
+/// The family of units used by `Natural::humanize`.
+#[derive(Clone,Copy,Debug,PartialEq,Eq)]
+pub enum Units {
+    /// "1.2 quadrillion"
+    ShortScale,
+    /// "1.2 P"
+    Si,
+    /// "1.2 Pi", in steps of 1024
+    PowersOfTwo,
+    /// "1.2e15"
+    Scientific
+}
+
+const SI: [&str; 10] = ["k", "M", "G", "T", "P", "E", "Z", "Y", "R", "Q"];
+const BINARY: [&str; 10] = ["Ki", "Mi", "Gi", "Ti", "Pi", "Ei", "Zi", "Yi", "Ri", "Qi"];
+
+impl Natural {
+    /// Summarize to one (truncated) decimal place in the given units.
+    ///
+    /// Values too large for the SI and binary prefix tables fall back to
+    /// scientific notation.
+    pub fn humanize(&self, units: Units) -> String {
+        let len = self.significant_len();
+        let group = len.saturating_sub(1) / 3;
+        match units {
+            Units::PowersOfTwo => self.humanize_binary(),
+            Units::Scientific if len > 1 => {
+                format!("{}e{}", self.mantissa(len, len - 1), len - 1)
+            },
+            Units::Scientific => self.to_string(),
+            _ if group == 0 => self.to_string(),
+            Units::ShortScale => {
+                format!("{} {}", self.mantissa(len, 3 * group), English::scale(group))
+            },
+            Units::Si if group <= SI.len() => {
+                format!("{} {}", self.mantissa(len, 3 * group), SI[group - 1])
+            },
+            Units::Si => self.humanize(Units::Scientific)
+        }
+    }
+
+    // The digits above `point`, plus the first digit below it if nonzero
+    fn mantissa(&self, len: usize, point: usize) -> String {
+        let mut s: String = self.digits[point..len].iter().rev().map(|d| d.to_string()).collect();
+        if point > 0 && self.digits[point - 1] != Digit::Zero {
+            s.push('.');
+            s.push_str(&self.digits[point - 1].to_string());
+        }
+        s
+    }
+
+    fn humanize_binary(&self) -> String {
+        let size = self.to_byte_size();
+        match size.power {
+            0 => self.to_string(),
+            p if p > BINARY.len() => self.humanize(Units::Scientific),
+            p => match size.tenths() {
+                0 => format!("{} {}", size.whole, BINARY[p - 1]),
+                t => format!("{}.{} {}", size.whole, t, BINARY[p - 1])
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn n(s: &str) -> Natural {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn short_scale() {
+        assert_eq!(n("1234567890123456").humanize(Units::ShortScale), "1.2 quadrillion");
+        assert_eq!(n("5000").humanize(Units::ShortScale), "5 thousand");
+        assert_eq!(n("999").humanize(Units::ShortScale), "999");
+    }
+
+    #[test]
+    fn si() {
+        assert_eq!(n("1234567890123456").humanize(Units::Si), "1.2 P");
+        let huge = format!("34{}", "0".repeat(119));
+        assert_eq!(n(&huge).humanize(Units::Si), "3.4e120");
+    }
+
+    #[test]
+    fn scientific() {
+        let huge = format!("34{}", "0".repeat(119));
+        assert_eq!(n(&huge).humanize(Units::Scientific), "3.4e120");
+        assert_eq!(n("7").humanize(Units::Scientific), "7");
+    }
+
+    #[test]
+    fn powers_of_two() {
+        assert_eq!(n("1536").humanize(Units::PowersOfTwo), "1.5 Ki");
+        assert_eq!(n("1048576").humanize(Units::PowersOfTwo), "1 Mi");
+        assert_eq!(n("1023").humanize(Units::PowersOfTwo), "1023");
+    }
+}
@@ -0,0 +1,600 @@
+//! Signed Integers
+
+use crate::error::ParseNaturalError;
+use crate::natural::Natural;
+use std::cmp::Ordering;
+
+#[derive(Clone,Copy,Debug,PartialEq,Eq,PartialOrd,Ord,Hash)]
+pub enum Sign {
+    Negative,
+    Zero,
+    Positive
+}
+
+impl std::ops::Mul for Sign {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self::Output {
+        match (self, other) {
+            (Sign::Zero, _) | (_, Sign::Zero) => Sign::Zero,
+            (a, b) if a == b => Sign::Positive,
+            _ => Sign::Negative
+        }
+    }
+}
+
+impl std::ops::Neg for Sign {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        match self {
+            Sign::Negative => Sign::Positive,
+            Sign::Zero => Sign::Zero,
+            Sign::Positive => Sign::Negative
+        }
+    }
+}
+
+/// A signed integer: a sign and a `Natural` magnitude.
+///
+/// Zero always has `Sign::Zero`, and nonzero values never do, so there is
+/// exactly one representation of each value.
+#[derive(Clone,Debug,PartialEq,Eq)]
+pub struct Integer {
+    sign: Sign,
+    magnitude: Natural
+}
+
+impl Integer {
+    /// The integer with the given sign and magnitude. The sign is ignored
+    /// for a zero magnitude.
+    ///
+    /// Panics if `sign` is `Sign::Zero` but `magnitude` is not zero.
+    pub fn new(sign: Sign, magnitude: Natural) -> Self {
+        if magnitude == Natural::zero() {
+            return Self::zero();
+        }
+        assert!(sign != Sign::Zero, "nonzero magnitude with Sign::Zero");
+        Self{ sign, magnitude }
+    }
+
+    pub fn zero() -> Self {
+        Self{ sign: Sign::Zero, magnitude: Natural::zero() }
+    }
+
+    pub fn one() -> Self {
+        Self{ sign: Sign::Positive, magnitude: Natural::one() }
+    }
+
+    pub fn sign(&self) -> Sign {
+        self.sign
+    }
+
+    pub fn magnitude(&self) -> &Natural {
+        &self.magnitude
+    }
+
+    /// -1, 0 or 1.
+    pub fn signum(&self) -> Self {
+        Self::one().with_sign(self.sign)
+    }
+
+    pub fn abs(&self) -> Natural {
+        self.magnitude.clone()
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.sign == Sign::Negative
+    }
+
+    pub fn is_positive(&self) -> bool {
+        self.sign == Sign::Positive
+    }
+
+    /// This magnitude with the given sign, like `f64::copysign`. Zero stays
+    /// zero, and `Sign::Zero` gives zero.
+    pub fn with_sign(&self, sign: Sign) -> Self {
+        match sign {
+            Sign::Zero => Self::zero(),
+            _ => Self::new(sign, self.magnitude.clone())
+        }
+    }
+
+    /// Truncated division: the quotient rounds toward zero and the
+    /// remainder takes the sign of `self`, as `/` and `%` do for the
+    /// primitive integers.
+    ///
+    /// Panics if `other` is zero.
+    pub fn div_rem(&self, other: &Self) -> (Self, Self) {
+        assert!(other.sign != Sign::Zero, "attempt to divide by zero");
+        let (q, r) = self.magnitude.div_rem(&other.magnitude);
+        (Self::new(self.sign * other.sign, q), Self::new(self.sign, r))
+    }
+
+    /// Euclidean division: the quotient that makes `rem_euclid`
+    /// nonnegative, matching `i64::div_euclid`. It rounds toward negative
+    /// infinity for a positive divisor and toward positive infinity for a
+    /// negative one.
+    ///
+    /// Panics if `other` is zero.
+    pub fn div_euclid(&self, other: &Self) -> Self {
+        let (q, r) = self.div_rem(other);
+        if r.sign != Sign::Negative {
+            return q;
+        }
+        match other.sign {
+            Sign::Negative => q + Self::one(),
+            _ => q - Self::one()
+        }
+    }
+
+    /// The remainder of Euclidean division, which is always in
+    /// `0..|other|`, matching `i64::rem_euclid`.
+    ///
+    /// Panics if `other` is zero.
+    pub fn rem_euclid(&self, other: &Self) -> Self {
+        let r = self.div_rem(other).1;
+        if r.sign != Sign::Negative {
+            return r;
+        }
+        r + Self::from(other.magnitude.clone())
+    }
+
+    /// The value as a `Natural`, or `None` if it is negative.
+    pub fn to_natural(&self) -> Option<Natural> {
+        match self.sign {
+            Sign::Negative => None,
+            _ => Some(self.magnitude.clone())
+        }
+    }
+}
+
+impl Natural {
+    /// `(g, x, y)` with `g = gcd(self, other)` and `self * x + other * y =
+    /// g`. The coefficients are the small ones Euclid's algorithm finds:
+    /// for positive inputs, `|x| <= other / g` and `|y| <= self / g`.
+    pub fn extended_gcd(&self, other: &Natural) -> (Natural, Integer, Integer) {
+        let (mut r0, mut r1) = (self.clone(), other.clone());
+        let (mut s0, mut s1) = (Integer::one(), Integer::zero());
+        let (mut t0, mut t1) = (Integer::zero(), Integer::one());
+        while r1 != Natural::zero() {
+            let (q, r) = r0.div_rem(&r1);
+            let q = Integer::from(q);
+            (r0, r1) = (r1, r);
+            (s0, s1) = (s1.clone(), s0 - q.clone() * s1);
+            (t0, t1) = (t1.clone(), t0 - q * t1);
+        }
+        (Natural::from_digits(r0.digits), s0, t0)
+    }
+
+    /// The `x` in `0..modulus` with `self * x ≡ 1 (mod modulus)`, or
+    /// `None` if there is none, which is when they are not coprime or the
+    /// modulus is zero.
+    pub fn mod_inverse(&self, modulus: &Natural) -> Option<Natural> {
+        if modulus.significant_len() == 0 {
+            return None;
+        }
+        let (g, x, _) = self.extended_gcd(modulus);
+        if g != Natural::one() {
+            return None;
+        }
+        x.rem_euclid(&Integer::from(modulus.clone())).to_natural()
+    }
+}
+
+impl From<Natural> for Integer {
+    fn from(n: Natural) -> Self {
+        Self::new(Sign::Positive, n)
+    }
+}
+
+impl std::str::FromStr for Integer {
+    type Err = ParseNaturalError;
+
+    /// Digits with an optional leading `-`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.strip_prefix('-') {
+            Some(digits) => Ok(Self::new(Sign::Negative, digits.parse()?)),
+            None => Ok(Self::new(Sign::Positive, s.parse()?))
+        }
+    }
+}
+
+impl std::fmt::Display for Integer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.sign == Sign::Negative {
+            write!(f, "-")?;
+        }
+        write!(f, "{}", self.magnitude)
+    }
+}
+
+impl PartialOrd for Integer {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Integer {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match self.sign.cmp(&other.sign) {
+            Ordering::Equal => match self.sign {
+                Sign::Negative => other.magnitude.cmp(&self.magnitude),
+                _ => self.magnitude.cmp(&other.magnitude)
+            },
+            order => order
+        }
+    }
+}
+
+impl std::ops::Neg for Integer {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self{ sign: -self.sign, magnitude: self.magnitude }
+    }
+}
+
+impl std::ops::Add for Integer {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self::Output {
+        if self.sign == Sign::Zero {
+            return other;
+        }
+        if other.sign == Sign::Zero || self.sign == other.sign {
+            return Self::new(self.sign, self.magnitude + other.magnitude);
+        }
+
+        // Opposite signs: the larger magnitude decides the sign
+        match self.magnitude.cmp(&other.magnitude) {
+            Ordering::Less => Self::new(other.sign, other.magnitude - self.magnitude),
+            Ordering::Equal => Self::zero(),
+            Ordering::Greater => Self::new(self.sign, self.magnitude - other.magnitude)
+        }
+    }
+}
+
+impl std::ops::Sub for Integer {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self::Output {
+        self + (-other)
+    }
+}
+
+impl std::ops::Mul for Integer {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self::Output {
+        Self::new(self.sign * other.sign, self.magnitude * other.magnitude)
+    }
+}
+
+/// Truncating division, rounding toward zero like the primitive integers.
+impl std::ops::Div for Integer {
+    type Output = Self;
+
+    fn div(self, other: Self) -> Self::Output {
+        self.div_rem(&other).0
+    }
+}
+
+/// The remainder of truncating division, with the sign of `self`.
+impl std::ops::Rem for Integer {
+    type Output = Self;
+
+    fn rem(self, other: Self) -> Self::Output {
+        self.div_rem(&other).1
+    }
+}
+
+// Bitwise operations treat an `Integer` as an infinite two's-complement
+// bit string, as Python and num-bigint do: nonnegative values have
+// infinitely many leading zeros, and a negative x is the complement of
+// |x| - 1, with infinitely many leading ones. Each operand is held as
+// those finite bytes and a flag saying whether they are complemented.
+impl Integer {
+    fn to_twos_complement(&self) -> (Vec<u8>, bool) {
+        match self.sign {
+            Sign::Negative => ((self.magnitude.clone() - Natural::one()).to_bytes_le(), true),
+            _ => (self.magnitude.to_bytes_le(), false)
+        }
+    }
+
+    fn from_twos_complement(bytes: &[u8], complemented: bool) -> Self {
+        let magnitude = Natural::from_bytes_le(bytes);
+        match complemented {
+            true => Self::new(Sign::Negative, magnitude + Natural::one()),
+            false => Self::from(magnitude)
+        }
+    }
+
+    // Apply `f` to the actual bits of both operands. Past the stored bytes
+    // every bit is the fill, all zeros or all ones, so `f` of the two
+    // fills says whether the result is complemented.
+    fn bitwise(&self, other: &Self, f: impl Fn(u8, u8) -> u8) -> Self {
+        let (a, a_complemented) = self.to_twos_complement();
+        let (b, b_complemented) = other.to_twos_complement();
+        let flag = |c: bool| if c { 0xff } else { 0 };
+        let complemented = f(flag(a_complemented), flag(b_complemented)) != 0;
+        let bytes: Vec<u8> = (0..a.len().max(b.len()))
+            .map(|k| {
+                let x = a.get(k).copied().unwrap_or(0) ^ flag(a_complemented);
+                let y = b.get(k).copied().unwrap_or(0) ^ flag(b_complemented);
+                f(x, y) ^ flag(complemented)
+            })
+            .collect();
+        Self::from_twos_complement(&bytes, complemented)
+    }
+}
+
+impl std::ops::BitAnd for Integer {
+    type Output = Self;
+
+    fn bitand(self, other: Self) -> Self::Output {
+        self.bitwise(&other, |x, y| x & y)
+    }
+}
+
+impl std::ops::BitOr for Integer {
+    type Output = Self;
+
+    fn bitor(self, other: Self) -> Self::Output {
+        self.bitwise(&other, |x, y| x | y)
+    }
+}
+
+impl std::ops::BitXor for Integer {
+    type Output = Self;
+
+    fn bitxor(self, other: Self) -> Self::Output {
+        self.bitwise(&other, |x, y| x ^ y)
+    }
+}
+
+/// `-self - 1`, flipping every bit.
+impl std::ops::Not for Integer {
+    type Output = Self;
+
+    fn not(self) -> Self::Output {
+        -self - Self::one()
+    }
+}
+
+/// An arithmetic shift, rounding toward negative infinity: `-5 >> 1` is
+/// `-3`, as in Python.
+impl std::ops::Shr<usize> for Integer {
+    type Output = Self;
+
+    fn shr(self, shift: usize) -> Self::Output {
+        let (bytes, complemented) = self.to_twos_complement();
+        let (whole, bits) = (shift / 8, shift % 8);
+        let shifted: Vec<u8> = (whole..bytes.len())
+            .map(|k| {
+                let high = bytes.get(k + 1).copied().unwrap_or(0);
+                ((u16::from(high) << 8 | u16::from(bytes[k])) >> bits) as u8
+            })
+            .collect();
+        Self::from_twos_complement(&shifted, complemented)
+    }
+}
+
+// Mixed operands: an `Integer` on either side makes the result an
+// `Integer`, with the `Natural` converted first. Only `Natural` with
+// `Natural` stays `Natural`.
+macro_rules! mixed_ops {
+    ($($op:ident $method:ident $assign:ident $assign_method:ident),*) => {$(
+        impl std::ops::$op<Natural> for Integer {
+            type Output = Integer;
+
+            fn $method(self, other: Natural) -> Integer {
+                std::ops::$op::$method(self, Integer::from(other))
+            }
+        }
+
+        impl std::ops::$op<Integer> for Natural {
+            type Output = Integer;
+
+            fn $method(self, other: Integer) -> Integer {
+                std::ops::$op::$method(Integer::from(self), other)
+            }
+        }
+
+        impl std::ops::$assign for Integer {
+            fn $assign_method(&mut self, other: Integer) {
+                let value = std::mem::replace(self, Integer::zero());
+                *self = std::ops::$op::$method(value, other);
+            }
+        }
+
+        impl std::ops::$assign<Natural> for Integer {
+            fn $assign_method(&mut self, other: Natural) {
+                let value = std::mem::replace(self, Integer::zero());
+                *self = std::ops::$op::$method(value, other);
+            }
+        }
+    )*};
+}
+
+mixed_ops!(
+    Add add AddAssign add_assign,
+    Sub sub SubAssign sub_assign,
+    Mul mul MulAssign mul_assign,
+    Div div DivAssign div_assign,
+    Rem rem RemAssign rem_assign
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn i(s: &str) -> Integer {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn parse_and_display() {
+        assert_eq!(i("-123").to_string(), "-123");
+        assert_eq!(i("-0"), Integer::zero());
+        assert_eq!(i("-007").to_string(), "-7");
+        assert_eq!("-".parse::<Integer>(), Err(ParseNaturalError::Empty));
+        assert_eq!("--1".parse::<Integer>(), Err(ParseNaturalError::InvalidCharacter('-')));
+    }
+
+    #[test]
+    fn ordering() {
+        let mut values = vec![i("3"), i("-10"), i("0"), i("-2"), i("10")];
+        values.sort();
+        assert_eq!(values, vec![i("-10"), i("-2"), i("0"), i("3"), i("10")]);
+    }
+
+    #[test]
+    fn add_sub() {
+        assert_eq!(i("5") - i("8"), i("-3"));
+        assert_eq!(i("-5") + i("8"), i("3"));
+        assert_eq!(i("-5") - i("8"), i("-13"));
+        assert_eq!(i("7") + i("-7"), Integer::zero());
+        assert_eq!(i("0") - i("4"), i("-4"));
+    }
+
+    #[test]
+    fn mul_div() {
+        assert_eq!(i("-6") * i("7"), i("-42"));
+        assert_eq!(i("-6") * i("-7"), i("42"));
+        assert_eq!(i("-6") * i("0"), Integer::zero());
+        // Truncation toward zero, matching -7 / 2 == -3 for i64
+        assert_eq!(i("-7") / i("2"), i("-3"));
+        assert_eq!(i("7") / i("-2"), i("-3"));
+        assert_eq!(i("-7") / i("-2"), i("3"));
+    }
+
+    #[test]
+    fn matches_primitive_division() {
+        for a in -12_i64..=12 {
+            for b in (-5_i64..=5).filter(|b| *b != 0) {
+                let (x, y) = (i(&a.to_string()), i(&b.to_string()));
+                assert_eq!((x.clone() / y.clone()).to_string(), (a / b).to_string());
+                assert_eq!((x.clone() % y.clone()).to_string(), (a % b).to_string());
+                assert_eq!(x.div_euclid(&y).to_string(), a.div_euclid(b).to_string());
+                assert_eq!(x.rem_euclid(&y).to_string(), a.rem_euclid(b).to_string());
+            }
+        }
+    }
+
+    #[test]
+    fn sign_helpers() {
+        assert_eq!(i("-42").signum(), i("-1"));
+        assert_eq!(i("0").signum(), i("0"));
+        assert_eq!(i("42").signum(), i("1"));
+        assert_eq!(i("-42").abs(), "42".parse().unwrap());
+        assert!(i("-1").is_negative() && !i("-1").is_positive());
+        assert!(!i("0").is_negative() && !i("0").is_positive());
+        assert_eq!(i("-42").with_sign(Sign::Positive), i("42"));
+        assert_eq!(i("42").with_sign(Sign::Negative), i("-42"));
+        assert_eq!(i("42").with_sign(Sign::Zero), Integer::zero());
+        assert_eq!(i("0").with_sign(Sign::Negative), Integer::zero());
+    }
+
+    #[test]
+    fn mixed_operands() {
+        let n = |s: &str| s.parse::<Natural>().unwrap();
+        assert_eq!(i("-5") + n("8"), i("3"));
+        assert_eq!(n("5") - i("8"), i("-3"));
+        assert_eq!(n("6") * i("-7"), i("-42"));
+        assert_eq!(i("-7") / n("2"), i("-3"));
+        assert_eq!(n("7") % i("-2"), i("1"));
+
+        let mut x = i("10");
+        x -= n("25");
+        assert_eq!(x, i("-15"));
+        x *= i("-2");
+        assert_eq!(x, i("30"));
+        x %= n("7");
+        assert_eq!(x, i("2"));
+        x += i("-2");
+        assert_eq!(x, Integer::zero());
+    }
+
+    #[test]
+    fn extended_gcd() {
+        let n = |s: &str| s.parse::<Natural>().unwrap();
+        for (a, b) in [("240", "46"), ("17", "5"), ("0", "9"), ("9", "0"), ("123456789012345", "9876543210")] {
+            let (g, x, y) = n(a).extended_gcd(&n(b));
+            assert_eq!(g, n(a).gcd(&n(b)));
+            assert_eq!(Integer::from(n(a)) * x + Integer::from(n(b)) * y, Integer::from(g));
+        }
+        assert_eq!(n("240").extended_gcd(&n("46")), (n("2"), i("-9"), i("47")));
+    }
+
+    #[test]
+    fn mod_inverse() {
+        let n = |s: &str| s.parse::<Natural>().unwrap();
+        assert_eq!(n("3").mod_inverse(&n("11")), Some(n("4")));
+        assert_eq!(n("10").mod_inverse(&n("17")), Some(n("12")));
+        assert_eq!(n("6").mod_inverse(&n("9")), None);
+        assert_eq!(n("5").mod_inverse(&n("0")), None);
+        assert_eq!(n("5").mod_inverse(&n("1")), Some(n("0")));
+        let p = n("170141183460469231731687303715884105727");
+        let x = n("123456789123456789").mod_inverse(&p).unwrap();
+        assert_eq!(x * n("123456789123456789") % p, n("1"));
+    }
+
+    #[test]
+    #[should_panic]
+    fn divide_by_zero() {
+        let _ = i("1") / i("0");
+    }
+
+    // Computed with Python's int, which has the same semantics
+    #[test]
+    fn bitwise_matches_python() {
+        let vectors = [
+            ("0", "255", "0", "255", "255"),
+            ("1", "561684854493", "1", "561684854493", "561684854492"),
+            ("-1", "411391562664144335163292320026", "411391562664144335163292320026", "-1", "-411391562664144335163292320027"),
+            ("255", "-1", "255", "-1", "-256"),
+            ("-256", "949954274705828642529480309660", "949954274705828642529480309504", "-100", "-949954274705828642529480309604"),
+            ("18446744073709551616", "-145", "18446744073709551616", "-145", "-18446744073709551761"),
+            ("-18446744073709551615", "1", "1", "-18446744073709551615", "-18446744073709551616"),
+            ("949954274705828642529480309660", "-18446744073709551615", "949954274689271686437854511104", "-1889787982083753059", "-949954274691161474419938264163"),
+            ("561684854493", "455240267227926703755788866324", "561667936788", "455240267227926703755805784029", "455240267227926703194137847241"),
+            ("803607757964290647670573889318", "18446744073709551616", "0", "803607757982737391744283440934", "803607757982737391744283440934"),
+            ("455240267227926703755788866324", "803607757964290647670573889318", "11316453555907900882205332228", "1247531571636309450544157423414", "1236215118080401549661952091186"),
+            ("-145", "35", "35", "-145", "-180"),
+            ("411391562664144335163292320026", "-256", "411391562664144335163292320000", "-230", "-411391562664144335163292320230"),
+            ("-58", "987517176070783535590292204350", "987517176070783535590292204294", "-2", "-987517176070783535590292204296"),
+            ("35", "-58", "2", "-25", "-27")
+        ];
+        for (a, b, and, or, xor) in vectors {
+            assert_eq!(i(a) & i(b), i(and), "{} & {}", a, b);
+            assert_eq!(i(a) | i(b), i(or), "{} | {}", a, b);
+            assert_eq!(i(a) ^ i(b), i(xor), "{} ^ {}", a, b);
+            assert_eq!(i(b) & i(a), i(and), "{} & {}", b, a);
+            assert_eq!(!i(a), -i(a) - Integer::one());
+        }
+    }
+
+    #[test]
+    fn shift_matches_python() {
+        let vectors = [
+            ("0", 0, "0"),
+            ("1", 1, "0"),
+            ("-1", 3, "-1"),
+            ("255", 8, "0"),
+            ("-256", 65, "-1"),
+            ("18446744073709551616", 200, "0"),
+            ("-18446744073709551615", 0, "-18446744073709551615"),
+            ("949954274705828642529480309660", 1, "474977137352914321264740154830"),
+            ("561684854493", 3, "70210606811"),
+            ("987517176070783535590292204350", 8, "3857488969026498185899578923"),
+            ("803607757964290647670573889318", 65, "21781831925"),
+            ("-145", 0, "-145"),
+            ("-58", 3, "-8"),
+            ("-5", 1, "-3")
+        ];
+        for (a, shift, expected) in vectors {
+            assert_eq!(i(a) >> shift, i(expected), "{} >> {}", a, shift);
+        }
+    }
+}
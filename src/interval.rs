@@ -0,0 +1,139 @@
+//! Interval Arithmetic over Exact Endpoints
+//!
+//! An `Interval<T>` is the closed range `[lower, upper]`. Because the
+//! endpoints are exact, every operation gives the tightest enclosure of
+//! all possible results, with no outward rounding needed.
+
+use std::ops::{Add, Mul, Sub};
+
+#[derive(Clone,Debug,PartialEq,Eq)]
+pub struct Interval<T> {
+    lower: T,
+    upper: T
+}
+
+impl<T: Ord + Clone> Interval<T> {
+    /// The interval `[lower, upper]`, or `None` if `lower > upper`.
+    pub fn new(lower: T, upper: T) -> Option<Self> {
+        if lower > upper {
+            return None;
+        }
+        Some(Self{ lower, upper })
+    }
+
+    pub fn point(x: T) -> Self {
+        Self{ lower: x.clone(), upper: x }
+    }
+
+    pub fn lower(&self) -> &T {
+        &self.lower
+    }
+
+    pub fn upper(&self) -> &T {
+        &self.upper
+    }
+
+    pub fn contains(&self, x: &T) -> bool {
+        self.lower <= *x && *x <= self.upper
+    }
+
+    pub fn is_subset(&self, other: &Self) -> bool {
+        other.lower <= self.lower && self.upper <= other.upper
+    }
+
+    /// The overlap of two intervals, if they meet.
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let lower = std::cmp::max(&self.lower, &other.lower).clone();
+        let upper = std::cmp::min(&self.upper, &other.upper).clone();
+        Self::new(lower, upper)
+    }
+
+    /// The smallest interval containing both.
+    pub fn hull(&self, other: &Self) -> Self {
+        let lower = std::cmp::min(&self.lower, &other.lower).clone();
+        let upper = std::cmp::max(&self.upper, &other.upper).clone();
+        Self{ lower, upper }
+    }
+}
+
+impl<T: Ord + Clone + Sub<Output = T>> Interval<T> {
+    pub fn width(&self) -> T {
+        self.upper.clone() - self.lower.clone()
+    }
+}
+
+impl<T: Add<Output = T>> Add for Interval<T> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self::Output {
+        Self{ lower: self.lower + other.lower, upper: self.upper + other.upper }
+    }
+}
+
+/// `[a, b] - [c, d] = [a - d, b - c]`. For unsigned endpoints this panics
+/// unless `a >= d`, just as the endpoint subtraction does.
+impl<T: Sub<Output = T>> Sub for Interval<T> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self::Output {
+        Self{ lower: self.lower - other.upper, upper: self.upper - other.lower }
+    }
+}
+
+/// The hull of the four endpoint products, which is exact for any ordered
+/// ring, signed or not.
+impl<T: Ord + Clone + Mul<Output = T>> Mul for Interval<T> {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self::Output {
+        let products = [
+            self.lower.clone() * other.lower.clone(),
+            self.lower * other.upper.clone(),
+            self.upper.clone() * other.lower,
+            self.upper * other.upper
+        ];
+        let lower = products.iter().min().unwrap().clone();
+        let upper = products.iter().max().unwrap().clone();
+        Self{ lower, upper }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::natural::Natural;
+
+    fn i(a: &str, b: &str) -> Interval<Natural> {
+        Interval::new(a.parse().unwrap(), b.parse().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn ordered() {
+        assert!(Interval::new(Natural::one(), Natural::zero()).is_none());
+        assert!(i("3", "7").contains(&"5".parse().unwrap()));
+        assert!(!i("3", "7").contains(&"8".parse().unwrap()));
+    }
+
+    #[test]
+    fn arithmetic() {
+        assert_eq!(i("1", "2") + i("10", "20"), i("11", "22"));
+        assert_eq!(i("10", "20") - i("1", "2"), i("8", "19"));
+        assert_eq!(i("2", "3") * i("10", "100"), i("20", "300"));
+        assert_eq!(i("10", "20").width(), "10".parse().unwrap());
+    }
+
+    #[test]
+    fn signed_mul() {
+        let a = Interval::new(-2_i64, 3).unwrap();
+        let b = Interval::new(-5_i64, 4).unwrap();
+        assert_eq!(a * b, Interval::new(-15, 12).unwrap());
+    }
+
+    #[test]
+    fn set_operations() {
+        assert_eq!(i("1", "5").intersection(&i("3", "9")), Some(i("3", "5")));
+        assert_eq!(i("1", "2").intersection(&i("3", "9")), None);
+        assert_eq!(i("1", "2").hull(&i("3", "9")), i("1", "9"));
+        assert!(i("3", "4").is_subset(&i("1", "9")));
+    }
+}
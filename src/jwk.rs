@@ -0,0 +1,95 @@
+//! JSON Web Key Integers
+//!
+//! JWKs carry RSA moduli and exponents, and other big numbers, as the
+//! unpadded base64url encoding (RFC 4648, section 5) of their minimal
+//! big-endian bytes (RFC 7518, section 2). Zero, which still needs one
+//! octet, is "AA".
+
+use crate::error::Base64Error;
+use crate::natural::Natural;
+
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+fn sextet(c: char) -> Result<u32, Base64Error> {
+    match c {
+        'A'..='Z' => Ok(c as u32 - 'A' as u32),
+        'a'..='z' => Ok(c as u32 - 'a' as u32 + 26),
+        '0'..='9' => Ok(c as u32 - '0' as u32 + 52),
+        '-' => Ok(62),
+        '_' => Ok(63),
+        _ => Err(Base64Error::InvalidCharacter(c))
+    }
+}
+
+impl Natural {
+    pub fn to_base64url(&self) -> String {
+        let mut bytes = self.to_bytes_be();
+        if bytes.is_empty() {
+            bytes.push(0);
+        }
+        let mut s = String::with_capacity(bytes.len().div_ceil(3) * 4);
+        for chunk in bytes.chunks(3) {
+            let block = chunk.iter().enumerate()
+                .fold(0_u32, |acc, (i, b)| acc | (u32::from(*b) << (16 - 8 * i)));
+            // n bytes fill n + 1 characters
+            for i in 0..=chunk.len() {
+                s.push(ALPHABET[((block >> (18 - 6 * i)) & 63) as usize] as char);
+            }
+        }
+        s
+    }
+
+    /// Leading zero bytes are accepted and ignored.
+    pub fn from_base64url(s: &str) -> Result<Natural, Base64Error> {
+        let sextets = s.chars().map(sextet).collect::<Result<Vec<_>, _>>()?;
+        if sextets.is_empty() || sextets.len() % 4 == 1 {
+            return Err(Base64Error::InvalidLength);
+        }
+        let mut bytes = Vec::with_capacity(sextets.len() * 3 / 4);
+        for chunk in sextets.chunks(4) {
+            let block = chunk.iter().enumerate()
+                .fold(0_u32, |acc, (i, v)| acc | (v << (18 - 6 * i)));
+            for i in 0..chunk.len() - 1 {
+                bytes.push((block >> (16 - 8 * i)) as u8);
+            }
+        }
+        Ok(Natural::from_bytes_be(&bytes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn n(s: &str) -> Natural {
+        s.parse().unwrap()
+    }
+
+    // The RSA public key from RFC 7517, appendix A.1
+    const MODULUS: &str = "0vx7agoebGcQSuuPiLJXZptN9nndrQmbXEps2aiAFbWhM78LhWx4cbbfAAtVT86zwu1RK7aPFFxuhDR1L6tSoc_BJECPebWKRXjBZCiFV4n3oknjhMstn64tZ_2W-5JsGY4Hc5n9yBXArwl93lqt7_RN5w6Cf0h4QyQ5v-65YGjQR0_FDW2QvzqY368QQMicAtaSqzs8KJZgnYb9c7d0zgdAZHzu6qMQvRL5hajrn1n91CbOpbISD08qNLyrdkt-bFTWhAI4vMQFh6WeZu0fM4lFd2NcRwr3XPksINHaQ-G_xBniIqbw0Ls1jF44-csFCur-kEgU8awapJzKnqDKgw";
+    const MODULUS_DECIMAL: &str = "26634547600177008912365441464036882611104634136430581696102639463075266436216946316053845642300166320042915031924501272705275043130211783228252369194856949397782880847235143381529207382262647906987655738647387007320361149854766523417293323739185308113373529512728932838100141612048712597178695720651344295450174895369923383396704334331627261565907266749863744707920606364678231639106403854977302183719246256958550651555767664134467706614553219592981545363271425781391262006405169505726523023628770285432062044391310047445749287563161668548354322560223509946990827691654627968182167826397015368836435965354956581554819";
+
+    #[test]
+    fn rfc7517_key() {
+        assert_eq!(Natural::from_base64url("AQAB"), Ok(n("65537")));
+        assert_eq!(n("65537").to_base64url(), "AQAB");
+        assert_eq!(Natural::from_base64url(MODULUS), Ok(n(MODULUS_DECIMAL)));
+        assert_eq!(n(MODULUS_DECIMAL).to_base64url(), MODULUS);
+    }
+
+    #[test]
+    fn partial_blocks() {
+        assert_eq!(Natural::zero().to_base64url(), "AA");
+        assert_eq!(n("255").to_base64url(), "_w");
+        assert_eq!(n("65535").to_base64url(), "__8");
+        assert_eq!(Natural::from_base64url("AAAA_w"), Ok(n("255")));
+    }
+
+    #[test]
+    fn rejects() {
+        assert_eq!(Natural::from_base64url("AQAB="), Err(Base64Error::InvalidCharacter('=')));
+        assert_eq!(Natural::from_base64url("AQ+B"), Err(Base64Error::InvalidCharacter('+')));
+        assert_eq!(Natural::from_base64url("AQABA"), Err(Base64Error::InvalidLength));
+        assert_eq!(Natural::from_base64url(""), Err(Base64Error::InvalidLength));
+    }
+}
@@ -1,2 +1,156 @@
-mod digit;
-mod natural;
+//! Real Big Numbers
+//!
+//! The integer core (`digit`, `natural`, `integer`, `rounding`, `digest`,
+//! `random`, `thresholds`, `limits`) is always built, along with `error`
+//! and `types`, the stable home of every public type. Everything else
+//! lives in its own module behind a cargo feature, all enabled by default:
+//!
+//! - `format`: number words, humanized summaries, byte sizes, typesetting and negative styles
+//! - `time`: `BigDuration` and `BigTimestamp`
+//! - `cache`: memoization of expensive results
+//! - `testvectors`: the golden test vectors and their runner
+//! - `balanced`: balanced signed-digit decimals with carry-free addition
+//! - `rns`: an experimental residue number system
+//! - `interval`: intervals with exact endpoints
+//! - `linalg`: exact dot products and matrix-vector products
+//! - `padic`: p-adic integers at fixed precision
+//! - `zeckendorf`: Zeckendorf representations and Fibonacci codes
+//! - `varint`: LEB128 and VLQ variable-length integers
+//! - `wire`: SSH mpint and TLS length-prefixed big numbers
+//! - `jwk`: base64url big numbers as used in JSON Web Keys
+//! - `compact`: Bitcoin compact difficulty targets (`nBits`)
+//! - `rlp`: Ethereum RLP encoding of integers
+//! - `select`: top-N and `n`th-largest selection in place
+//! - `sort`: radix sorting of natural numbers
+//! - `compress`: run-length compressed storage
+//! - `view`: zero-copy views of digit buffers
+//! - `outofcore`: disk-backed addition and multiplication
+//! - `rational`: exact fractions
+//! - `async`: futures for long computations, without a runtime
+//! - `shard`: deterministic work units for distributed computation
+//! - `calibrate`: timing-based tuning of algorithm thresholds
+//! - `primes`: Miller-Rabin primality testing
+//! - `crypto-demos`: random primes and textbook RSA, for teaching rather than security
+//! - `stats`: exact medians, quantiles and means
+//! - `stream`: running totals, signed accumulators and sliding-window sums and means
+//! - `packed`: COBOL packed BCD (`COMP-3`) integers
+//! - `spreadsheet`: thousands separators, decimal commas and parenthesized negatives
+//! - `allocate`: proportional allocation by largest remainders
+//! - `apportion`: D'Hondt, Sainte-Laguë and Huntington-Hill seat apportionment
+//! - `prob`: exact binomial and hypergeometric probabilities
+//! - `bitset`: sets of bits convertible to and from `Natural`
+//! - `evm`: EVM `ADDMOD`, `MULMOD` and `MODEXP` semantics
+//! - `congruence`: linear congruences and rational reconstruction
+//! - `cli`: the `bignum` binary
+//!
+//! There is no `decimal` feature, because nothing would be behind it: the
+//! core types are already decimal, digit for digit, and exact decimal
+//! fractions are `Rational`s, which `Notation::Decimal` writes to any
+//! number of places.
+//!
+//! Integrations with other crates are off by default:
+//!
+//! - `serde`: `Serialize` and `Deserialize`, in the `serialize` module
+//!
+//! Feature modules add methods to core types from their own files, so the
+//! core never refers to them. Build with `default-features = false` for
+//! the core alone.
+//!
+//! The crate contains no unsafe code, and the compiler holds it to that.
+
+#![forbid(unsafe_code)]
+
+pub mod digit;
+pub mod natural;
+pub mod integer;
+pub mod rounding;
+pub mod digest;
+pub mod random;
+pub mod thresholds;
+pub mod limits;
+pub mod error;
+pub mod types;
+#[cfg(feature = "format")]
+pub mod words;
+#[cfg(feature = "format")]
+pub mod humanize;
+#[cfg(feature = "format")]
+pub mod bytesize;
+#[cfg(feature = "format")]
+pub mod typeset;
+#[cfg(feature = "format")]
+pub mod negative;
+#[cfg(feature = "time")]
+pub mod duration;
+#[cfg(feature = "time")]
+pub mod timestamp;
+#[cfg(feature = "cache")]
+pub mod cache;
+#[cfg(feature = "testvectors")]
+pub mod testvectors;
+#[cfg(feature = "balanced")]
+pub mod balanced;
+#[cfg(feature = "rns")]
+pub mod rns;
+#[cfg(feature = "interval")]
+pub mod interval;
+#[cfg(feature = "linalg")]
+pub mod linalg;
+#[cfg(feature = "padic")]
+pub mod padic;
+#[cfg(feature = "zeckendorf")]
+pub mod zeckendorf;
+#[cfg(feature = "varint")]
+pub mod varint;
+#[cfg(feature = "wire")]
+pub mod wire;
+#[cfg(feature = "jwk")]
+pub mod jwk;
+#[cfg(feature = "compact")]
+pub mod compact;
+#[cfg(feature = "rlp")]
+pub mod rlp;
+#[cfg(feature = "select")]
+pub mod select;
+#[cfg(feature = "sort")]
+pub mod sort;
+#[cfg(feature = "compress")]
+pub mod compress;
+#[cfg(feature = "view")]
+pub mod view;
+#[cfg(feature = "outofcore")]
+pub mod outofcore;
+#[cfg(feature = "rational")]
+pub mod rational;
+#[cfg(feature = "async")]
+pub mod task;
+#[cfg(feature = "shard")]
+pub mod shard;
+#[cfg(feature = "calibrate")]
+pub mod calibrate;
+#[cfg(feature = "primes")]
+pub mod prime;
+#[cfg(feature = "crypto-demos")]
+pub mod crypto;
+#[cfg(feature = "stats")]
+pub mod stats;
+#[cfg(feature = "serde")]
+pub mod serialize;
+#[cfg(feature = "stream")]
+pub mod stream;
+#[cfg(feature = "packed")]
+pub mod packed;
+#[cfg(feature = "spreadsheet")]
+pub mod spreadsheet;
+#[cfg(feature = "allocate")]
+pub mod allocate;
+#[cfg(feature = "apportion")]
+pub mod apportion;
+#[cfg(feature = "prob")]
+pub mod prob;
+#[cfg(feature = "bitset")]
+pub mod bitset;
+#[cfg(feature = "evm")]
+pub mod evm;
+#[cfg(feature = "congruence")]
+pub mod congruence;
@@ -0,0 +1,31 @@
+//! Resource Limits
+//!
+//! Caps on untrusted input, in one process-wide setting that can be
+//! replaced like `Thresholds`. Nothing is capped by default.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+// usize::MAX stands for no limit
+static MAX_PARSE_LEN: AtomicUsize = AtomicUsize::new(usize::MAX);
+
+#[derive(Clone,Copy,Debug,Default,PartialEq,Eq)]
+pub struct Limits {
+    /// Reject longer strings, in bytes, before looking at any of them,
+    /// wherever a number is parsed without a limit of its own
+    pub max_parse_len: Option<usize>
+}
+
+impl Limits {
+    /// The limits in force.
+    pub fn current() -> Self {
+        match MAX_PARSE_LEN.load(Ordering::Relaxed) {
+            usize::MAX => Self{ max_parse_len: None },
+            len => Self{ max_parse_len: Some(len) }
+        }
+    }
+
+    /// Enforce these limits for every later operation in the process.
+    pub fn install(self) {
+        MAX_PARSE_LEN.store(self.max_parse_len.unwrap_or(usize::MAX), Ordering::Relaxed);
+    }
+}
@@ -0,0 +1,103 @@
+//! Exact Linear Algebra Kernels
+//!
+//! Long inputs are split into chunks that are summed on separate threads
+//! and then combined, which is exact because natural addition is
+//! associative.
+
+use crate::natural::Natural;
+
+// Below this many terms, threads cost more than they save
+const PARALLEL_THRESHOLD: usize = 64;
+
+fn threads() -> usize {
+    std::thread::available_parallelism().map_or(1, |n| n.get())
+}
+
+fn dot_sequential(a: &[Natural], b: &[Natural]) -> Natural {
+    a.iter().zip(b).fold(Natural::zero(), |acc, (x, y)| x.clone().mul_add(y.clone(), acc))
+}
+
+/// The exact dot product of two equal-length vectors.
+///
+/// Panics if the lengths differ.
+pub fn dot(a: &[Natural], b: &[Natural]) -> Natural {
+    assert_eq!(a.len(), b.len(), "dot product of vectors with different lengths");
+    if a.len() < PARALLEL_THRESHOLD {
+        return dot_sequential(a, b);
+    }
+
+    let chunk = a.len().div_ceil(threads());
+    std::thread::scope(|s| {
+        let partials: Vec<_> = a.chunks(chunk).zip(b.chunks(chunk))
+            .map(|(x, y)| s.spawn(move || dot_sequential(x, y)))
+            .collect();
+        let mut total = Natural::zero();
+        for partial in partials {
+            total += partial.join().unwrap();
+        }
+        total
+    })
+}
+
+/// The exact product of a matrix, given as rows, and a column vector.
+///
+/// Panics if any row's length differs from the vector's.
+pub fn mat_vec(matrix: &[Vec<Natural>], v: &[Natural]) -> Vec<Natural> {
+    for row in matrix {
+        assert_eq!(row.len(), v.len(), "matrix row and vector have different lengths");
+    }
+    if matrix.len() * v.len() < PARALLEL_THRESHOLD {
+        return matrix.iter().map(|row| dot_sequential(row, v)).collect();
+    }
+
+    let chunk = matrix.len().div_ceil(threads()).max(1);
+    std::thread::scope(|s| {
+        let blocks: Vec<_> = matrix.chunks(chunk)
+            .map(|rows| s.spawn(move || {
+                rows.iter().map(|row| dot_sequential(row, v)).collect::<Vec<_>>()
+            }))
+            .collect();
+        blocks.into_iter().flat_map(|block| block.join().unwrap()).collect()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn v(xs: &[u32]) -> Vec<Natural> {
+        xs.iter().map(|x| x.to_string().parse().unwrap()).collect()
+    }
+
+    #[test]
+    fn small_dot() {
+        assert_eq!(dot(&v(&[1, 2, 3]), &v(&[4, 5, 6])), "32".parse().unwrap());
+        assert_eq!(dot(&[], &[]), Natural::zero());
+    }
+
+    #[test]
+    fn parallel_dot() {
+        let a: Vec<u32> = (1..=500).collect();
+        let expected: u64 = a.iter().map(|x| u64::from(*x) * u64::from(*x)).sum();
+        assert_eq!(dot(&v(&a), &v(&a)).to_string(), expected.to_string());
+    }
+
+    #[test]
+    #[should_panic]
+    fn mismatched() {
+        dot(&v(&[1, 2]), &v(&[1]));
+    }
+
+    #[test]
+    fn matrix_vector() {
+        let m = vec![v(&[1, 2]), v(&[3, 4]), v(&[5, 6])];
+        assert_eq!(mat_vec(&m, &v(&[10, 1])), v(&[12, 34, 56]));
+    }
+
+    #[test]
+    fn parallel_matrix_vector() {
+        let m: Vec<Vec<Natural>> = (0..40).map(|i| v(&[i, 1, 2])).collect();
+        let expected: Vec<u32> = (0..40).map(|i| 7 * i + 3 + 2).collect();
+        assert_eq!(mat_vec(&m, &v(&[7, 3, 1])), v(&expected));
+    }
+}
@@ -1,3 +1,91 @@
-fn main() {
-    println!("Hello, world!");
+//! The `bignum` Calculator
+//!
+//! One operation on two integers of any size, written as separate
+//! arguments:
+//!
+//! ```text
+//! $ bignum 2 ^ 100
+//! 1267650600228229401496703205376
+//! $ bignum -- -7 / 2
+//! -3
+//! ```
+//!
+//! The operators are `+`, `-`, `*`, `/` and `%`, with division truncating
+//! toward zero, and `^` for a power whose exponent fits in a `usize`.
+
+use bignum::types::{Integer, Sign};
+use std::process::ExitCode;
+
+const USAGE: &str = "usage: bignum <integer> <+|-|*|/|%|^> <integer>";
+
+fn power(base: Integer, exp: &Integer) -> Result<Integer, String> {
+    let exp = exp.to_natural()
+        .and_then(|e| usize::try_from(&e).ok())
+        .ok_or("the exponent must be a nonnegative integer that fits in a usize")?;
+    let sign = match (base.sign(), exp % 2) {
+        (Sign::Negative, 1) => Sign::Negative,
+        _ => Sign::Positive
+    };
+    Ok(Integer::new(sign, base.magnitude().pow(exp)))
+}
+
+fn calculate(args: &[String]) -> Result<Integer, String> {
+    let [a, op, b] = args else {
+        return Err(USAGE.to_string());
+    };
+    let parse = |s: &str| s.parse::<Integer>().map_err(|e| format!("{}: {}", s, e));
+    let (a, b) = (parse(a)?, parse(b)?);
+    if matches!(op.as_str(), "/" | "%") && b == Integer::zero() {
+        return Err("division by zero".to_string());
+    }
+    match op.as_str() {
+        "+" => Ok(a + b),
+        "-" => Ok(a - b),
+        "*" => Ok(a * b),
+        "/" => Ok(a / b),
+        "%" => Ok(a % b),
+        "^" => power(a, &b),
+        _ => Err(USAGE.to_string())
+    }
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).filter(|arg| arg != "--").collect();
+    match calculate(&args) {
+        Ok(result) => {
+            println!("{}", result);
+            ExitCode::SUCCESS
+        }
+        Err(message) => {
+            eprintln!("bignum: {}", message);
+            ExitCode::from(2)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(line: &str) -> Result<String, String> {
+        let args: Vec<String> = line.split(' ').map(String::from).collect();
+        calculate(&args).map(|result| result.to_string())
+    }
+
+    #[test]
+    fn operations() {
+        assert_eq!(run("2 ^ 100"), Ok("1267650600228229401496703205376".to_string()));
+        assert_eq!(run("-3 ^ 3"), Ok("-27".to_string()));
+        assert_eq!(run("-3 ^ 0"), Ok("1".to_string()));
+        assert_eq!(run("-7 / 2"), Ok("-3".to_string()));
+        assert_eq!(run("-7 % 2"), Ok("-1".to_string()));
+        assert_eq!(run("99999999999999999999 + 1"), Ok("100000000000000000000".to_string()));
+        assert_eq!(run("12 - 34"), Ok("-22".to_string()));
+        assert_eq!(run("-12 * 34"), Ok("-408".to_string()));
+        assert_eq!(run("1 / 0"), Err("division by zero".to_string()));
+        assert!(run("2 ^ -1").is_err());
+        assert!(run("2 & 1").is_err());
+        assert!(run("2 +").is_err());
+        assert!(run("x + 1").is_err());
+    }
 }
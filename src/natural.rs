@@ -1,55 +1,229 @@
 use crate::digit;
+use crate::error::{FromChunksError, ParseNaturalError, TryFromNaturalError};
+use crate::limits::Limits;
+use crate::thresholds::Thresholds;
 use std::cmp::Ordering;
 
 #[derive(Clone,Debug,PartialEq,Eq)]
 pub struct Natural {
-    digits: Vec<digit::Digit>
+    pub(crate) digits: Vec<digit::Digit>
 }
 
 impl PartialOrd for Natural {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Natural {
+    fn cmp(&self, other: &Self) -> Ordering {
         if self.degree() < other.degree() {
-            return Some(Ordering::Less);
+            return Ordering::Less;
         }
         if self.degree() > other.degree() {
-            return Some(Ordering::Greater);
+            return Ordering::Greater;
         }
 
         for p in (0..self.degree()+1).rev() {
             if self.coefficient(p) < other.coefficient(p) {
-                return Some(Ordering::Less);
+                return Ordering::Less;
             }
             if self.coefficient(p) > other.coefficient(p) {
-                return Some(Ordering::Greater);
+                return Ordering::Greater;
             }
         }
 
-        Some(Ordering::Equal)
+        Ordering::Equal
     }
 }
 
-impl Ord for Natural {
-    fn cmp(&self, other: &Self) -> Ordering {
-        self.partial_cmp(other).unwrap()
+/// What `Natural::parse_with` accepts. The default is exactly what
+/// `FromStr` accepts: ASCII digits only, up to the length allowed by
+/// `Limits::current()`.
+#[derive(Clone,Copy,Debug,Default,PartialEq,Eq)]
+pub struct ParseOptions {
+    /// Accept the decimal digits of every script, as `Digit::from_unicode`
+    /// does, and not just ASCII
+    pub unicode_digits: bool,
+    /// Reject longer strings, in bytes, before reading any digits. `None`
+    /// falls back to the process-wide `Limits`.
+    pub max_len: Option<usize>
+}
+
+// The most digits in `radix` that always fit in a u32, and the radix to
+// that power
+fn chunk_size(radix: u32) -> (usize, u32) {
+    let mut per_chunk = 1;
+    let mut divisor = radix;
+    while let Some(next) = divisor.checked_mul(radix) {
+        per_chunk += 1;
+        divisor = next;
     }
+    (per_chunk, divisor)
 }
 
-impl std::str::FromStr for Natural {
-    type Err = &'static str;
+// Reject `s` if it is longer than `max_len`, or else the global limit
+fn check_len(s: &str, max_len: Option<usize>) -> Result<(), ParseNaturalError> {
+    match max_len.or(Limits::current().max_parse_len) {
+        Some(limit) if s.len() > limit => Err(ParseNaturalError::TooLong(limit)),
+        _ => Ok(())
+    }
+}
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
+impl Natural {
+    /// Parse decimal digits, most significant first, as `options` allow.
+    pub fn parse_with(s: &str, options: ParseOptions) -> Result<Self, ParseNaturalError> {
+        check_len(s, options.max_len)?;
         let mut digits = vec![];
         for c in s.chars() {
-            let d: digit::Digit = c.try_into()?;
-            digits.insert(0, d)
+            let d = match options.unicode_digits {
+                true => digit::Digit::from_unicode(c),
+                false => c.try_into()
+            };
+            digits.push(d.map_err(|_| ParseNaturalError::InvalidCharacter(c))?)
         }
+        digits.reverse();
         match digits.len() {
-            0 => Err("We cannot have a zero-digit number"),
-            _ => Ok(Self{ digits })
+            0 => Err(ParseNaturalError::Empty),
+            _ => Ok(Self::from_digits(digits))
+        }
+    }
+
+    /// Parse digits in `radix`, most significant first, with letters in
+    /// either case past 9 and the same length limit as `FromStr`.
+    ///
+    /// Panics unless `radix` is in `2..=36`.
+    pub fn from_str_radix(s: &str, radix: u32) -> Result<Self, ParseNaturalError> {
+        assert!((2..=36).contains(&radix), "radix {} is not in 2..=36", radix);
+        if radix == 10 {
+            return s.parse();
+        }
+        check_len(s, None)?;
+        let mut groups = vec![];
+        for c in s.chars() {
+            groups.push(c.to_digit(radix).ok_or(ParseNaturalError::InvalidCharacter(c))?);
+        }
+        if groups.is_empty() {
+            return Err(ParseNaturalError::Empty);
+        }
+        if radix.is_power_of_two() {
+            groups.reverse();
+            return Ok(Self::from_power_of_two_le(&groups, radix.trailing_zeros()));
+        }
+        // As many digits at a time as fit in a u32
+        let (per_chunk, _) = chunk_size(radix);
+        let leading = groups.len() % per_chunk;
+        let (head, tail) = groups.split_at(leading);
+        let value = |chunk: &[u32]| chunk.iter().fold(0, |acc, g| acc * radix + g);
+        let mut n = Self::from(value(head));
+        for chunk in tail.chunks(per_chunk) {
+            n = n.mul_small(radix.pow(per_chunk as u32)).add_small(value(chunk));
+        }
+        Ok(n)
+    }
+
+    /// Digits in `radix`, most significant first, with lowercase letters
+    /// past 9. Zero is `"0"`.
+    ///
+    /// Panics unless `radix` is in `2..=36`.
+    pub fn to_str_radix(&self, radix: u32) -> String {
+        assert!((2..=36).contains(&radix), "radix {} is not in 2..=36", radix);
+        if radix == 10 {
+            return self.to_string();
+        }
+        if radix.is_power_of_two() {
+            return self.to_power_of_two_string(radix.trailing_zeros(), false);
+        }
+        // Peel off chunks by short division, each but the last padded
+        let (per_chunk, divisor) = chunk_size(radix);
+        let mut chunks = vec![];
+        let mut rest = self.clone();
+        while rest != Self::zero() {
+            let (quotient, chunk) = rest.div_rem_small(divisor);
+            chunks.push(chunk);
+            rest = quotient;
+        }
+        let digits = |mut chunk: u32| {
+            let mut digits = vec![];
+            while chunk > 0 {
+                digits.push(char::from_digit(chunk % radix, radix).unwrap());
+                chunk /= radix;
+            }
+            digits.into_iter().rev().collect::<String>()
+        };
+        match chunks.split_last() {
+            None => "0".to_string(),
+            Some((top, rest)) => {
+                let mut s = digits(*top);
+                for chunk in rest.iter().rev() {
+                    s.push_str(&format!("{:0>width$}", digits(*chunk), width = per_chunk));
+                }
+                s
+            }
+        }
+    }
+
+    // Digits in base 2^bits, most significant first, as `Display` writes
+    // decimal
+    fn to_power_of_two_string(&self, bits: u32, upper: bool) -> String {
+        let groups = self.to_power_of_two_le(bits);
+        if groups.is_empty() {
+            return "0".to_string();
+        }
+        let digits = groups.iter().rev().map(|g| char::from_digit(*g, 1 << bits).unwrap());
+        match upper {
+            true => digits.map(|c| c.to_ascii_uppercase()).collect(),
+            false => digits.collect()
         }
     }
 }
 
+impl std::str::FromStr for Natural {
+    type Err = ParseNaturalError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_with(s, ParseOptions::default())
+    }
+}
+
+macro_rules! from_unsigned {
+    ($($t:ty),*) => {$(
+        impl From<$t> for Natural {
+            fn from(n: $t) -> Self {
+                let mut n = n as u128;
+                let mut digits = vec![];
+                loop {
+                    digits.push(((n % 10) as u8).try_into().unwrap());
+                    n /= 10;
+                    if n == 0 {
+                        return Self{ digits };
+                    }
+                }
+            }
+        }
+    )*};
+}
+
+from_unsigned!(u8, u16, u32, u64, u128, usize);
+
+macro_rules! try_into_unsigned {
+    ($($t:ty),*) => {$(
+        impl TryFrom<&Natural> for $t {
+            type Error = TryFromNaturalError;
+
+            fn try_from(n: &Natural) -> Result<Self, Self::Error> {
+                n.digits.iter().rev().try_fold(0 as $t, |acc, d| {
+                    acc.checked_mul(10)
+                        .and_then(|acc| acc.checked_add(<$t>::from(d.as_u8())))
+                        .ok_or(TryFromNaturalError::TooLarge)
+                })
+            }
+        }
+    )*};
+}
+
+try_into_unsigned!(u8, u16, u32, u64, u128, usize);
+
 impl std::fmt::Display for Natural {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         for digit in self.digits.iter().rev() {
@@ -60,14 +234,43 @@ impl std::fmt::Display for Natural {
     }
 }
 
-// We assume that the digits vector always has at least one digit in it
+/// Honours `#` for a `0x` prefix, and the width and fill.
+impl std::fmt::LowerHex for Natural {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.pad_integral(true, "0x", &self.to_power_of_two_string(4, false))
+    }
+}
+
+/// Honours `#` for a `0b` prefix, like `LowerHex`.
+impl std::fmt::Binary for Natural {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.pad_integral(true, "0b", &self.to_power_of_two_string(1, false))
+    }
+}
+
+/// Honours `#` for a `0o` prefix, like `LowerHex`.
+impl std::fmt::Octal for Natural {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.pad_integral(true, "0o", &self.to_power_of_two_string(3, false))
+    }
+}
+
+impl std::fmt::UpperHex for Natural {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.pad_integral(true, "0x", &self.to_power_of_two_string(4, true))
+    }
+}
+
+// The digits vector always has at least one digit in it, and no leading
+// zeros, so the derived equality and the ordering by degree are by value
 impl Natural {
+    // Built directly, since parsing is subject to `Limits`
     pub fn zero() -> Self {
-        "0".parse().unwrap()
+        Self{ digits: vec![digit::Digit::Zero] }
     }
 
     pub fn one() -> Self {
-        "1".parse().unwrap()
+        Self{ digits: vec![digit::Digit::One] }
     }
 
     pub fn increment(&mut self) {
@@ -86,14 +289,601 @@ impl Natural {
         self.digits[power]
     }
 
+    /// Replace the digit of `10^power`, which must be at most `degree()`.
+    /// Zeroing the leading digit lowers the degree.
     pub fn set_coefficient(&mut self, power: usize, coefficient: digit::Digit) {
         self.digits[power] = coefficient;
+        self.normalize();
+    }
+
+    /// Drop leading zeros. Every constructor and operation already leaves
+    /// values normalized, so this only matters after editing digits in
+    /// place.
+    pub fn normalize(&mut self) {
+        while self.digits.len() > 1 && self.digits[self.digits.len() - 1] == digit::Digit::Zero {
+            self.digits.pop();
+        }
+        if self.digits.is_empty() {
+            self.digits.push(digit::Digit::Zero);
+        }
+    }
+
+    /// Whether there are no leading zeros, so zero is the single digit 0.
+    pub fn is_normalized(&self) -> bool {
+        match self.digits.as_slice() {
+            [] => false,
+            [_] => true,
+            [.., top] => *top != digit::Digit::Zero
+        }
+    }
+
+    /// Compare by number of significant digits alone, so values of the
+    /// same length are `Equal`. This never looks past the leading digit.
+    pub fn compare_magnitude_only(&self, other: &Self) -> Ordering {
+        self.significant_len().cmp(&other.significant_len())
+    }
+
+    /// Compare the lengths, then only the `figures` most significant
+    /// digits, so values agreeing that far are `Equal`. With `figures` at
+    /// least the length of both this is an exact comparison.
+    pub fn approx_cmp_with_tolerance(&self, other: &Self, figures: usize) -> Ordering {
+        let len = self.significant_len();
+        let order = len.cmp(&other.significant_len());
+        if order != Ordering::Equal {
+            return order;
+        }
+        let top = self.digits[..len].iter().rev().take(figures);
+        top.cmp(other.digits[..len].iter().rev().take(figures))
+    }
+
+    /// The quotient and remainder together, for the cost of one division.
+    ///
+    /// This is schoolbook long division, one quotient digit per digit of
+    /// `self`. Panics if `other` is zero.
+    pub fn div_rem(&self, other: &Self) -> (Self, Self) {
+        let divisor = other;
+        assert!(*divisor != Self::zero(), "attempt to divide by zero");
+        let multiples: Vec<Self> = (0..10).map(|k| divisor.mul_small(k)).collect();
+        let divisor_len = divisor.digits.len();
+        // Estimate from at most 17 leading digits, so both tops fit in a u64
+        let figures = divisor_len.min(17);
+        let divisor_top = divisor.leading(figures);
+
+        // The leading digits short of the divisor's length give no quotient
+        // digits, so they start the remainder whole
+        let head = self.digits.len().min(divisor_len - 1);
+        let (rest, top) = self.digits.split_at(self.digits.len() - head);
+        let mut quotient = vec![];
+        let mut remainder = Self::from_digits(top.to_vec());
+        for d in rest.iter().rev() {
+            remainder.digits.insert(0, *d);
+            remainder = Self::from_digits(remainder.digits);
+            if remainder < *divisor {
+                quotient.push(digit::Digit::Zero);
+                continue;
+            }
+
+            // The remainder is below 10 * divisor, so it has the divisor's
+            // length or one more. Truncating both to their leading digits
+            // leaves the estimate off by at most one either way.
+            let extra = remainder.digits.len() - divisor_len;
+            let mut q = (remainder.leading(figures + extra) / divisor_top).min(9) as usize;
+            while multiples[q] > remainder {
+                q -= 1;
+            }
+            while q < 9 && multiples[q + 1] <= remainder {
+                q += 1;
+            }
+            remainder -= &multiples[q];
+            quotient.push((q as u8).try_into().unwrap());
+        }
+        quotient.reverse();
+        (Self::from_digits(quotient), remainder)
+    }
+
+    // The value of the `count` most significant digits
+    fn leading(&self, count: usize) -> u64 {
+        self.digits.iter().rev().take(count).fold(0, |acc, d| acc * 10 + u64::from(d.as_u8()))
+    }
+
+    /// The greatest common divisor, by Euclid's algorithm, or by half-GCD
+    /// steps once both operands reach the current `Thresholds::half_gcd`
+    /// length. `gcd(0, 0)` is zero.
+    pub fn gcd(&self, other: &Self) -> Self {
+        self.gcd_with_cutoff(other, Thresholds::current().half_gcd)
+    }
+
+    fn gcd_with_cutoff(&self, other: &Self, cutoff: usize) -> Self {
+        let (mut a, mut b) = match self < other {
+            true => (other.clone(), self.clone()),
+            false => (self.clone(), other.clone())
+        };
+        // Each half-GCD call halves the length. The division before it
+        // leaves a decreasing pair, and moves on even when the remainder is
+        // already short enough
+        while b != Self::zero() && b.digits.len() >= cutoff {
+            let r = a.div_rem(&b).1;
+            let s = b.digits.len() / 2;
+            (a, b) = half_gcd(b, r, s).1;
+        }
+        while b != Self::zero() {
+            let r = a.div_rem(&b).1;
+            (a, b) = (b, r);
+        }
+        a
+    }
+
+    /// The least common multiple. Dividing before multiplying keeps the
+    /// intermediate no larger than the result. `lcm` with zero is zero.
+    pub fn lcm(&self, other: &Self) -> Self {
+        let g = self.gcd(other);
+        if g == Self::zero() {
+            return Self::zero();
+        }
+        self.div_rem(&g).0 * other
+    }
+
+    /// `self` raised to `exp`, by square-and-multiply: one squaring per
+    /// bit of `exp` and one more multiplication per set bit.
+    pub fn pow(&self, exp: usize) -> Self {
+        let mut result = Self::one();
+        let mut base = self.clone();
+        let mut exp = exp;
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result *= base.clone();
+            }
+            exp >>= 1;
+            if exp > 0 {
+                base = base.clone() * base;
+            }
+        }
+        result
+    }
+
+    /// `self` raised to `exp`, modulo `modulus`, by square-and-multiply
+    /// with a reduction after every step.
+    ///
+    /// Panics if `modulus` is zero.
+    pub fn mod_pow(&self, exp: &Self, modulus: &Self) -> Self {
+        let mut base = self.div_rem(modulus).1;
+        let mut result = Self::one().div_rem(modulus).1;
+        for bit in exp.to_power_of_two_le(1) {
+            if bit == 1 {
+                result = (result * base.clone()).div_rem(modulus).1;
+            }
+            base = (base.clone() * base).div_rem(modulus).1;
+        }
+        result
+    }
+
+    /// The floor of the `n`th root, by Newton's method from above.
+    ///
+    /// Panics if `n` is zero.
+    pub fn nth_root(&self, n: usize) -> Self {
+        assert!(n > 0, "zeroth root");
+        let value = self.clone();
+        let len = value.digits.len();
+        if n == 1 || value <= Self::one() {
+            return value;
+        }
+        // 2^n > 10^len > self, so the root is 1
+        if n >= 4 * len {
+            return Self::one();
+        }
+
+        // 10^ceil(len / n) is above the root, and each step from above
+        // stays at or above the floor until it stops decreasing
+        let degree = Self::from(n);
+        let below = degree.clone() - Self::one();
+        let mut x = Self::power_of_ten(len.div_ceil(n));
+        loop {
+            let y = (x.clone() * below.clone() + value.clone() / x.pow(n - 1)) / degree.clone();
+            if y >= x {
+                return x;
+            }
+            x = y;
+        }
+    }
+
+    // (F(n), F(n + 1)) by fast doubling, from the top bit of `n` down:
+    // F(2k) = F(k) * (2F(k + 1) - F(k)) and F(2k + 1) = F(k)^2 + F(k + 1)^2
+    fn fibonacci_pair(n: u64) -> (Self, Self) {
+        let (mut a, mut b) = (Self::zero(), Self::one());
+        for bit in (0..u64::BITS - n.leading_zeros()).rev() {
+            let doubled = a.clone() * (b.mul_small(2) - a.clone());
+            let next = a.clone() * a + b.clone() * b;
+            (a, b) = match n >> bit & 1 {
+                0 => (doubled, next),
+                _ => (next.clone(), doubled + next)
+            };
+        }
+        (a, b)
+    }
+
+    /// F(n), with F(0) = 0 and F(1) = 1, using O(log n) multiplications.
+    pub fn fibonacci(n: u64) -> Self {
+        Self::fibonacci_pair(n).0
+    }
+
+    /// The Lucas number L(n) = F(n - 1) + F(n + 1), with L(0) = 2.
+    pub fn lucas(n: u64) -> Self {
+        let (a, b) = Self::fibonacci_pair(n);
+        b.mul_small(2) - a
+    }
+
+    /// `self - other`, or `None` if `other` is larger.
+    pub fn checked_sub(&self, other: &Self) -> Option<Self> {
+        match self.cmp(other) {
+            Ordering::Less => None,
+            _ => Some(self.clone() - other.clone())
+        }
+    }
+
+    /// `self / other`, or `None` if `other` is zero.
+    pub fn checked_div(&self, other: &Self) -> Option<Self> {
+        match other.significant_len() {
+            0 => None,
+            _ => Some(self.div_rem(other).0)
+        }
+    }
+
+    /// `self % other`, or `None` if `other` is zero.
+    pub fn checked_rem(&self, other: &Self) -> Option<Self> {
+        match other.significant_len() {
+            0 => None,
+            _ => Some(self.div_rem(other).1)
+        }
+    }
+
+    /// `self - other`, or zero if `other` is larger.
+    pub fn saturating_sub(&self, other: &Self) -> Self {
+        self.checked_sub(other).unwrap_or_else(Self::zero)
+    }
+
+    /// This value as a `u64`, if it fits.
+    pub fn to_u64(&self) -> Option<u64> {
+        u64::try_from(self).ok()
+    }
+
+    /// This value clamped to `u64::MAX`, together with whether it had to
+    /// be clamped, in the manner of the primitives' `overflowing_` methods.
+    pub fn to_u64_saturating(&self) -> (u64, bool) {
+        match u64::try_from(self) {
+            Ok(v) => (v, false),
+            Err(_) => (u64::MAX, true)
+        }
+    }
+
+    /// This value clamped to `u128::MAX`, together with whether it had to
+    /// be clamped.
+    pub fn to_u128_saturating(&self) -> (u128, bool) {
+        match u128::try_from(self) {
+            Ok(v) => (v, false),
+            Err(_) => (u128::MAX, true)
+        }
+    }
+
+    /// `self * a + b`, the building block of dot products.
+    pub fn mul_add(self, a: Self, b: Self) -> Self {
+        self * a + b
+    }
+
+    /// Minimal big-endian bytes; zero is the empty slice.
+    pub fn to_bytes_be(&self) -> Vec<u8> {
+        let mut bytes = self.to_bytes_le();
+        bytes.reverse();
+        bytes
+    }
+
+    /// Minimal little-endian bytes; zero is the empty slice.
+    pub fn to_bytes_le(&self) -> Vec<u8> {
+        self.to_power_of_two_le(8).into_iter().map(|b| b as u8).collect()
+    }
+
+    /// Read big-endian bytes, ignoring any leading zeros.
+    pub fn from_bytes_be(bytes: &[u8]) -> Self {
+        let groups: Vec<u32> = bytes.iter().rev().map(|b| u32::from(*b)).collect();
+        Self::from_power_of_two_le(&groups, 8)
+    }
+
+    pub fn from_bytes_le(bytes: &[u8]) -> Self {
+        let groups: Vec<u32> = bytes.iter().map(|b| u32::from(*b)).collect();
+        Self::from_power_of_two_le(&groups, 8)
+    }
+
+    /// Minimal little-endian base-10^9 chunks, nine decimal digits each;
+    /// zero is the empty slice.
+    pub fn to_u32_chunks_le(&self) -> Vec<u32> {
+        self.digits[..self.significant_len()].chunks(9)
+            .map(|chunk| chunk.iter().rev().fold(0, |acc, d| acc * 10 + u32::from(d.as_u8())))
+            .collect()
+    }
+
+    /// Minimal big-endian base-10^9 chunks.
+    pub fn to_u32_chunks_be(&self) -> Vec<u32> {
+        let mut chunks = self.to_u32_chunks_le();
+        chunks.reverse();
+        chunks
+    }
+
+    /// Read little-endian base-10^9 chunks, each below 10^9.
+    pub fn from_u32_chunks_le(chunks: &[u32]) -> Result<Self, FromChunksError> {
+        let mut digits = Vec::with_capacity(9 * chunks.len());
+        for chunk in chunks {
+            if *chunk >= 1_000_000_000 {
+                return Err(FromChunksError::OutOfRange(*chunk));
+            }
+            let mut rest = *chunk;
+            for _ in 0..9 {
+                digits.push(((rest % 10) as u8).try_into().unwrap());
+                rest /= 10;
+            }
+        }
+        Ok(Self::from_digits(digits))
+    }
+
+    /// Read big-endian base-10^9 chunks, each below 10^9.
+    pub fn from_u32_chunks_be(chunks: &[u32]) -> Result<Self, FromChunksError> {
+        let chunks: Vec<u32> = chunks.iter().rev().copied().collect();
+        Self::from_u32_chunks_le(&chunks)
+    }
+
+    // Build from little-endian digits, dropping any leading zeros
+    pub(crate) fn from_digits(digits: Vec<digit::Digit>) -> Self {
+        let mut n = Self{ digits };
+        n.normalize();
+        n
+    }
+
+    // Short division by a machine-sized divisor
+    pub(crate) fn div_rem_small(&self, divisor: u32) -> (Self, u32) {
+        assert!(divisor != 0, "attempt to divide by zero");
+        let divisor = u64::from(divisor);
+        let mut remainder: u64 = 0;
+        let mut digits = vec![digit::Digit::Zero; self.digits.len()];
+        for p in (0..self.digits.len()).rev() {
+            remainder = remainder * 10 + u64::from(self.digits[p].as_u8());
+            digits[p] = ((remainder / divisor) as u8).try_into().unwrap();
+            remainder %= divisor;
+        }
+        (Self::from_digits(digits), remainder as u32)
+    }
+
+    pub(crate) fn mul_small(&self, factor: u32) -> Self {
+        let factor = u64::from(factor);
+        let mut carry: u64 = 0;
+        let mut digits = Vec::with_capacity(self.digits.len() + 10);
+        for d in &self.digits {
+            carry += u64::from(d.as_u8()) * factor;
+            digits.push(((carry % 10) as u8).try_into().unwrap());
+            carry /= 10;
+        }
+        while carry > 0 {
+            digits.push(((carry % 10) as u8).try_into().unwrap());
+            carry /= 10;
+        }
+        Self::from_digits(digits)
+    }
+
+    pub(crate) fn add_small(&self, addend: u32) -> Self {
+        let mut carry = u64::from(addend);
+        let mut digits = Vec::with_capacity(self.digits.len() + 10);
+        for d in &self.digits {
+            carry += u64::from(d.as_u8());
+            digits.push(((carry % 10) as u8).try_into().unwrap());
+            carry /= 10;
+        }
+        while carry > 0 {
+            digits.push(((carry % 10) as u8).try_into().unwrap());
+            carry /= 10;
+        }
+        Self::from_digits(digits)
+    }
+
+    pub(crate) fn power_of_ten(exponent: usize) -> Self {
+        let mut digits = vec![digit::Digit::Zero; exponent];
+        digits.push(digit::Digit::One);
+        Self{ digits }
+    }
+
+    // The number of binary digits; zero has none
+    pub(crate) fn bit_len(&self) -> usize {
+        self.to_power_of_two_le(1).len()
+    }
+
+    // Digits in base 2^bits, least significant first; zero has none
+    pub(crate) fn to_power_of_two_le(&self, bits: u32) -> Vec<u32> {
+        assert!((1..=28).contains(&bits));
+        // Peel off 28 bits per short division, then split each limb
+        let per_limb = 28 / bits;
+        let mut limbs = vec![];
+        let mut rest = self.clone();
+        while rest != Self::zero() {
+            let (quotient, limb) = rest.div_rem_small(1 << (per_limb * bits));
+            limbs.push(limb);
+            rest = quotient;
+        }
+
+        let mask = (1 << bits) - 1;
+        let mut groups = vec![];
+        for limb in limbs {
+            for i in 0..per_limb {
+                groups.push((limb >> (i * bits)) & mask);
+            }
+        }
+        while groups.last() == Some(&0) {
+            groups.pop();
+        }
+        groups
+    }
+
+    // Inverse of to_power_of_two_le. Groups are packed into 28-bit limbs
+    // first, so there is one pass over the digits per limb, not per group.
+    pub(crate) fn from_power_of_two_le(groups: &[u32], bits: u32) -> Self {
+        assert!((1..=28).contains(&bits));
+        let per_limb = (28 / bits) as usize;
+        groups.chunks(per_limb).rev().fold(Self::zero(), |acc, chunk| {
+            let limb = chunk.iter().rev().fold(0, |limb, g| (limb << bits) | g);
+            acc.mul_small(1 << (chunk.len() as u32 * bits)).add_small(limb)
+        })
+    }
+}
+
+// Operators on references, and on a reference with a value, for when the
+// operands are still needed afterwards
+macro_rules! ref_ops {
+    ($($op:ident $method:ident),*) => {$(
+        impl std::ops::$op<&Natural> for &Natural {
+            type Output = Natural;
+
+            fn $method(self, other: &Natural) -> Natural {
+                std::ops::$op::$method(self.clone(), other.clone())
+            }
+        }
+
+        impl std::ops::$op<Natural> for &Natural {
+            type Output = Natural;
+
+            fn $method(self, other: Natural) -> Natural {
+                std::ops::$op::$method(self.clone(), other)
+            }
+        }
+
+        impl std::ops::$op<&Natural> for Natural {
+            type Output = Natural;
+
+            fn $method(self, other: &Natural) -> Natural {
+                std::ops::$op::$method(self, other.clone())
+            }
+        }
+    )*};
+}
+
+ref_ops!(Add add, Sub sub, Mul mul, Div div, Rem rem);
+
+// Primitive operands that fit in a u32 take the short paths; wider ones
+// are converted first
+fn small<T: TryInto<u32>>(n: T) -> Option<u32> {
+    n.try_into().ok()
+}
+
+macro_rules! primitive_ops {
+    ($($t:ty),*) => {$(
+        impl std::ops::Add<$t> for &Natural {
+            type Output = Natural;
+
+            fn add(self, other: $t) -> Natural {
+                match small(other) {
+                    Some(other) => self.add_small(other),
+                    None => self + Natural::from(other)
+                }
+            }
+        }
+
+        impl std::ops::Sub<$t> for &Natural {
+            type Output = Natural;
+
+            fn sub(self, other: $t) -> Natural {
+                self - Natural::from(other)
+            }
+        }
+
+        impl std::ops::Mul<$t> for &Natural {
+            type Output = Natural;
+
+            fn mul(self, other: $t) -> Natural {
+                match small(other) {
+                    Some(other) => self.mul_small(other),
+                    None => self * Natural::from(other)
+                }
+            }
+        }
+
+        impl std::ops::Div<$t> for &Natural {
+            type Output = Natural;
+
+            fn div(self, other: $t) -> Natural {
+                match small(other) {
+                    Some(other) => self.div_rem_small(other).0,
+                    None => self / Natural::from(other)
+                }
+            }
+        }
+
+        impl std::ops::Rem<$t> for &Natural {
+            type Output = Natural;
+
+            fn rem(self, other: $t) -> Natural {
+                match small(other) {
+                    Some(other) => Natural::from(self.div_rem_small(other).1),
+                    None => self % Natural::from(other)
+                }
+            }
+        }
+
+        primitive_ops!(@forward $t, Add add, Sub sub, Mul mul, Div div, Rem rem);
+
+        impl std::ops::Add<Natural> for $t {
+            type Output = Natural;
+
+            fn add(self, other: Natural) -> Natural {
+                &other + self
+            }
+        }
+
+        impl std::ops::Sub<Natural> for $t {
+            type Output = Natural;
+
+            fn sub(self, other: Natural) -> Natural {
+                Natural::from(self) - other
+            }
+        }
+
+        impl std::ops::Mul<Natural> for $t {
+            type Output = Natural;
+
+            fn mul(self, other: Natural) -> Natural {
+                &other * self
+            }
+        }
+    )*};
+    (@forward $t:ty, $($op:ident $method:ident),*) => {$(
+        impl std::ops::$op<$t> for Natural {
+            type Output = Natural;
+
+            fn $method(self, other: $t) -> Natural {
+                std::ops::$op::$method(&self, other)
+            }
+        }
+    )*};
+}
+
+primitive_ops!(u8, u16, u32, u64, u128, usize);
+
+/// In place, so a running total reuses its buffer.
+impl std::ops::AddAssign<&Natural> for Natural {
+    fn add_assign(&mut self, other: &Natural) {
+        if self.digits.len() < other.digits.len() {
+            self.digits.resize(other.digits.len(), digit::Digit::Zero);
+        }
+        let mut cs: digit::CarrySum = Default::default();
+        for p in 0..self.digits.len() {
+            if p >= other.digits.len() && !cs.carry {
+                return;
+            }
+            cs = cs.add_two(self.digits[p], other.coefficient(p));
+            self.digits[p] = cs.sum;
+        }
+        if cs.carry {
+            self.digits.push(digit::Digit::One);
+        }
     }
 }
 
 impl std::ops::AddAssign for Natural {
     fn add_assign(&mut self, other: Self) {
-        *self = self.clone() + other;
+        *self += &other;
     }
 }
 
@@ -108,7 +898,7 @@ impl std::ops::Add for Natural {
             let a = self.coefficient(p);
             let b = other.coefficient(p);
             cs = cs.add_two(a, b);
-            digits.push(cs.sum.clone());
+            digits.push(cs.sum);
         }
 
         if cs.carry {
@@ -119,6 +909,8 @@ impl std::ops::Add for Natural {
     }
 }
 
+/// Panics if `other` is larger than `self`, as the primitive unsigned
+/// integers do in debug builds; see `checked_sub`.
 impl std::ops::Sub for Natural {
     type Output = Self;
 
@@ -134,16 +926,17 @@ impl std::ops::Sub for Natural {
                 let mut pp = p;
                 loop {
                     pp += 1;
+                    assert!(pp <= self.degree(), "attempt to subtract with overflow");
                     let c = self.coefficient(pp);
                     if c == digit::Digit::Zero {
                         continue;
                     }
-                    self.set_coefficient(pp, (c - digit::Digit::One).difference);
+                    self.digits[pp] = (c - digit::Digit::One).difference;
                     break;
                 }
                 // Unwind the borrow -- All zeros will become nines.
                 for ppp in (p+1)..pp {
-                    self.set_coefficient(ppp, digit::Digit::Nine);
+                    self.digits[ppp] = digit::Digit::Nine;
                 }
                 digits.push(bd.difference);
             } else {
@@ -152,23 +945,12 @@ impl std::ops::Sub for Natural {
             }
         }
 
-        // Remove leading zeros
-        loop {
-            if digits.len() > 1 && digits[digits.len() - 1] == digit::Digit::Zero {
-                digits.pop();
-            } else {
-                break;
-            }
-        }
-
-        Self::Output{ digits }
+        Self::from_digits(digits)
     }
 }
 
-impl std::ops::Mul for Natural {
-    type Output = Self;
-
-    fn mul(self, other: Self) -> Self::Output {
+impl Natural {
+    pub(crate) fn mul_schoolbook(&self, other: &Self) -> Self {
         let mut summands = vec![];
 
         for (i, a) in self.digits.iter().enumerate() {
@@ -179,7 +961,7 @@ impl std::ops::Mul for Natural {
             }
             for b in &other.digits {
                 cp = cp.mul_two(*a, *b);
-                digits.push(cp.product.clone());
+                digits.push(cp.product);
             }
             if cp.carry != digit::Digit::Zero {
                 digits.push(cp.carry);
@@ -189,36 +971,264 @@ impl std::ops::Mul for Natural {
 
         let mut total = Natural::zero();
         for summand in summands {
-            total = total + summand;
+            total += summand;
+        }
+        Self::from_digits(total.digits)
+    }
+
+    // (low, high) with self = high * 10^at + low
+    pub(crate) fn split_at(&self, at: usize) -> (Self, Self) {
+        let at = at.min(self.digits.len());
+        (Self::from_digits(self.digits[..at].to_vec()), Self::from_digits(self.digits[at..].to_vec()))
+    }
+
+    // self * 10^places
+    pub(crate) fn shifted(mut self, places: usize) -> Self {
+        if self == Self::zero() {
+            return self;
+        }
+        self.digits.splice(0..0, std::iter::repeat_n(digit::Digit::Zero, places));
+        self
+    }
+
+    // Three half-size products instead of four:
+    // (a 10^m + b)(c 10^m + d) = ac 10^2m + ((a + b)(c + d) - ac - bd) 10^m + bd
+    pub(crate) fn mul_karatsuba(&self, other: &Self, threshold: usize) -> Self {
+        if self.digits.len().min(other.digits.len()) < threshold.max(2) {
+            return self.mul_schoolbook(other);
         }
-        total
+        let m = self.digits.len().max(other.digits.len()) / 2;
+        let (b, a) = self.split_at(m);
+        let (d, c) = other.split_at(m);
+        let ac = a.mul_karatsuba(&c, threshold);
+        let bd = b.mul_karatsuba(&d, threshold);
+        let middle = (a + b).mul_karatsuba(&(c + d), threshold) - ac.clone() - bd.clone();
+        ac.shifted(2 * m) + middle.shifted(m) + bd
     }
 }
 
+/// A product of Euclid's quotient matrices `[[q, 1], [1, 0]]`, which takes
+/// a later pair of remainders back to an earlier one: `(a, b) = M (a', b')`.
+#[derive(Clone,Debug,PartialEq,Eq)]
+pub(crate) struct Quotients {
+    /// `[[m00, m01], [m10, m11]]`, by rows
+    pub(crate) matrix: [Natural; 4],
+    quotients: Vec<Natural>
+}
+
+impl Quotients {
+    fn identity() -> Self {
+        Self{ matrix: [Natural::one(), Natural::zero(), Natural::zero(), Natural::one()], quotients: vec![] }
+    }
+
+    /// The number of division steps, whose parity is the sign of the
+    /// determinant: one when even, minus one when odd.
+    pub(crate) fn steps(&self) -> usize {
+        self.quotients.len()
+    }
+
+    fn push(&mut self, q: Natural) {
+        let [m00, m01, m10, m11] = &self.matrix;
+        self.matrix = [m00 * &q + m01, m00.clone(), m10 * &q + m11, m10.clone()];
+        self.quotients.push(q);
+    }
+
+    // Drop the last quotient, moving the remainders it led to back a step
+    fn pop(&mut self, (x, y): (Natural, Natural)) -> (Natural, Natural) {
+        let q = self.quotients.pop().unwrap();
+        let [m00, m01, m10, m11] = &self.matrix;
+        self.matrix = [m01.clone(), m00 - &(m01 * &q), m11.clone(), m10 - &(m11 * &q)];
+        (q * &x + y, x)
+    }
+
+    fn then(&mut self, later: Self) {
+        if later.quotients.is_empty() {
+            return;
+        }
+        if self.quotients.is_empty() {
+            *self = later;
+            return;
+        }
+        let [m00, m01, m10, m11] = &self.matrix;
+        let [n00, n01, n10, n11] = &later.matrix;
+        self.matrix = [m00 * n00 + m01 * n10, m00 * n01 + m01 * n11, m10 * n00 + m11 * n10, m10 * n01 + m11 * n11];
+        self.quotients.extend(later.quotients);
+    }
+
+    // M^-1 (a, b) for a = x 10^p + low_a and b = y 10^p + low_b, given
+    // (x, y) = M^-1 (a / 10^p, b / 10^p), or None if either is negative
+    fn undo(&self, (x, y): (&Natural, &Natural), p: usize, (low_a, low_b): (&Natural, &Natural)) -> Option<(Natural, Natural)> {
+        let [m00, m01, m10, m11] = &self.matrix;
+        // The determinant is one after an even number of steps, else minus one
+        let combine = |high: &Natural, plus: Natural, minus: Natural| match self.steps() % 2 {
+            0 => (high.clone().shifted(p) + plus).checked_sub(&minus),
+            _ => (high.clone().shifted(p) + minus).checked_sub(&plus)
+        };
+        Some((combine(x, m11 * low_a, m01 * low_b)?, combine(y, m00 * low_b, m10 * low_a)?))
+    }
+}
+
+// Below this excess half_gcd takes Euclid's steps one at a time
+const HALF_GCD_BASE: usize = 32;
+
+/// Euclid's algorithm on `a > b` until the remainder falls below `10^s`,
+/// as the quotients taken and the remainders `a' >= 10^s > b'` reached.
+///
+/// The quotients come from a recursive call on leading digits alone,
+/// which agree with those of the whole pair until their remainders run
+/// short: first the leading halves of what is to be removed, then the
+/// leading halves of what is left. The digits split off only need
+/// multiplying by the matrix, and its last quotients are dropped while
+/// the result is not a pair of Euclid's remainders, decreasing and with
+/// `a'` still past `10^s`. That makes it `O(M(n) log n)` for `M(n)` the
+/// cost of a multiplication, rather than Euclid's `O(n^2)`.
+pub(crate) fn half_gcd(a: Natural, b: Natural, s: usize) -> (Quotients, (Natural, Natural)) {
+    let mut steps = Quotients::identity();
+    // Single steps gather in a matrix of their own, so that each costs a
+    // short multiplication rather than one the length of the whole
+    let mut single = Quotients::identity();
+    let (mut a, mut b) = (a, b);
+    while b.significant_len() > s {
+        let excess = a.digits.len() - s;
+        // Split off the digits below the leading 2 excess, which decide the
+        // quotients, or once that is all of them, halve the excess
+        let split = match excess {
+            _ if s > excess => Some((s - excess, excess)),
+            _ if excess >= HALF_GCD_BASE => Some((s, excess / 2 + 1)),
+            _ => None
+        };
+        if let Some((p, target)) = split {
+            let (low_a, top_a) = a.split_at(p);
+            let (low_b, top_b) = b.split_at(p);
+            if top_a > top_b {
+                let (mut leading, mut top) = half_gcd(top_a, top_b, target);
+                while leading.steps() > 0 {
+                    if let Some((x, y)) = leading.undo((&top.0, &top.1), p, (&low_a, &low_b)) {
+                        if x > y && x.significant_len() > s {
+                            (a, b) = (x, y);
+                            break;
+                        }
+                    }
+                    top = leading.pop(top);
+                }
+                if leading.steps() > 0 {
+                    steps.then(std::mem::replace(&mut single, Quotients::identity()));
+                    steps.then(leading);
+                    continue;
+                }
+            }
+        }
+        let (q, r) = a.div_rem(&b);
+        single.push(q);
+        (a, b) = (b, r);
+    }
+    steps.then(single);
+    (steps, (a, b))
+}
+
+/// Schoolbook multiplication, switching to Karatsuba's method once both
+/// operands reach the current `Thresholds::karatsuba` length.
+impl std::ops::Mul for Natural {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self::Output {
+        self.mul_karatsuba(&other, Thresholds::current().karatsuba)
+    }
+}
+
+/// Panics if `other` is zero; see `checked_div`.
 impl std::ops::Div for Natural {
     type Output = Self;
 
     fn div(self, other: Self) -> Self::Output {
-        let mut n = Natural::one();
-        let mut product = other.clone();
-        while self >= product {
-            n.increment();
-            product += other.clone();
-        }
-        n - Natural::one()
+        self.div_rem(&other).0
     }
 }
 
+/// Panics if `other` is zero; see `checked_rem`.
 impl std::ops::Rem for Natural {
     type Output = Self;
 
     fn rem(self, other: Self) -> Self::Output {
-        let original = other.clone();
-        let mut product = other.clone();
-        while self > product {
-            product += other.clone();
+        self.div_rem(&other).1
+    }
+}
+
+/// In place, like `AddAssign`.
+///
+/// Panics if `other` is larger than `self`.
+impl std::ops::SubAssign<&Natural> for Natural {
+    fn sub_assign(&mut self, other: &Natural) {
+        let len = other.significant_len();
+        assert!(len <= self.digits.len(), "attempt to subtract with overflow");
+        let mut borrow = false;
+        for p in 0..self.digits.len() {
+            if p >= len && !borrow {
+                break;
+            }
+            let mut d = self.digits[p].as_u8() as i8 - other.coefficient(p).as_u8() as i8 - borrow as i8;
+            borrow = d < 0;
+            if borrow {
+                d += 10;
+            }
+            self.digits[p] = (d as u8).try_into().unwrap();
         }
-        original - (product - self)
+        assert!(!borrow, "attempt to subtract with overflow");
+        self.normalize();
+    }
+}
+
+impl std::ops::SubAssign for Natural {
+    fn sub_assign(&mut self, other: Self) {
+        *self -= &other;
+    }
+}
+
+impl std::ops::MulAssign for Natural {
+    fn mul_assign(&mut self, other: Self) {
+        let value = std::mem::replace(self, Natural::zero());
+        *self = value * other;
+    }
+}
+
+impl std::ops::RemAssign for Natural {
+    fn rem_assign(&mut self, other: Self) {
+        let value = std::mem::replace(self, Natural::zero());
+        *self = value % other;
+    }
+}
+
+/// Adds into a single accumulator with `AddAssign`.
+impl std::iter::Sum for Natural {
+    fn sum<I: Iterator<Item = Natural>>(iter: I) -> Self {
+        iter.fold(Natural::zero(), |mut acc, x| {
+            acc += &x;
+            acc
+        })
+    }
+}
+
+impl<'a> std::iter::Sum<&'a Natural> for Natural {
+    fn sum<I: Iterator<Item = &'a Natural>>(iter: I) -> Self {
+        iter.fold(Natural::zero(), |mut acc, x| {
+            acc += x;
+            acc
+        })
+    }
+}
+
+/// Multiplication cannot happen in place, so each step makes a new
+/// product, but borrowed factors are never copied.
+impl std::iter::Product for Natural {
+    fn product<I: Iterator<Item = Natural>>(iter: I) -> Self {
+        iter.fold(Natural::one(), |acc, x| acc * x)
+    }
+}
+
+impl<'a> std::iter::Product<&'a Natural> for Natural {
+    fn product<I: Iterator<Item = &'a Natural>>(iter: I) -> Self {
+        let threshold = Thresholds::current().karatsuba;
+        iter.fold(Natural::one(), |acc, x| acc.mul_karatsuba(x, threshold))
     }
 }
 
@@ -238,6 +1248,30 @@ mod tests {
         assert_eq!(x.degree(), 2);
     }
 
+    #[test]
+    fn normalization() {
+        let n = |s: &str| s.parse::<Natural>().unwrap();
+        assert_eq!(n("007"), n("7"));
+        assert_eq!(n("007").degree(), 0);
+        assert_eq!(n("000").to_string(), "0");
+        assert!(n("007") < n("10"));
+        assert!(n("0100").is_normalized());
+
+        let mut x = n("105");
+        x.set_coefficient(2, digit::Digit::Zero);
+        assert_eq!(x.degree(), 0);
+        assert!(x.is_normalized());
+        assert_eq!(x, n("5"));
+
+        let mut x = Natural{ digits: vec![digit::Digit::One, digit::Digit::Zero] };
+        assert!(!x.is_normalized());
+        x.normalize();
+        assert_eq!(x, n("1"));
+        let mut x = Natural{ digits: vec![] };
+        x.normalize();
+        assert_eq!(x, Natural::zero());
+    }
+
     #[test]
     fn coefficient() {
         let x: Natural = "123".parse().unwrap();
@@ -277,13 +1311,13 @@ mod tests {
     #[test]
     fn mul_80bit() {
         let two_1: Natural = "2".parse().unwrap();
-        let two_2 = two_1.clone() * two_1.clone();
-        let two_4 = two_2.clone() * two_2;
-        let two_8 = two_4.clone() * two_4;
-        let two_16 = two_8.clone() * two_8;
-        let two_32 = two_16.clone() * two_16.clone();
-        let two_64 = two_32.clone() * two_32;
-        let two_80 = two_64 * two_16;
+        let two_2 = &two_1 * &two_1;
+        let two_4 = &two_2 * &two_2;
+        let two_8 = &two_4 * &two_4;
+        let two_16 = &two_8 * &two_8;
+        let two_32 = &two_16 * &two_16;
+        let two_64 = &two_32 * &two_32;
+        let two_80 = two_64 * &two_16;
         assert_eq!(&format!("{}", two_80), "1208925819614629174706176");
     }
 
@@ -370,4 +1404,456 @@ mod tests {
         let b: Natural = "1024".parse().unwrap();
         assert_eq!(a % b, "351".parse().unwrap());
     }
+
+    #[test]
+    fn mul_zero() {
+        let a: Natural = "0".parse().unwrap();
+        let b: Natural = "123".parse().unwrap();
+        assert_eq!(a * b, Natural::zero());
+    }
+
+    #[test]
+    fn rem_exact() {
+        let a: Natural = "10".parse().unwrap();
+        let b: Natural = "5".parse().unwrap();
+        assert_eq!(a % b, Natural::zero());
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!("".parse::<Natural>(), Err(ParseNaturalError::Empty));
+        assert_eq!("12a".parse::<Natural>(), Err(ParseNaturalError::InvalidCharacter('a')));
+    }
+
+    #[test]
+    fn bytes() {
+        let x: Natural = "1099511627776".parse().unwrap();
+        assert_eq!(x.to_bytes_be(), vec![1, 0, 0, 0, 0, 0]);
+        assert_eq!(Natural::from_bytes_be(&[0, 1, 0, 0, 0, 0, 0]), x);
+        assert_eq!(Natural::from_bytes_le(&x.to_bytes_le()), x);
+        assert!(Natural::zero().to_bytes_be().is_empty());
+    }
+
+    #[test]
+    fn approximate_compare() {
+        let a: Natural = "123456".parse().unwrap();
+        let b: Natural = "123999".parse().unwrap();
+        let c: Natural = "0099999".parse().unwrap();
+        assert_eq!(a.compare_magnitude_only(&b), Ordering::Equal);
+        assert_eq!(c.compare_magnitude_only(&a), Ordering::Less);
+        assert_eq!(a.approx_cmp_with_tolerance(&b, 3), Ordering::Equal);
+        assert_eq!(a.approx_cmp_with_tolerance(&b, 4), Ordering::Less);
+        assert_eq!(a.approx_cmp_with_tolerance(&c, 1), Ordering::Greater);
+    }
+
+    #[test]
+    fn rem_assign() {
+        let mut x: Natural = "100".parse().unwrap();
+        x %= "7".parse().unwrap();
+        assert_eq!(x, "2".parse().unwrap());
+    }
+
+    #[test]
+    fn div_rem() {
+        let x: Natural = "1000".parse().unwrap();
+        let (q, r) = x.div_rem(&"0007".parse().unwrap());
+        assert_eq!((q.to_string(), r.to_string()), ("142".to_string(), "6".to_string()));
+        assert_eq!(Natural::one().div_rem(&x), (Natural::zero(), Natural::one()));
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to divide by zero")]
+    fn divide_by_zero() {
+        let _ = Natural::one() / Natural::zero();
+    }
+
+    #[test]
+    fn long_division() {
+        let x: Natural = "12345678901234567890123456789012345678901234567890".parse().unwrap();
+        let (q, r) = x.div_rem(&"7".parse().unwrap());
+        assert_eq!(q.to_string(), "1763668414462081127160493827001763668414462081127");
+        assert_eq!(r.to_string(), "1");
+
+        let y: Natural = "98765432109876543210987".parse().unwrap();
+        let (q, r) = x.div_rem(&y);
+        assert_eq!(q.clone() * y.clone() + r.clone(), x);
+        assert!(r < y);
+
+        // Long divisors agreeing in their leading digits stress the estimate
+        let d: Natural = "100000000000000000000000001".parse().unwrap();
+        let n = d.clone() * "3".parse::<Natural>().unwrap();
+        assert_eq!(n.div_rem(&d), ("3".parse().unwrap(), Natural::zero()));
+        let n = n - Natural::one();
+        assert_eq!(n.div_rem(&d).0, "2".parse().unwrap());
+    }
+
+    #[test]
+    fn karatsuba() {
+        let x: Natural = "31415926535897932384626433832795028841971693993751058209749445923078164062862".parse().unwrap();
+        let y: Natural = "27182818284590452353602874713526624977572470936999595749669676277240766303535".parse().unwrap();
+        let expected = x.mul_schoolbook(&y);
+        for threshold in [2, 5, 16, 100] {
+            assert_eq!(x.mul_karatsuba(&y, threshold), expected);
+        }
+        let short: Natural = "12345".parse().unwrap();
+        assert_eq!(x.mul_karatsuba(&short, 2), x.mul_schoolbook(&short));
+        assert_eq!(x.mul_karatsuba(&Natural::zero(), 2), Natural::zero());
+    }
+
+    #[test]
+    fn pow() {
+        let two: Natural = "2".parse().unwrap();
+        assert_eq!(two.pow(10), "1024".parse().unwrap());
+        assert_eq!(Natural::zero().pow(0), Natural::one());
+        assert_eq!(Natural::zero().pow(3), Natural::zero());
+        let big = two.pow(4096);
+        assert_eq!(big.to_string().len(), 1234);
+        assert!(big.to_string().ends_with("4190336"));
+    }
+
+    #[test]
+    fn gcd() {
+        let n = |s: &str| s.parse::<Natural>().unwrap();
+        assert_eq!(n("48").gcd(&n("180")), n("12"));
+        assert_eq!(n("0").gcd(&n("5")), n("5"));
+        assert_eq!(n("5").gcd(&n("0")), n("5"));
+        assert_eq!(n("17").gcd(&n("4")), n("1"));
+        // Consecutive Fibonacci numbers are the slowest case
+        assert_eq!(n("354224848179261915075").gcd(&n("218922995834555169026")), n("1"));
+    }
+
+    #[test]
+    fn half_gcd_matches_euclid() {
+        let g = Natural::from(3u32).pow(800) + Natural::one();
+        let a = Natural::from(7u32).pow(2000) * &g;
+        let b = (Natural::from(11u32).pow(1600) + Natural::from(5u32)) * &g;
+        assert!(b.to_string().len() > 2000);
+        let expected = a.gcd_with_cutoff(&b, usize::MAX);
+        assert!(expected >= g);
+        assert_eq!(a.gcd_with_cutoff(&b, 0), expected);
+        assert_eq!(b.gcd_with_cutoff(&a, 1000), expected);
+        // Every quotient is one, the most steps for the length
+        let (f, g) = (Natural::fibonacci(10000), Natural::fibonacci(9999));
+        assert_eq!(f.gcd_with_cutoff(&g, 0), Natural::one());
+        assert_eq!(f.gcd_with_cutoff(&(f.clone() * 3u32), 0), f);
+    }
+
+    #[test]
+    fn half_gcd_stops_where_euclid_does() {
+        let a = Natural::from(13u32).pow(1000);
+        let b = Natural::from(6u32).pow(1250) + Natural::from(17u32);
+        for s in [200, 600, 1000] {
+            let (steps, (x, y)) = half_gcd(a.clone(), b.clone(), s);
+            let (mut quotients, (mut r0, mut r1)) = (vec![], (a.clone(), b.clone()));
+            while r1.significant_len() > s {
+                let (q, r) = r0.div_rem(&r1);
+                quotients.push(q);
+                (r0, r1) = (r1, r);
+            }
+            assert_eq!((&steps.quotients, &x, &y), (&quotients, &r0, &r1));
+            let [m00, m01, m10, m11] = &steps.matrix;
+            assert_eq!((m00 * &x + m01 * &y, m10 * &x + m11 * &y), (a.clone(), b.clone()));
+        }
+    }
+
+    #[test]
+    fn lcm() {
+        let n = |s: &str| s.parse::<Natural>().unwrap();
+        assert_eq!(n("4").lcm(&n("6")), n("12"));
+        assert_eq!(n("0").lcm(&n("6")), n("0"));
+        assert_eq!(n("0").lcm(&n("0")), n("0"));
+        assert_eq!(n("123456789").lcm(&n("987654321")), n("13548070123626141"));
+    }
+
+    #[test]
+    fn nth_root() {
+        let n = |s: &str| s.parse::<Natural>().unwrap();
+        assert_eq!(n("27").nth_root(3), n("3"));
+        assert_eq!(n("26").nth_root(3), n("2"));
+        assert_eq!(n("1000000").nth_root(2), n("1000"));
+        assert_eq!(n("999999").nth_root(2), n("999"));
+        assert_eq!(n("0").nth_root(5), n("0"));
+        assert_eq!(n("1").nth_root(5), n("1"));
+        assert_eq!(n("0042").nth_root(1), n("42"));
+        assert_eq!(n("255").nth_root(8), n("1"));
+        assert_eq!(n("256").nth_root(8), n("2"));
+        let x = n("123456789123456789");
+        for k in 2..8 {
+            let power = x.pow(k);
+            assert_eq!(power.nth_root(k), x);
+            assert_eq!((power - Natural::one()).nth_root(k), x.clone() - Natural::one());
+        }
+    }
+
+    #[test]
+    fn fibonacci_and_lucas() {
+        let n = |s: &str| s.parse::<Natural>().unwrap();
+        let fibs: Vec<String> = (0..12).map(|k| Natural::fibonacci(k).to_string()).collect();
+        assert_eq!(fibs, ["0", "1", "1", "2", "3", "5", "8", "13", "21", "34", "55", "89"]);
+        let lucas: Vec<String> = (0..8).map(|k| Natural::lucas(k).to_string()).collect();
+        assert_eq!(lucas, ["2", "1", "3", "4", "7", "11", "18", "29"]);
+        assert_eq!(Natural::fibonacci(100), n("354224848179261915075"));
+        assert_eq!(Natural::lucas(100), n("792070839848372253127"));
+        let big = Natural::fibonacci(10_000);
+        assert_eq!(big.to_string().len(), 2090);
+        assert!(big.to_string().starts_with("33644764876431783266"));
+        assert!(big.to_string().ends_with("66073310059947366875"));
+    }
+
+    #[test]
+    fn mod_pow() {
+        let n = |s: &str| s.parse::<Natural>().unwrap();
+        assert_eq!(n("4").mod_pow(&n("13"), &n("497")), n("445"));
+        assert_eq!(n("2").mod_pow(&n("0"), &n("7")), n("1"));
+        assert_eq!(n("2").mod_pow(&n("0"), &n("1")), n("0"));
+        assert_eq!(n("0").mod_pow(&n("0"), &n("7")), n("1"));
+        // Fermat: 2^(p - 1) = 1 mod p for the prime 2^61 - 1
+        let p = n("2305843009213693951");
+        assert_eq!(n("2").mod_pow(&(p.clone() - Natural::one()), &p), n("1"));
+        assert_eq!(n("3").mod_pow(&n("200"), &n("1000000007")), n("3").pow(200) % n("1000000007"));
+    }
+
+    #[test]
+    fn from_unsigned() {
+        assert_eq!(Natural::from(0u8), Natural::zero());
+        assert_eq!(Natural::from(255u8).to_string(), "255");
+        assert_eq!(Natural::from(65535u16).to_string(), "65535");
+        assert_eq!(Natural::from(u32::MAX).to_string(), u32::MAX.to_string());
+        assert_eq!(Natural::from(u64::MAX).to_string(), u64::MAX.to_string());
+        assert_eq!(Natural::from(u128::MAX).to_string(), u128::MAX.to_string());
+        assert_eq!(Natural::from(1000usize).to_string(), "1000");
+    }
+
+    #[test]
+    fn parse_unicode_digits() {
+        let unicode = ParseOptions{ unicode_digits: true, ..Default::default() };
+        let expected: Natural = "1234".parse().unwrap();
+        for s in ["\u{661}\u{662}\u{663}\u{664}", "\u{967}\u{968}\u{969}\u{96A}", "\u{FF11}\u{FF12}\u{FF13}\u{FF14}", "1\u{662}\u{969}\u{FF14}"] {
+            assert_eq!(Natural::parse_with(s, unicode), Ok(expected.clone()));
+            assert_eq!(s.parse::<Natural>(), Err(ParseNaturalError::InvalidCharacter(s.chars().find(|c| !c.is_ascii()).unwrap())));
+        }
+        assert_eq!(Natural::parse_with("1\u{BD}", unicode), Err(ParseNaturalError::InvalidCharacter('\u{BD}')));
+        assert_eq!(Natural::parse_with("", unicode), Err(ParseNaturalError::Empty));
+    }
+
+    #[test]
+    fn parse_length_limit() {
+        let limited = ParseOptions{ max_len: Some(4), ..Default::default() };
+        assert_eq!(Natural::parse_with("1234", limited), Ok("1234".parse().unwrap()));
+        assert_eq!(Natural::parse_with("12345", limited), Err(ParseNaturalError::TooLong(4)));
+        // Rejected on length alone, before the invalid characters
+        assert_eq!(Natural::parse_with("xxxxx", limited), Err(ParseNaturalError::TooLong(4)));
+
+        // Other tests parse in parallel, so the global limit stays above
+        // anything they use
+        let long = "x".repeat(1_000_001);
+        Limits{ max_parse_len: Some(1_000_000) }.install();
+        let result = long.parse::<Natural>();
+        let per_call = Natural::parse_with(&long, ParseOptions{ max_len: Some(usize::MAX - 1), ..Default::default() });
+        Limits::default().install();
+        assert_eq!(result, Err(ParseNaturalError::TooLong(1_000_000)));
+        // The per-call limit wins, so the digits are read
+        assert_eq!(per_call, Err(ParseNaturalError::InvalidCharacter('x')));
+        assert_eq!(Limits::current(), Limits{ max_parse_len: None });
+    }
+
+    #[test]
+    fn try_into_unsigned() {
+        let n = |s: &str| s.parse::<Natural>().unwrap();
+        assert_eq!(n("0").to_u64(), Some(0));
+        assert_eq!(n("00018446744073709551615").to_u64(), Some(u64::MAX));
+        assert_eq!(n("18446744073709551616").to_u64(), None);
+        assert_eq!(u128::try_from(&n("18446744073709551616")), Ok(1 << 64));
+        assert_eq!(u128::try_from(&Natural::from(u128::MAX)), Ok(u128::MAX));
+        assert_eq!(u128::try_from(&(Natural::from(u128::MAX) + Natural::one())), Err(TryFromNaturalError::TooLarge));
+        assert_eq!(usize::try_from(&n("42")), Ok(42));
+    }
+
+    #[test]
+    fn saturating_conversions() {
+        let n = |s: &str| s.parse::<Natural>().unwrap();
+        assert_eq!(n("42").to_u64_saturating(), (42, false));
+        assert_eq!(n("18446744073709551615").to_u64_saturating(), (u64::MAX, false));
+        assert_eq!(n("18446744073709551616").to_u64_saturating(), (u64::MAX, true));
+        assert_eq!(n("18446744073709551616").to_u128_saturating(), (1 << 64, false));
+        assert_eq!(n(&"9".repeat(40)).to_u128_saturating(), (u128::MAX, true));
+    }
+
+    #[test]
+    fn assign_ops() {
+        let n = |s: &str| s.parse::<Natural>().unwrap();
+        let mut x = n("999");
+        x += n("1");
+        assert_eq!(x, n("1000"));
+        x += n("123456");
+        assert_eq!(x, n("124456"));
+        x += n("0");
+        assert_eq!(x, n("124456"));
+        x *= n("1000");
+        assert_eq!(x, n("124456000"));
+        x -= n("124455001");
+        assert_eq!(x, n("999"));
+        x -= &n("0999");
+        assert_eq!(x, n("0"));
+    }
+
+    #[test]
+    fn checked_sub() {
+        let n = |s: &str| s.parse::<Natural>().unwrap();
+        assert_eq!(n("1000").checked_sub(&n("1")), Some(n("999")));
+        assert_eq!(n("5").checked_sub(&n("5")), Some(n("0")));
+        assert_eq!(n("5").checked_sub(&n("6")), None);
+        assert_eq!(n("0005").checked_sub(&n("06")), None);
+        assert_eq!(n("100").checked_sub(&n("00099")), Some(n("1")));
+        assert_eq!(n("100").saturating_sub(&n("1")), n("99"));
+        assert_eq!(n("100").saturating_sub(&n("101")), n("0"));
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to subtract with overflow")]
+    fn sub_overflow() {
+        let _ = Natural::from(1000u32) - Natural::from(1001u32);
+    }
+
+    #[test]
+    fn checked_div_rem() {
+        let n = |s: &str| s.parse::<Natural>().unwrap();
+        assert_eq!(n("17").checked_div(&n("5")), Some(n("3")));
+        assert_eq!(n("17").checked_rem(&n("5")), Some(n("2")));
+        assert_eq!(n("17").checked_div(&n("0")), None);
+        assert_eq!(n("17").checked_rem(&n("000")), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to divide by zero")]
+    fn rem_by_zero() {
+        let _ = Natural::from(1u8) % Natural::zero();
+    }
+
+    #[test]
+    fn reference_ops() {
+        let a: Natural = "1000".parse().unwrap();
+        let b: Natural = "7".parse().unwrap();
+        assert_eq!(&a + &b, "1007".parse().unwrap());
+        assert_eq!(&a - &b, "993".parse().unwrap());
+        assert_eq!(&a * &b, "7000".parse().unwrap());
+        assert_eq!(&a / &b, "142".parse().unwrap());
+        assert_eq!(&a % &b, "6".parse().unwrap());
+        assert_eq!(&a + b.clone(), a.clone() + &b);
+        assert_eq!(a.clone() * &b, &a * b);
+    }
+
+    #[test]
+    fn base_billion_chunks() {
+        let x: Natural = "1234567890123456789".parse().unwrap();
+        assert_eq!(x.to_u32_chunks_le(), vec![123456789, 234567890, 1]);
+        assert_eq!(x.to_u32_chunks_be(), vec![1, 234567890, 123456789]);
+        assert_eq!(Natural::from_u32_chunks_le(&[123456789, 234567890, 1]), Ok(x.clone()));
+        assert_eq!(Natural::from_u32_chunks_be(&[0, 1, 234567890, 123456789]), Ok(x));
+        assert_eq!(Natural::zero().to_u32_chunks_le(), Vec::<u32>::new());
+        assert_eq!(Natural::from_u32_chunks_le(&[]), Ok(Natural::zero()));
+        assert_eq!(Natural::from_u32_chunks_le(&[1_000_000_000]), Err(FromChunksError::OutOfRange(1_000_000_000)));
+    }
+
+    #[test]
+    fn primitive_operands() {
+        let n = |s: &str| s.parse::<Natural>().unwrap();
+        let x = n("123456789012345678901234567890");
+        assert_eq!(&x + 10u8, n("123456789012345678901234567900"));
+        assert_eq!(x.clone() * 1000u32, n("123456789012345678901234567890000"));
+        assert_eq!(&x * u64::MAX, x.clone() * Natural::from(u64::MAX));
+        assert_eq!(&x - 890u16, n("123456789012345678901234567000"));
+        assert_eq!(&x / 1_000_000_000u64, n("123456789012345678901"));
+        assert_eq!(&x % 1_000_000_000_000u64, n("901234567890"));
+        assert_eq!(x.clone() / u128::MAX, Natural::zero());
+        assert_eq!(5u64 + n("7"), n("12"));
+        assert_eq!(3usize * n("7"), n("21"));
+        assert_eq!(10u32 - n("7"), n("3"));
+    }
+
+    #[test]
+    fn sums_and_products() {
+        let n = |s: &str| s.parse::<Natural>().unwrap();
+        let values = vec![n("999999999999999999999"), n("1"), n("0042")];
+        assert_eq!(values.iter().sum::<Natural>(), n("1000000000000000000042"));
+        assert_eq!(values.iter().product::<Natural>(), n("41999999999999999999958"));
+        assert_eq!(values.into_iter().sum::<Natural>(), n("1000000000000000000042"));
+        assert_eq!((1..=20u32).map(Natural::from).product::<Natural>(), n("2432902008176640000"));
+        assert_eq!(std::iter::empty::<Natural>().sum::<Natural>(), Natural::zero());
+        assert_eq!(std::iter::empty::<&Natural>().product::<Natural>(), Natural::one());
+    }
+
+    #[test]
+    fn hexadecimal() {
+        let n = |s: &str| s.parse::<Natural>().unwrap();
+        let x = n("340282366920938463463374607431768211455");
+        assert_eq!(format!("{:x}", x), "ffffffffffffffffffffffffffffffff");
+        assert_eq!(format!("{:X}", n("3735928559")), "DEADBEEF");
+        assert_eq!(format!("{:#x}", n("255")), "0xff");
+        assert_eq!(format!("{:08x}", n("255")), "000000ff");
+        assert_eq!(format!("{:#06X}", n("255")), "0x00FF");
+        assert_eq!(format!("{:x}", Natural::zero()), "0");
+        assert_eq!(Natural::from_str_radix("ffffffffffffffffffffffffffffffff", 16), Ok(x));
+        assert_eq!(Natural::from_str_radix("DeadBeef", 16), Ok(n("3735928559")));
+        assert_eq!(Natural::from_str_radix("00ff", 16), Ok(n("255")));
+        assert_eq!(Natural::from_str_radix("123", 10), Ok(n("123")));
+        assert_eq!(Natural::from_str_radix("", 16), Err(ParseNaturalError::Empty));
+        assert_eq!(Natural::from_str_radix("0xff", 16), Err(ParseNaturalError::InvalidCharacter('x')));
+        assert_eq!(Natural::from_str_radix("fg", 16), Err(ParseNaturalError::InvalidCharacter('g')));
+    }
+
+    #[test]
+    #[should_panic(expected = "radix 37 is not in 2..=36")]
+    fn unsupported_radix() {
+        let _ = Natural::from_str_radix("1", 37);
+    }
+
+    #[test]
+    fn binary() {
+        let n = |s: &str| s.parse::<Natural>().unwrap();
+        assert_eq!(format!("{:b}", n("10")), "1010");
+        assert_eq!(format!("{:#010b}", n("5")), "0b00000101");
+        assert_eq!(format!("{:b}", Natural::zero()), "0");
+        let x = n("2").pow(1000) - Natural::one();
+        assert_eq!(format!("{:b}", x), "1".repeat(1000));
+        assert_eq!(Natural::from_str_radix(&"1".repeat(1000), 2), Ok(x));
+        assert_eq!(Natural::from_str_radix("0001010", 2), Ok(n("10")));
+        assert_eq!(Natural::from_str_radix("102", 2), Err(ParseNaturalError::InvalidCharacter('2')));
+    }
+
+    #[test]
+    fn octal() {
+        let n = |s: &str| s.parse::<Natural>().unwrap();
+        assert_eq!(format!("{:o}", n("511")), "777");
+        assert_eq!(format!("{:#o}", n("8")), "0o10");
+        assert_eq!(format!("{:o}", Natural::zero()), "0");
+        let x = n("2").pow(300);
+        assert_eq!(format!("{:o}", x), format!("1{}", "0".repeat(100)));
+        assert_eq!(Natural::from_str_radix(&format!("{:o}", x), 8), Ok(x));
+        assert_eq!(Natural::from_str_radix("0755", 8), Ok(n("493")));
+        assert_eq!(Natural::from_str_radix("8", 8), Err(ParseNaturalError::InvalidCharacter('8')));
+    }
+
+    #[test]
+    fn any_radix() {
+        let n = |s: &str| s.parse::<Natural>().unwrap();
+        assert_eq!(n("35").to_str_radix(36), "z");
+        assert_eq!(n("1295").to_str_radix(36), "zz");
+        assert_eq!(Natural::from_str_radix("ZZ", 36), Ok(n("1295")));
+        assert_eq!(n("100").to_str_radix(3), "10201");
+        assert_eq!(n("255").to_str_radix(16), "ff");
+        assert_eq!(Natural::zero().to_str_radix(7), "0");
+        assert_eq!(Natural::from_str_radix("0", 7), Ok(Natural::zero()));
+        assert_eq!(Natural::from_str_radix("009", 7), Err(ParseNaturalError::InvalidCharacter('9')));
+        // 3^40 has 41 digits in base 3, more than one chunk
+        let x = n("3").pow(40);
+        assert_eq!(x.to_str_radix(3), format!("1{}", "0".repeat(40)));
+        let y = n("123456789012345678901234567890123456789");
+        assert_eq!(y.to_str_radix(36), "5hy8cqpp6qj5vz0m8iov0uej9");
+        assert_eq!(y.to_str_radix(7), "1103504602560214512564043620010366601665655241");
+        for radix in 2..=36 {
+            assert_eq!(Natural::from_str_radix(&y.to_str_radix(radix), radix), Ok(y.clone()));
+            assert_eq!(Natural::from_str_radix(&x.to_str_radix(radix), radix), Ok(x.clone()));
+        }
+    }
 }
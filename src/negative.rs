@@ -0,0 +1,82 @@
+//! Negative Number Styles
+//!
+//! Financial reports write negatives as `(1234)` or `1234-` as often as
+//! `-1234`. `format_signed` writes an `Integer` in any of these styles,
+//! and can pad non-negative values so that the digits of a column line up
+//! whichever way its negatives are marked.
+
+use crate::integer::Integer;
+
+/// Where the sign of a negative value goes.
+#[derive(Clone,Copy,Debug,Default,PartialEq,Eq)]
+pub enum NegativeStyle {
+    /// `-1234`
+    #[default]
+    LeadingMinus,
+    /// `(1234)`, as in accounting
+    Parentheses,
+    /// `1234-`, as on many ledgers and mainframe reports
+    TrailingMinus
+}
+
+/// How `format_signed` writes an `Integer`.
+#[derive(Clone,Copy,Debug,Default,PartialEq,Eq)]
+pub struct SignFormat {
+    pub negative: NegativeStyle,
+    /// Pad non-negative values with a space where a negative has a sign,
+    /// so that right-aligned columns keep their digits aligned
+    pub align: bool
+}
+
+impl Integer {
+    /// This value in the given style, such as `(1234)` for -1234 in
+    /// parentheses. Zero is never negative.
+    pub fn format_signed(&self, format: SignFormat) -> String {
+        let digits = self.magnitude().to_string();
+        match (self.is_negative(), format.negative, format.align) {
+            (true, NegativeStyle::LeadingMinus, _) => format!("-{}", digits),
+            (true, NegativeStyle::Parentheses, _) => format!("({})", digits),
+            (true, NegativeStyle::TrailingMinus, _) => format!("{}-", digits),
+            (false, NegativeStyle::LeadingMinus, true) => format!(" {}", digits),
+            (false, _, true) => format!("{} ", digits),
+            (false, _, false) => digits
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn i(s: &str) -> Integer {
+        s.parse().unwrap()
+    }
+
+    fn style(negative: NegativeStyle) -> SignFormat {
+        SignFormat{ negative, align: false }
+    }
+
+    #[test]
+    fn styles() {
+        assert_eq!(i("-1234").format_signed(SignFormat::default()), "-1234");
+        assert_eq!(i("-1234").format_signed(style(NegativeStyle::Parentheses)), "(1234)");
+        assert_eq!(i("-1234").format_signed(style(NegativeStyle::TrailingMinus)), "1234-");
+        for negative in [NegativeStyle::LeadingMinus, NegativeStyle::Parentheses, NegativeStyle::TrailingMinus] {
+            assert_eq!(i("1234").format_signed(style(negative)), "1234");
+            assert_eq!(i("-0").format_signed(style(negative)), "0");
+        }
+        let big = format!("-{}", "9".repeat(40));
+        assert_eq!(i(&big).format_signed(style(NegativeStyle::Parentheses)), format!("({})", &big[1..]));
+    }
+
+    #[test]
+    fn aligned() {
+        let column = |negative| {
+            let format = SignFormat{ negative, align: true };
+            ["-12", "340", "0"].iter().map(|s| format!("{:>6}", i(s).format_signed(format))).collect::<Vec<_>>()
+        };
+        assert_eq!(column(NegativeStyle::LeadingMinus), ["   -12", "   340", "     0"]);
+        assert_eq!(column(NegativeStyle::Parentheses), ["  (12)", "  340 ", "    0 "]);
+        assert_eq!(column(NegativeStyle::TrailingMinus), ["   12-", "  340 ", "    0 "]);
+    }
+}
@@ -0,0 +1,245 @@
+//! Out-of-core Arithmetic
+//!
+//! A `DiskNatural` lives in a file of ASCII digits, least significant
+//! first, so that both operands and the result can be streamed in the
+//! order carries travel. Only a few blocks of digits are in memory at
+//! once, whatever the size of the numbers.
+//!
+//! This is slow: addition is one pass over the files, but multiplication
+//! is schoolbook over blocks, quadratic in the number of digits and with a
+//! read and write of the result file for every pair of blocks. It is meant
+//! for numbers that cannot be held in memory at all.
+
+use crate::digit::Digit;
+use crate::natural::Natural;
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufReader, BufWriter, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+/// Digits read or multiplied at a time.
+pub const BLOCK: usize = 1 << 16;
+
+#[derive(Clone,Debug,PartialEq,Eq)]
+pub struct DiskNatural {
+    path: PathBuf
+}
+
+fn invalid(b: u8) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, format!("{:?} is not a digit", char::from(b)))
+}
+
+// Fill as much of `buf` as the reader has left, returning how much that is
+fn read_block(reader: &mut impl Read, buf: &mut [u8]) -> io::Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..])? {
+            0 => break,
+            n => filled += n
+        }
+    }
+    match buf[..filled].iter().find(|b| !b.is_ascii_digit()) {
+        Some(b) => Err(invalid(*b)),
+        None => Ok(filled)
+    }
+}
+
+// Add little-endian ASCII `digits` into the file at `offset`, carrying as
+// far as needed. The file must be long enough to hold the final carry.
+fn add_at(file: &mut File, offset: u64, digits: &[u8]) -> io::Result<()> {
+    let mut buf = vec![0; digits.len().max(1)];
+    let mut offset = offset;
+    let mut carry = 0;
+    let mut first = true;
+    while first || carry > 0 {
+        file.seek(SeekFrom::Start(offset))?;
+        let n = read_block(file, &mut buf)?;
+        for p in 0..n {
+            let addend = if first { digits[p] - b'0' } else { 0 };
+            let s = buf[p] - b'0' + addend + carry;
+            buf[p] = s % 10 + b'0';
+            carry = s / 10;
+        }
+        file.seek(SeekFrom::Start(offset))?;
+        file.write_all(&buf[..n])?;
+        if n == 0 && carry > 0 {
+            return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "carry ran off the end of the file"));
+        }
+        offset += n as u64;
+        first = false;
+    }
+    Ok(())
+}
+
+impl DiskNatural {
+    /// Use an existing digits file. Its contents are only checked as they
+    /// are read.
+    pub fn open<P: Into<PathBuf>>(path: P) -> Self {
+        Self{ path: path.into() }
+    }
+
+    /// Write `n` to a new digits file at `path`.
+    pub fn create<P: Into<PathBuf>>(path: P, n: &Natural) -> io::Result<Self> {
+        let path = path.into();
+        let digits: Vec<u8> = n.digits[..n.significant_len().max(1)].iter().map(|d| d.as_u8() + b'0').collect();
+        std::fs::write(&path, digits)?;
+        Ok(Self{ path })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn digit_count(&self) -> io::Result<u64> {
+        Ok(std::fs::metadata(&self.path)?.len())
+    }
+
+    /// Read the whole number into memory.
+    pub fn to_natural(&self) -> io::Result<Natural> {
+        let bytes = std::fs::read(&self.path)?;
+        let digits = bytes.iter()
+            .map(|b| b.wrapping_sub(b'0').try_into().map_err(|_| invalid(*b)))
+            .collect::<io::Result<Vec<Digit>>>()?;
+        Ok(Natural::from_digits(digits))
+    }
+
+    /// Stream `self + other` into a new file at `out`.
+    pub fn add(&self, other: &Self, out: &Path) -> io::Result<Self> {
+        let mut a = BufReader::new(File::open(&self.path)?);
+        let mut b = BufReader::new(File::open(&other.path)?);
+        let mut w = BufWriter::new(File::create(out)?);
+        let (mut x, mut y) = (vec![0; BLOCK], vec![0; BLOCK]);
+        let mut carry = 0;
+        loop {
+            let n = read_block(&mut a, &mut x)?;
+            let m = read_block(&mut b, &mut y)?;
+            if n == 0 && m == 0 {
+                break;
+            }
+            let block: Vec<u8> = (0..n.max(m))
+                .map(|p| {
+                    let s = if p < n { x[p] - b'0' } else { 0 } + if p < m { y[p] - b'0' } else { 0 } + carry;
+                    carry = s / 10;
+                    s % 10 + b'0'
+                })
+                .collect();
+            w.write_all(&block)?;
+        }
+        if carry > 0 {
+            w.write_all(&[carry + b'0'])?;
+        }
+        w.flush()?;
+        Ok(Self::open(out))
+    }
+
+    /// Multiply into a new file at `out`, one pair of blocks at a time.
+    pub fn mul(&self, other: &Self, out: &Path) -> io::Result<Self> {
+        self.mul_blocked(other, out, BLOCK)
+    }
+
+    fn mul_blocked(&self, other: &Self, out: &Path, block: usize) -> io::Result<Self> {
+        let len = self.digit_count()? + other.digit_count()?;
+        let mut result = OpenOptions::new().read(true).write(true).create(true).truncate(true).open(out)?;
+        let mut w = BufWriter::new(&mut result);
+        let zeros = vec![b'0'; block];
+        let mut left = len;
+        while left > 0 {
+            let n = left.min(block as u64) as usize;
+            w.write_all(&zeros[..n])?;
+            left -= n as u64;
+        }
+        w.flush()?;
+        drop(w);
+
+        let mut a = BufReader::new(File::open(&self.path)?);
+        let (mut x, mut y) = (vec![0; block], vec![0; block]);
+        let mut i = 0;
+        loop {
+            let n = read_block(&mut a, &mut x)?;
+            if n == 0 {
+                break;
+            }
+            let x_block = DiskNatural::block_natural(&x[..n]);
+            let mut b = BufReader::new(File::open(&other.path)?);
+            let mut j = 0;
+            loop {
+                let m = read_block(&mut b, &mut y)?;
+                if m == 0 {
+                    break;
+                }
+                let product = x_block.clone() * DiskNatural::block_natural(&y[..m]);
+                let digits: Vec<u8> = product.digits.iter().map(|d| d.as_u8() + b'0').collect();
+                add_at(&mut result, i + j, &digits)?;
+                j += m as u64;
+            }
+            i += n as u64;
+        }
+
+        // Drop leading zeros, which sit at the end of the file
+        let mut end = len;
+        let mut byte = [0];
+        while end > 1 {
+            result.seek(SeekFrom::Start(end - 1))?;
+            result.read_exact(&mut byte)?;
+            if byte[0] != b'0' {
+                break;
+            }
+            end -= 1;
+        }
+        result.set_len(end)?;
+        Ok(Self::open(out))
+    }
+
+    fn block_natural(digits: &[u8]) -> Natural {
+        Natural::from_digits(digits.iter().map(|b| (b - b'0').try_into().unwrap()).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn n(s: &str) -> Natural {
+        s.parse().unwrap()
+    }
+
+    fn scratch(test: &str) -> PathBuf {
+        let root = std::env::temp_dir().join(format!("bignum-outofcore-{}-{}", std::process::id(), test));
+        std::fs::create_dir_all(&root).unwrap();
+        root
+    }
+
+    #[test]
+    fn add() {
+        let root = scratch("add");
+        let a = DiskNatural::create(root.join("a"), &n(&"9".repeat(200_000))).unwrap();
+        let b = DiskNatural::create(root.join("b"), &n("1")).unwrap();
+        let sum = a.add(&b, &root.join("sum")).unwrap();
+        assert_eq!(sum.digit_count().unwrap(), 200_001);
+        assert_eq!(sum.to_natural().unwrap(), Natural::power_of_ten(200_000));
+        std::fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn mul() {
+        let x = n("98765432109876543210987654321");
+        let y = n("12345678901234567890");
+        let root = scratch("mul");
+        let a = DiskNatural::create(root.join("a"), &x).unwrap();
+        let b = DiskNatural::create(root.join("b"), &y).unwrap();
+        // Small blocks, so carries cross block boundaries
+        let product = a.mul_blocked(&b, &root.join("product"), 4).unwrap();
+        assert_eq!(product.to_natural().unwrap(), x * y);
+        let zero = DiskNatural::create(root.join("zero"), &Natural::zero()).unwrap();
+        let product = a.mul_blocked(&zero, &root.join("zero-product"), 4).unwrap();
+        assert_eq!(product.digit_count().unwrap(), 1);
+        std::fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn invalid_file() {
+        let root = scratch("invalid");
+        std::fs::write(root.join("bad"), b"12x").unwrap();
+        assert!(DiskNatural::open(root.join("bad")).to_natural().is_err());
+        std::fs::remove_dir_all(root).unwrap();
+    }
+}
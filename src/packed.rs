@@ -0,0 +1,101 @@
+//! Packed BCD Integers
+//!
+//! COBOL's `COMP-3` fields, which mainframe records are full of, store a
+//! decimal two digits to a byte, most significant first, with the last
+//! nibble the sign: 0xc positive, 0xd negative and 0xf unsigned. A field
+//! declared `PIC S9(n) COMP-3` takes `n / 2 + 1` bytes, so an even number
+//! of digits gets a leading zero nibble. Decoding also accepts the
+//! alternate signs 0xa and 0xe (positive) and 0xb (negative), as IBM
+//! hardware does. An implied decimal point (`V` in the picture) is not
+//! stored, so it is up to the caller to scale the result.
+
+use crate::digit::Digit;
+use crate::error::PackedBcdError;
+use crate::integer::{Integer, Sign};
+use crate::natural::Natural;
+
+const POSITIVE: u8 = 0xc;
+const NEGATIVE: u8 = 0xd;
+
+impl Integer {
+    /// The shortest packed BCD encoding, with sign 0xc or 0xd.
+    pub fn to_packed_bcd(&self) -> Vec<u8> {
+        let len = self.magnitude().significant_len().max(1);
+        self.to_packed_bcd_width(len / 2 + 1).unwrap()
+    }
+
+    /// The encoding in a field of `width` bytes, which holds up to
+    /// `2 * width - 1` digits. `None` if the value does not fit.
+    pub fn to_packed_bcd_width(&self, width: usize) -> Option<Vec<u8>> {
+        let digits = &self.magnitude().digits[..self.magnitude().significant_len()];
+        if width == 0 || digits.len() > 2 * width - 1 {
+            return None;
+        }
+        let sign = match self.sign() {
+            Sign::Negative => NEGATIVE,
+            _ => POSITIVE
+        };
+        // Nibbles from least significant: the sign, then the digits
+        let nibble = |k: usize| match k {
+            0 => sign,
+            _ => digits.get(k - 1).map_or(0, |d| d.as_u8())
+        };
+        Some((0..width).rev().map(|k| nibble(2 * k + 1) << 4 | nibble(2 * k)).collect())
+    }
+
+    /// Decode a packed BCD field of any width.
+    pub fn from_packed_bcd(bytes: &[u8]) -> Result<Integer, PackedBcdError> {
+        let last = *bytes.last().ok_or(PackedBcdError::Empty)?;
+        let sign = match last & 0xf {
+            0xb | NEGATIVE => Sign::Negative,
+            0xa | POSITIVE | 0xe | 0xf => Sign::Positive,
+            nibble => return Err(PackedBcdError::InvalidSign(nibble))
+        };
+        let mut digits = Vec::with_capacity(2 * bytes.len());
+        let nibbles = bytes.iter().flat_map(|b| [b >> 4, b & 0xf]);
+        for nibble in nibbles.rev().skip(1) {
+            digits.push(Digit::try_from(nibble).map_err(|_| PackedBcdError::InvalidDigit(nibble))?);
+        }
+        Ok(Integer::new(sign, Natural::from_digits(digits)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn i(s: &str) -> Integer {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn encodings() {
+        assert_eq!(i("12345").to_packed_bcd(), vec![0x12, 0x34, 0x5c]);
+        assert_eq!(i("-1234").to_packed_bcd(), vec![0x01, 0x23, 0x4d]);
+        assert_eq!(i("0").to_packed_bcd(), vec![0x0c]);
+        assert_eq!(i("-7").to_packed_bcd(), vec![0x7d]);
+        // PIC S9(7) COMP-3
+        assert_eq!(i("-42").to_packed_bcd_width(4), Some(vec![0x00, 0x00, 0x04, 0x2d]));
+        assert_eq!(i("1234").to_packed_bcd_width(2), None);
+        assert_eq!(i("1").to_packed_bcd_width(0), None);
+    }
+
+    #[test]
+    fn decodings() {
+        assert_eq!(Integer::from_packed_bcd(&[0x12, 0x34, 0x5c]), Ok(i("12345")));
+        assert_eq!(Integer::from_packed_bcd(&[0x00, 0x00, 0x04, 0x2d]), Ok(i("-42")));
+        assert_eq!(Integer::from_packed_bcd(&[0x99, 0x9f]), Ok(i("999")));
+        assert_eq!(Integer::from_packed_bcd(&[0x5b]), Ok(i("-5")));
+        assert_eq!(Integer::from_packed_bcd(&[0x0d]), Ok(i("0")));
+        assert_eq!(Integer::from_packed_bcd(&[]), Err(PackedBcdError::Empty));
+        assert_eq!(Integer::from_packed_bcd(&[0x1a, 0x2c]), Err(PackedBcdError::InvalidDigit(0xa)));
+        assert_eq!(Integer::from_packed_bcd(&[0x12]), Err(PackedBcdError::InvalidSign(0x2)));
+    }
+
+    #[test]
+    fn round_trip() {
+        for s in ["1", "-10", "99", "-100", "123456789012345678901234567890", "-98765432109876543210"] {
+            assert_eq!(Integer::from_packed_bcd(&i(s).to_packed_bcd()), Ok(i(s)));
+        }
+    }
+}
@@ -0,0 +1,218 @@
+//! p-adic Integers at Fixed Precision
+//!
+//! A `PAdic` is a p-adic integer known modulo p^k, stored as its k base-p
+//! digits. Arithmetic wraps modulo p^k, which is exactly p-adic arithmetic
+//! truncated to k digits; negation needs no sign, since -1 is ...(p-1)(p-1).
+
+use crate::natural::Natural;
+
+#[derive(Clone,Debug,PartialEq,Eq)]
+pub struct PAdic {
+    prime: u32,
+    // Base-p digits, least significant first, always `precision` of them
+    digits: Vec<u32>
+}
+
+impl PAdic {
+    /// The image of `value` in Z_p modulo p^precision.
+    ///
+    /// Panics if `prime` is below 2 or `precision` is zero. Any `prime` of
+    /// 2 or more gives a valid ring, but `inverse` and `valuation` only
+    /// mean what they say when it really is prime.
+    pub fn new(prime: u32, precision: usize, value: &Natural) -> Self {
+        assert!(prime >= 2, "p-adic base must be at least 2");
+        assert!(precision > 0, "p-adic precision must be at least one digit");
+        let mut digits = Vec::with_capacity(precision);
+        let mut rest = value.clone();
+        for _ in 0..precision {
+            let (quotient, digit) = rest.div_rem_small(prime);
+            digits.push(digit);
+            rest = quotient;
+        }
+        Self{ prime, digits }
+    }
+
+    pub fn prime(&self) -> u32 {
+        self.prime
+    }
+
+    pub fn precision(&self) -> usize {
+        self.digits.len()
+    }
+
+    pub fn digits(&self) -> &[u32] {
+        &self.digits
+    }
+
+    /// The number of factors of p, or `None` for zero, whose valuation is
+    /// infinite (at least the precision).
+    pub fn valuation(&self) -> Option<usize> {
+        self.digits.iter().position(|d| *d != 0)
+    }
+
+    pub fn is_unit(&self) -> bool {
+        self.valuation() == Some(0)
+    }
+
+    /// The representative in `0..p^precision`.
+    pub fn to_natural(&self) -> Natural {
+        self.digits.iter().rev().fold(Natural::zero(), |acc, d| acc.mul_small(self.prime).add_small(*d))
+    }
+
+    /// The multiplicative inverse, if this is a unit.
+    pub fn inverse(&self) -> Option<Self> {
+        let p = i64::from(self.prime);
+        let (mut r0, mut r1) = (p, i64::from(self.digits[0]));
+        let (mut t0, mut t1) = (0_i64, 1_i64);
+        while r1 != 0 {
+            let q = r0 / r1;
+            (r0, r1) = (r1, r0 - q * r1);
+            (t0, t1) = (t1, t0 - q * t1);
+        }
+        if r0 != 1 {
+            return None;
+        }
+
+        // Newton's iteration x <- x(2 - ax) doubles the correct digits each step
+        let start = Natural::zero().add_small(t0.rem_euclid(p) as u32);
+        let mut x = Self::new(self.prime, self.precision(), &start);
+        let two = Self::new(self.prime, self.precision(), &Natural::one().add_small(1));
+        let mut correct = 1;
+        while correct < self.precision() {
+            x = x.clone() * (two.clone() - self.clone() * x);
+            correct *= 2;
+        }
+        Some(x)
+    }
+
+    fn check(&self, other: &Self) {
+        assert!(
+            self.prime == other.prime && self.precision() == other.precision(),
+            "p-adic numbers with different primes or precisions"
+        );
+    }
+}
+
+impl std::ops::Add for PAdic {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self::Output {
+        self.check(&other);
+        let p = u64::from(self.prime);
+        let mut carry = 0;
+        let digits = self.digits.iter().zip(&other.digits)
+            .map(|(a, b)| {
+                let s = u64::from(*a) + u64::from(*b) + carry;
+                carry = s / p;
+                (s % p) as u32
+            })
+            .collect();
+        Self{ prime: self.prime, digits }
+    }
+}
+
+impl std::ops::Neg for PAdic {
+    type Output = Self;
+
+    // -x is the complement of every digit, plus one
+    fn neg(self) -> Self::Output {
+        let complement = Self{
+            prime: self.prime,
+            digits: self.digits.iter().map(|d| self.prime - 1 - d).collect()
+        };
+        let one = Self::new(self.prime, self.precision(), &Natural::one());
+        complement + one
+    }
+}
+
+impl std::ops::Sub for PAdic {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self::Output {
+        self + (-other)
+    }
+}
+
+impl std::ops::Mul for PAdic {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self::Output {
+        self.check(&other);
+        let p = u128::from(self.prime);
+        let k = self.precision();
+        let mut digits = Vec::with_capacity(k);
+        let mut carry: u128 = 0;
+        for i in 0..k {
+            let mut s = carry;
+            for j in 0..=i {
+                s += u128::from(self.digits[j]) * u128::from(other.digits[i - j]);
+            }
+            digits.push((s % p) as u32);
+            carry = s / p;
+        }
+        Self{ prime: self.prime, digits }
+    }
+}
+
+/// The digit expansion, most significant first: `...4444` is -1 in Z_5.
+/// Digits are separated by spaces when p is above 10.
+impl std::fmt::Display for PAdic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let separator = if self.prime > 10 { " " } else { "" };
+        let digits: Vec<String> = self.digits.iter().rev().map(|d| d.to_string()).collect();
+        write!(f, "...{}", digits.join(separator))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn n(s: &str) -> Natural {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn digits() {
+        let x = PAdic::new(5, 4, &n("38"));
+        assert_eq!(x.digits(), &[3, 2, 1, 0]);
+        assert_eq!(x.to_string(), "...0123");
+        assert_eq!(x.to_natural(), n("38"));
+    }
+
+    #[test]
+    fn negative_one() {
+        let minus_one = -PAdic::new(5, 6, &Natural::one());
+        assert_eq!(minus_one.to_string(), "...444444");
+        assert_eq!(minus_one.clone() + PAdic::new(5, 6, &Natural::one()), PAdic::new(5, 6, &Natural::zero()));
+    }
+
+    #[test]
+    fn arithmetic_mod_p_k() {
+        let a = PAdic::new(7, 5, &n("12345"));
+        let b = PAdic::new(7, 5, &n("678"));
+        // 7^5 = 16807
+        assert_eq!((a.clone() * b.clone()).to_natural(), n("12345") * n("678") % n("16807"));
+        assert_eq!((b - a).to_natural(), n("16807") - n("12345") + n("678"));
+    }
+
+    #[test]
+    fn valuation() {
+        assert_eq!(PAdic::new(5, 8, &n("50")).valuation(), Some(2));
+        assert_eq!(PAdic::new(5, 8, &Natural::zero()).valuation(), None);
+    }
+
+    #[test]
+    fn inverse() {
+        let three = PAdic::new(5, 10, &n("3"));
+        let third = three.inverse().unwrap();
+        assert_eq!((third * three).to_natural(), Natural::one());
+        assert_eq!(PAdic::new(5, 10, &n("10")).inverse(), None);
+    }
+
+    #[test]
+    fn large_prime() {
+        let x = PAdic::new(101, 3, &n("10203"));
+        assert_eq!(x.to_string(), "...1 0 2");
+    }
+}
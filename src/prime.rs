@@ -0,0 +1,102 @@
+//! Primality Testing
+//!
+//! Miller–Rabin with witnesses that make the answer exact below
+//! 3.3 × 10^24, and probable above it.
+
+use crate::natural::Natural;
+use crate::random::{RandomSource, SplitMix64};
+
+// Every composite below 3,317,044,064,679,887,385,961,981 fails one of
+// these witnesses (Sorenson and Webster, 2015)
+const WITNESSES: [u32; 13] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41];
+const DETERMINISTIC_BELOW: u128 = 3_317_044_064_679_887_385_961_981;
+
+impl Natural {
+    /// Whether this is prime, by Miller–Rabin.
+    ///
+    /// Below 3.3 × 10^24 the answer is exact. Above it, the fixed
+    /// witnesses are followed by `rounds` more chosen from this number's
+    /// fingerprint, and a composite passes each with probability at most
+    /// 1/4. The extra witnesses are deterministic, so the same number
+    /// always gets the same answer.
+    pub fn is_probable_prime(&self, rounds: usize) -> bool {
+        if *self <= Natural::one() {
+            return false;
+        }
+        for p in WITNESSES {
+            if *self == Natural::from(p) {
+                return true;
+            }
+            if self.div_rem_small(p).1 == 0 {
+                return false;
+            }
+        }
+
+        // self - 1 = d * 2^s with d odd
+        let below = self - Natural::one();
+        let mut d = below.clone();
+        let mut s = 0;
+        while d.div_rem_small(2).1 == 0 {
+            d = d.div_rem_small(2).0;
+            s += 1;
+        }
+        let passes = |a: &Natural| {
+            let mut x = a.mod_pow(&d, self);
+            if x == Natural::one() || x == below {
+                return true;
+            }
+            for _ in 1..s {
+                x = (x.clone() * x).div_rem(self).1;
+                if x == below {
+                    return true;
+                }
+            }
+            false
+        };
+
+        if !WITNESSES.iter().all(|p| passes(&Natural::from(*p))) {
+            return false;
+        }
+        if *self < Natural::from(DETERMINISTIC_BELOW) {
+            return true;
+        }
+        // Seeded by the fingerprint; every witness is far below self
+        let mut rng = SplitMix64::new(self.fingerprint());
+        (0..rounds).all(|_| passes(&Natural::from(rng.next_u64().max(2))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn n(s: &str) -> Natural {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn small() {
+        let primes: Vec<u32> = (0..100).filter(|k| n(&k.to_string()).is_probable_prime(0)).collect();
+        assert_eq!(primes, vec![2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71, 73, 79, 83, 89, 97]);
+    }
+
+    #[test]
+    fn pseudoprimes() {
+        // Carmichael numbers, and strong pseudoprimes to the first few bases
+        assert!(!n("561").is_probable_prime(0));
+        assert!(!n("3215031751").is_probable_prime(0));
+        assert!(!n("3825123056546413051").is_probable_prime(0));
+        assert!(!n("318665857834031151167461").is_probable_prime(0));
+        assert!(n("1000000007").is_probable_prime(0));
+    }
+
+    #[test]
+    fn large() {
+        // Mersenne primes 2^89 - 1 and 2^127 - 1, and a product of two
+        // primes beyond the deterministic range
+        assert!(n("618970019642690137449562111").is_probable_prime(2));
+        assert!(n("170141183460469231731687303715884105727").is_probable_prime(2));
+        let composite = n("618970019642690137449562111") * n("2305843009213693951");
+        assert!(!composite.is_probable_prime(2));
+    }
+}
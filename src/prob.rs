@@ -0,0 +1,121 @@
+//! Exact Probabilities
+//!
+//! Binomial and hypergeometric probabilities, and their tails, as exact
+//! `Rational`s, for audits and teaching where a floating point tail that
+//! is off in the last place is not good enough. Tails are sums of the
+//! individual probabilities, so these are for modest `n`.
+
+use crate::integer::Integer;
+use crate::natural::Natural;
+use crate::rational::Rational;
+
+/// The binomial coefficient `n` choose `k`, zero when `k > n`.
+pub fn choose(n: u64, k: u64) -> Natural {
+    if k > n {
+        return Natural::zero();
+    }
+    // Each partial product is itself a binomial coefficient, so every
+    // division is exact
+    let k = k.min(n - k);
+    (0..k).fold(Natural::one(), |c, i| &c * (n - i) / (i + 1))
+}
+
+// p^k for 0 <= p
+fn power(p: &Rational, k: u64) -> Rational {
+    let k = usize::try_from(k).unwrap();
+    Rational::new(Integer::from(p.numerator().magnitude().pow(k)), p.denominator().pow(k))
+}
+
+/// The probability of exactly `k` successes in `n` trials that each
+/// succeed with probability `p`.
+///
+/// Panics unless `p` is between zero and one.
+pub fn binomial_pmf(n: u64, k: u64, p: Rational) -> Rational {
+    assert!(p >= Rational::zero() && p <= Rational::one(), "probability outside 0..=1");
+    if k > n {
+        return Rational::zero();
+    }
+    let q = Rational::one() - p.clone();
+    Rational::from(choose(n, k)) * power(&p, k) * power(&q, n - k)
+}
+
+/// The probability of at most `k` successes.
+pub fn binomial_lower_tail(n: u64, k: u64, p: Rational) -> Rational {
+    (0..=k.min(n)).fold(Rational::zero(), |total, i| total + binomial_pmf(n, i, p.clone()))
+}
+
+/// The probability of at least `k` successes.
+pub fn binomial_upper_tail(n: u64, k: u64, p: Rational) -> Rational {
+    (k..=n).fold(Rational::zero(), |total, i| total + binomial_pmf(n, i, p.clone()))
+}
+
+/// The probability of exactly `k` successes in `draws` draws without
+/// replacement from a `population` that holds `successes` successes.
+///
+/// Panics if the `successes` or the `draws` are more than the
+/// `population`.
+pub fn hypergeometric_pmf(population: u64, successes: u64, draws: u64, k: u64) -> Rational {
+    assert!(successes <= population && draws <= population, "more successes or draws than the population");
+    if k > draws {
+        return Rational::zero();
+    }
+    let ways = choose(successes, k) * choose(population - successes, draws - k);
+    Rational::new(Integer::from(ways), choose(population, draws))
+}
+
+/// The probability of at most `k` successes.
+pub fn hypergeometric_lower_tail(population: u64, successes: u64, draws: u64, k: u64) -> Rational {
+    (0..=k.min(draws)).fold(Rational::zero(), |total, i| total + hypergeometric_pmf(population, successes, draws, i))
+}
+
+/// The probability of at least `k` successes.
+pub fn hypergeometric_upper_tail(population: u64, successes: u64, draws: u64, k: u64) -> Rational {
+    (k..=draws).fold(Rational::zero(), |total, i| total + hypergeometric_pmf(population, successes, draws, i))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn q(s: &str) -> Rational {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn coefficients() {
+        assert_eq!(choose(5, 2), Natural::from(10u32));
+        assert_eq!(choose(5, 0), Natural::one());
+        assert_eq!(choose(5, 6), Natural::zero());
+        assert_eq!(choose(100, 50), "100891344545564193334812497256".parse().unwrap());
+    }
+
+    #[test]
+    fn binomial() {
+        let half = q("1/2");
+        assert_eq!(binomial_pmf(4, 2, half.clone()), q("3/8"));
+        assert_eq!(binomial_pmf(3, 0, q("0.1")), q("0.729"));
+        assert_eq!(binomial_pmf(3, 4, half.clone()), Rational::zero());
+        assert_eq!(binomial_lower_tail(10, 10, q("1/3")), Rational::one());
+        // At least 8 heads in 10 fair tosses: (45 + 10 + 1) / 1024
+        assert_eq!(binomial_upper_tail(10, 8, half.clone()), q("56/1024"));
+        assert_eq!(binomial_lower_tail(10, 7, half.clone()) + binomial_upper_tail(10, 8, half.clone()), Rational::one());
+        assert_eq!(binomial_pmf(5, 5, Rational::one()), Rational::one());
+        assert_eq!(binomial_pmf(5, 0, Rational::zero()), Rational::one());
+    }
+
+    #[test]
+    fn hypergeometric() {
+        // Two aces in a five-card hand
+        assert_eq!(hypergeometric_pmf(52, 4, 5, 2), q("2162/54145"));
+        assert_eq!(hypergeometric_pmf(52, 4, 5, 5), Rational::zero());
+        assert_eq!(hypergeometric_lower_tail(52, 4, 5, 5), Rational::one());
+        // At least one defective in 3 from a lot of 10 with 2 defective
+        assert_eq!(hypergeometric_upper_tail(10, 2, 3, 1), q("8/15"));
+    }
+
+    #[test]
+    #[should_panic(expected = "probability outside 0..=1")]
+    fn bad_probability() {
+        binomial_pmf(1, 1, q("2"));
+    }
+}
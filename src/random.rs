@@ -0,0 +1,74 @@
+//! Random Sources
+//!
+//! Randomized operations, such as drawing primes and stochastic rounding,
+//! take their randomness from any `RandomSource`, so callers choose the
+//! generator and can replay a seed.
+
+use crate::natural::Natural;
+
+/// A source of uniformly random 64-bit words.
+///
+/// Implement this over a cryptographic generator to make keys; the
+/// `SplitMix64` here is only for demonstrations and tests.
+pub trait RandomSource {
+    fn next_u64(&mut self) -> u64;
+}
+
+/// SplitMix64, a small, fast and entirely predictable generator.
+///
+/// This is not a cryptographic generator.
+#[derive(Clone,Debug,PartialEq,Eq)]
+pub struct SplitMix64 {
+    state: u64
+}
+
+impl SplitMix64 {
+    pub fn new(seed: u64) -> Self {
+        Self{ state: seed }
+    }
+}
+
+impl RandomSource for SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+}
+
+/// A uniformly random value in `0..bound`, drawing as many bits as
+/// `bound` has and trying again when the draw is too large, which is less
+/// than half the time.
+///
+/// Panics if `bound` is zero.
+pub fn random_below(bound: &Natural, rng: &mut impl RandomSource) -> Natural {
+    assert!(*bound != Natural::zero(), "no values below zero");
+    let bits = bound.bit_len();
+    loop {
+        let mut bytes: Vec<u8> = (0..bits.div_ceil(8)).map(|_| rng.next_u64() as u8).collect();
+        let top = bytes.len() - 1;
+        bytes[top] &= (1u16 << ((bits - 1) % 8 + 1)).wrapping_sub(1) as u8;
+        let value = Natural::from_bytes_le(&bytes);
+        if value < *bound {
+            return value;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn below() {
+        let mut rng = SplitMix64::new(231);
+        let bound = Natural::from(1000u32);
+        let draws: Vec<Natural> = (0..2000).map(|_| random_below(&bound, &mut rng)).collect();
+        assert!(draws.iter().all(|x| *x < bound));
+        assert!(draws.iter().any(|x| *x >= Natural::from(900u32)));
+        assert!(draws.iter().any(|x| *x < Natural::from(100u32)));
+        assert_eq!(random_below(&Natural::one(), &mut rng), Natural::zero());
+    }
+}
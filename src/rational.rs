@@ -0,0 +1,437 @@
+//! Exact Fractions
+//!
+//! A `Rational` is always in lowest terms with a positive denominator, so
+//! equal values have equal representations and derived equality is value
+//! equality.
+
+use crate::error::{ParseNaturalError, ParseRationalError};
+use crate::integer::{Integer, Sign};
+use crate::natural::Natural;
+use crate::random::{random_below, RandomSource};
+use crate::rounding::RoundingMode;
+use std::cmp::Ordering;
+
+/// The ways `Rational::format` can write a value.
+#[derive(Clone,Copy,Debug,PartialEq,Eq)]
+pub enum Notation {
+    /// "22/7", the same as `Display`
+    Improper,
+    /// "3 1/7", or just "3" or "1/7" when either part is zero
+    Mixed,
+    /// "3.143", with exactly `places` digits after the point
+    Decimal{ places: usize, mode: RoundingMode }
+}
+
+#[derive(Clone,Debug,PartialEq,Eq)]
+pub struct Rational {
+    numerator: Integer,
+    denominator: Natural
+}
+
+impl Rational {
+    /// `numerator / denominator` in lowest terms.
+    ///
+    /// Panics if `denominator` is zero.
+    pub fn new(numerator: Integer, denominator: Natural) -> Self {
+        assert!(denominator != Natural::zero(), "rational with a zero denominator");
+        let g = numerator.magnitude().gcd(&denominator);
+        if g == Natural::one() {
+            return Self{ numerator, denominator };
+        }
+        let magnitude = numerator.magnitude().clone() / g.clone();
+        Self{
+            numerator: Integer::new(numerator.sign(), magnitude),
+            denominator: denominator / g
+        }
+    }
+
+    pub fn zero() -> Self {
+        Self{ numerator: Integer::zero(), denominator: Natural::one() }
+    }
+
+    pub fn one() -> Self {
+        Self{ numerator: Integer::one(), denominator: Natural::one() }
+    }
+
+    pub fn numerator(&self) -> &Integer {
+        &self.numerator
+    }
+
+    /// Always positive.
+    pub fn denominator(&self) -> &Natural {
+        &self.denominator
+    }
+
+    pub fn is_integer(&self) -> bool {
+        self.denominator == Natural::one()
+    }
+
+    /// The reciprocal.
+    ///
+    /// Panics if this is zero.
+    pub fn recip(&self) -> Self {
+        let sign = self.numerator.sign();
+        assert!(sign != Sign::Zero, "reciprocal of zero");
+        Self{
+            numerator: Integer::new(sign, self.denominator.clone()),
+            denominator: self.numerator.magnitude().clone()
+        }
+    }
+
+    /// Write this in the given notation, for reports and tables.
+    pub fn format(&self, notation: Notation) -> String {
+        let sign = if self.numerator.sign() == Sign::Negative { "-" } else { "" };
+        let magnitude = self.numerator.magnitude();
+        match notation {
+            Notation::Improper => self.to_string(),
+            Notation::Mixed => {
+                let (whole, part) = magnitude.div_rem(&self.denominator);
+                if part == Natural::zero() {
+                    self.numerator.to_string()
+                } else if whole == Natural::zero() {
+                    format!("{}{}/{}", sign, part, self.denominator)
+                } else {
+                    format!("{}{} {}/{}", sign, whole, part, self.denominator)
+                }
+            }
+            Notation::Decimal{ places, mode } => {
+                let scaled = magnitude.clone() * Natural::power_of_ten(places);
+                let (q, r) = scaled.div_rem(&self.denominator);
+                // Modes act on the magnitude, so `Down` is toward zero
+                let half = r.mul_small(2).cmp(&self.denominator);
+                let odd = q.coefficient(0).as_u8() % 2 == 1;
+                let up = match mode {
+                    RoundingMode::Down => false,
+                    RoundingMode::Up => r != Natural::zero(),
+                    RoundingMode::HalfUp => half != Ordering::Less,
+                    RoundingMode::HalfDown => half == Ordering::Greater,
+                    RoundingMode::HalfEven => half == Ordering::Greater || (half == Ordering::Equal && odd)
+                };
+                let q = if up { q + Natural::one() } else { q };
+                let sign = if q == Natural::zero() { "" } else { sign };
+                let digits = format!("{:0>width$}", q.to_string(), width = places + 1);
+                let (whole, fraction) = digits.split_at(digits.len() - places);
+                match places {
+                    0 => format!("{}{}", sign, whole),
+                    _ => format!("{}{}.{}", sign, whole, fraction)
+                }
+            }
+        }
+    }
+}
+
+// 2^exponent, for exponents from the smallest subnormal to the largest
+// normal power
+fn power_of_two(exponent: i64) -> f64 {
+    match exponent {
+        -1022.. => f64::from_bits(((exponent + 1023) as u64) << 52),
+        _ => f64::from_bits(1 << (exponent + 1074))
+    }
+}
+
+impl Rational {
+    // The magnitude as (q + r / d) 2^e with q below 2^53 and r below d,
+    // where q has all 53 bits unless 2^e is the smallest subnormal
+    fn to_f64_parts(&self) -> (u64, Natural, Natural, i64) {
+        let magnitude = self.numerator.magnitude();
+        let two = Natural::from(2u32);
+        let length = magnitude.bit_len() as i64 - self.denominator.bit_len() as i64;
+        let divide = |shift: i64| {
+            let shift = shift.min(1074);
+            let (n, d) = match shift {
+                0.. => (magnitude * two.pow(shift as usize), self.denominator.clone()),
+                _ => (magnitude.clone(), &self.denominator * two.pow(-shift as usize))
+            };
+            let (q, r) = n.div_rem(&d);
+            (u64::try_from(&q).unwrap(), r, d, -shift)
+        };
+        // The quotient has 53 bits or 54, and with one bit less it has 53
+        let parts = divide(53 - length);
+        match parts.0 >> 53 {
+            0 => parts,
+            _ => divide(52 - length)
+        }
+    }
+
+    fn compose(&self, q: u64, exponent: i64) -> f64 {
+        let magnitude = match exponent {
+            ..=1023 => q as f64 * power_of_two(exponent),
+            _ => f64::INFINITY
+        };
+        if self.numerator.is_negative() { -magnitude } else { magnitude }
+    }
+
+    /// The nearest `f64`, ties to even, as the `as` casts round. Values
+    /// too large are infinite, and values too small for the least
+    /// subnormal are zero of the same sign.
+    pub fn to_f64(&self) -> f64 {
+        let (q, r, d, exponent) = self.to_f64_parts();
+        let up = match r.mul_small(2).cmp(&d) {
+            Ordering::Less => false,
+            Ordering::Equal => q % 2 == 1,
+            Ordering::Greater => true
+        };
+        self.compose(q + u64::from(up), exponent)
+    }
+
+    /// One of the two `f64` on either side of this value, the one above in
+    /// magnitude with probability proportional to how close it is, so that
+    /// the expected result is exact. Sums of many stochastically rounded
+    /// values do not drift the way sums of rounded ones do.
+    pub fn to_f64_stochastic(&self, rng: &mut impl RandomSource) -> f64 {
+        let (q, r, d, exponent) = self.to_f64_parts();
+        let up = r != Natural::zero() && random_below(&d, rng) < r;
+        self.compose(q + u64::from(up), exponent)
+    }
+}
+
+impl From<Integer> for Rational {
+    fn from(n: Integer) -> Self {
+        Self{ numerator: n, denominator: Natural::one() }
+    }
+}
+
+impl From<Natural> for Rational {
+    fn from(n: Natural) -> Self {
+        Self::from(Integer::from(n))
+    }
+}
+
+// "a/b", "a.b" or "a", all unsigned, as a fraction
+fn parse_unsigned(s: &str) -> Result<Rational, ParseRationalError> {
+    let (numerator, denominator) = match (s.split_once('/'), s.split_once('.')) {
+        (Some((a, b)), _) => {
+            let a = a.parse().map_err(ParseRationalError::Invalid)?;
+            (a, b.parse().map_err(ParseRationalError::Invalid)?)
+        }
+        (None, Some((whole, fraction))) => {
+            let scale = Natural::power_of_ten(fraction.len());
+            let whole: Natural = whole.parse().map_err(ParseRationalError::Invalid)?;
+            let fraction: Natural = fraction.parse().map_err(ParseRationalError::Invalid)?;
+            (whole * scale.clone() + fraction, scale)
+        }
+        (None, None) => (s.parse().map_err(ParseRationalError::Invalid)?, Natural::one())
+    };
+    if denominator.significant_len() == 0 {
+        return Err(ParseRationalError::ZeroDenominator);
+    }
+    Ok(Rational::new(Integer::from(numerator), denominator))
+}
+
+impl std::str::FromStr for Rational {
+    type Err = ParseRationalError;
+
+    /// An integer ("3"), a fraction ("22/7"), a decimal ("0.125") or a
+    /// mixed number ("3 1/7"), with an optional leading `-` and trailing
+    /// `%`. Every form converts exactly.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (s, percent) = s.strip_suffix('%').map_or((s, false), |s| (s, true));
+        let (s, negative) = s.strip_prefix('-').map_or((s, false), |s| (s, true));
+        let value = match s.split_once(' ') {
+            Some((whole, fraction)) if fraction.contains('/') => {
+                let whole: Natural = whole.parse().map_err(ParseRationalError::Invalid)?;
+                Self::from(whole) + parse_unsigned(fraction)?
+            }
+            Some(_) => return Err(ParseRationalError::Invalid(ParseNaturalError::InvalidCharacter(' '))),
+            None => parse_unsigned(s)?
+        };
+        let value = if negative { -value } else { value };
+        match percent {
+            true => Ok(value / Self::from(Natural::power_of_ten(2))),
+            false => Ok(value)
+        }
+    }
+}
+
+impl std::fmt::Display for Rational {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.numerator, self.denominator)
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// a/b < c/d exactly when ad < cb, since both denominators are positive.
+impl Ord for Rational {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let left = self.numerator.clone() * Integer::from(other.denominator.clone());
+        let right = other.numerator.clone() * Integer::from(self.denominator.clone());
+        left.cmp(&right)
+    }
+}
+
+impl std::ops::Neg for Rational {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self{ numerator: -self.numerator, denominator: self.denominator }
+    }
+}
+
+impl std::ops::Add for Rational {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self::Output {
+        let numerator = self.numerator * Integer::from(other.denominator.clone())
+            + other.numerator * Integer::from(self.denominator.clone());
+        Self::new(numerator, self.denominator * other.denominator)
+    }
+}
+
+impl std::ops::Sub for Rational {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self::Output {
+        self + (-other)
+    }
+}
+
+impl std::ops::Mul for Rational {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self::Output {
+        Self::new(self.numerator * other.numerator, self.denominator * other.denominator)
+    }
+}
+
+/// Panics if `other` is zero.
+impl std::ops::Div for Rational {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, other: Self) -> Self::Output {
+        self * other.recip()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::random::SplitMix64;
+
+    fn q(s: &str) -> Rational {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn lowest_terms() {
+        assert_eq!(q("6/8").to_string(), "3/4");
+        assert_eq!(q("-10/5").to_string(), "-2/1");
+        assert_eq!(q("0/7"), Rational::zero());
+        assert_eq!(q("5"), q("10/2"));
+        let big = "1".to_string() + &"0".repeat(40);
+        assert_eq!(q(&format!("{}/{}", big, "4")).denominator(), &Natural::one());
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!("1/0".parse::<Rational>(), Err(ParseRationalError::ZeroDenominator));
+        assert_eq!("1/-2".parse::<Rational>(), Err(ParseRationalError::Invalid(ParseNaturalError::InvalidCharacter('-'))));
+        assert!("/2".parse::<Rational>().is_err());
+        assert!(".5".parse::<Rational>().is_err());
+        assert!("1.2.3".parse::<Rational>().is_err());
+        assert!("3 1".parse::<Rational>().is_err());
+        assert!("3 -1/7".parse::<Rational>().is_err());
+        assert_eq!("3 1/0".parse::<Rational>(), Err(ParseRationalError::ZeroDenominator));
+    }
+
+    #[test]
+    fn decimals_percents_and_mixed() {
+        assert_eq!(q("0.125"), q("1/8"));
+        assert_eq!(q("-2.50"), q("-5/2"));
+        assert_eq!(q("12.5%"), q("1/8"));
+        assert_eq!(q("-50%"), q("-1/2"));
+        assert_eq!(q("1/3%"), q("1/300"));
+        assert_eq!(q("3 1/7"), q("22/7"));
+        assert_eq!(q("-3 1/7"), q("-22/7"));
+        assert_eq!(q("0.000000000000000000001"), Rational::new(Integer::one(), Natural::power_of_ten(21)));
+    }
+
+    #[test]
+    fn arithmetic() {
+        assert_eq!(q("1/2") + q("1/3"), q("5/6"));
+        assert_eq!(q("1/2") - q("3/4"), q("-1/4"));
+        assert_eq!(q("-2/3") * q("9/4"), q("-3/2"));
+        assert_eq!(q("1/2") / q("-1/4"), q("-2"));
+        assert_eq!(q("7/3").recip(), q("3/7"));
+    }
+
+    #[test]
+    fn ordering() {
+        let mut values = vec![q("1/2"), q("-1/3"), q("2/5"), q("0"), q("-1/2")];
+        values.sort();
+        assert_eq!(values, vec![q("-1/2"), q("-1/3"), q("0"), q("2/5"), q("1/2")]);
+    }
+
+    #[test]
+    fn notations() {
+        assert_eq!(q("22/7").format(Notation::Improper), "22/7");
+        assert_eq!(q("22/7").format(Notation::Mixed), "3 1/7");
+        assert_eq!(q("-22/7").format(Notation::Mixed), "-3 1/7");
+        assert_eq!(q("-1/7").format(Notation::Mixed), "-1/7");
+        assert_eq!(q("6/2").format(Notation::Mixed), "3");
+        let decimal = |places, mode| Notation::Decimal{ places, mode };
+        assert_eq!(q("22/7").format(decimal(3, RoundingMode::HalfUp)), "3.143");
+        assert_eq!(q("22/7").format(decimal(0, RoundingMode::Down)), "3");
+        assert_eq!(q("1/8").format(decimal(5, RoundingMode::Down)), "0.12500");
+        assert_eq!(q("-1/40").format(decimal(1, RoundingMode::Up)), "-0.1");
+        assert_eq!(q("-1/40").format(decimal(1, RoundingMode::HalfUp)), "0.0");
+    }
+
+    #[test]
+    fn decimal_ties() {
+        let decimal = |mode| Notation::Decimal{ places: 2, mode };
+        assert_eq!(q("1/8").format(decimal(RoundingMode::HalfUp)), "0.13");
+        assert_eq!(q("1/8").format(decimal(RoundingMode::HalfDown)), "0.12");
+        assert_eq!(q("1/8").format(decimal(RoundingMode::HalfEven)), "0.12");
+        assert_eq!(q("-3/8").format(decimal(RoundingMode::HalfEven)), "-0.38");
+        assert_eq!(q("3/800").format(decimal(RoundingMode::Up)), "0.01");
+    }
+
+    fn power_of_two(exponent: usize) -> Natural {
+        Natural::from(2u32).pow(exponent)
+    }
+
+    #[test]
+    fn to_f64() {
+        assert_eq!(q("1/10").to_f64(), 0.1);
+        assert_eq!(q("1/3").to_f64(), 1.0 / 3.0);
+        assert_eq!(q("-5/2").to_f64(), -2.5);
+        assert_eq!(q("0").to_f64(), 0.0);
+        assert_eq!(q("123456789012345678901234567890").to_f64(), 123456789012345678901234567890.0);
+        // Ties go to even
+        let above = |k: u32| Rational::from(power_of_two(53) + Natural::from(k)).to_f64();
+        assert_eq!((above(1), above(3)), (9007199254740992.0, 9007199254740996.0));
+        // Subnormals, and past either end
+        let tiny = |n: u32, exponent| Rational::new(Integer::from(Natural::from(n)), power_of_two(exponent));
+        assert_eq!(tiny(1, 1074).to_f64(), f64::from_bits(1));
+        assert_eq!(tiny(3, 1075).to_f64(), f64::from_bits(2));
+        assert_eq!(tiny(1, 1076).to_f64(), 0.0);
+        assert!((-tiny(1, 1076)).to_f64().is_sign_negative());
+        assert_eq!(Rational::from(Natural::power_of_ten(400)).to_f64(), f64::INFINITY);
+        // Halfway from f64::MAX to 2^1024 rounds to the even side, which
+        // overflows
+        let halfway = power_of_two(1024) - power_of_two(970);
+        assert_eq!(Rational::from(halfway.clone() - Natural::one()).to_f64(), f64::MAX);
+        assert_eq!(Rational::from(halfway).to_f64(), f64::INFINITY);
+    }
+
+    #[test]
+    fn stochastic_to_f64() {
+        let mut rng = SplitMix64::new(231);
+        // A quarter of the way from 1 to the next f64 up
+        let x = Rational::new(Integer::from(power_of_two(54) + Natural::one()), power_of_two(54));
+        let next = 1.0 + f64::EPSILON;
+        let draws: Vec<f64> = (0..4000).map(|_| x.to_f64_stochastic(&mut rng)).collect();
+        assert!(draws.iter().all(|d| *d == 1.0 || *d == next));
+        let ups = draws.iter().filter(|d| **d == next).count();
+        assert!((900..1100).contains(&ups), "{} rounded up", ups);
+        assert!((0..100).all(|_| [-1.0, -next].contains(&(-x.clone()).to_f64_stochastic(&mut rng))));
+        // Exact values stay exact
+        assert!((0..100).all(|_| q("-5/4").to_f64_stochastic(&mut rng) == -1.25));
+    }
+}
@@ -0,0 +1,119 @@
+//! RLP Integers
+//!
+//! Ethereum's Recursive Length Prefix encoding stores an integer as the
+//! byte string of its minimal big-endian bytes, so zero is the empty
+//! string. A lone byte below 0x80 is its own encoding; other strings get
+//! a prefix of 0x80 plus their length, or, from 56 bytes, 0xb7 plus the
+//! length of a big-endian length. Decoding insists on the canonical form,
+//! as Ethereum clients do.
+
+use crate::error::RlpError;
+use crate::natural::Natural;
+
+const SHORT: u8 = 0x80;
+const LONG: u8 = 0xb7;
+const LIST: u8 = 0xc0;
+
+fn minimal_be(len: usize) -> Vec<u8> {
+    let bytes = len.to_be_bytes();
+    let zeros = bytes.iter().take_while(|b| **b == 0).count();
+    bytes[zeros..].to_vec()
+}
+
+impl Natural {
+    pub fn to_rlp(&self) -> Vec<u8> {
+        let mut body = self.to_bytes_be();
+        if body.len() == 1 && body[0] < SHORT {
+            return body;
+        }
+        let mut bytes = if body.len() < 56 {
+            vec![SHORT + body.len() as u8]
+        } else {
+            let len = minimal_be(body.len());
+            let mut prefix = vec![LONG + len.len() as u8];
+            prefix.extend(len);
+            prefix
+        };
+        bytes.append(&mut body);
+        bytes
+    }
+
+    pub fn from_rlp(bytes: &[u8]) -> Result<(Natural, usize), RlpError> {
+        let prefix = *bytes.first().ok_or(RlpError::Truncated)?;
+        let (start, len) = match prefix {
+            // Zero is the empty string, 0x80, never a zero byte
+            0 => return Err(RlpError::NonCanonical),
+            1..SHORT => return Ok((Natural::zero().add_small(u32::from(prefix)), 1)),
+            LIST.. => return Err(RlpError::List),
+            _ if prefix <= LONG => (1, usize::from(prefix - SHORT)),
+            _ => {
+                let width = usize::from(prefix - LONG);
+                let len_bytes = bytes.get(1..1 + width).ok_or(RlpError::Truncated)?;
+                if len_bytes[0] == 0 || width > std::mem::size_of::<usize>() {
+                    return Err(RlpError::NonCanonical);
+                }
+                let len = len_bytes.iter().fold(0_usize, |acc, b| (acc << 8) | usize::from(*b));
+                if len < 56 {
+                    return Err(RlpError::NonCanonical);
+                }
+                (1 + width, len)
+            }
+        };
+        let end = start.checked_add(len).ok_or(RlpError::Truncated)?;
+        let body = bytes.get(start..end).ok_or(RlpError::Truncated)?;
+        match body {
+            [0, ..] => Err(RlpError::NonCanonical),
+            [b] if *b < SHORT => Err(RlpError::NonCanonical),
+            _ => Ok((Natural::from_bytes_be(body), end))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn n(s: &str) -> Natural {
+        s.parse().unwrap()
+    }
+
+    // Examples from the Ethereum yellow paper and wiki
+    #[test]
+    fn known_encodings() {
+        assert_eq!(Natural::zero().to_rlp(), vec![0x80]);
+        assert_eq!(n("15").to_rlp(), vec![0x0f]);
+        assert_eq!(n("127").to_rlp(), vec![0x7f]);
+        assert_eq!(n("128").to_rlp(), vec![0x81, 0x80]);
+        assert_eq!(n("1024").to_rlp(), vec![0x82, 0x04, 0x00]);
+    }
+
+    #[test]
+    fn long_strings() {
+        // 2^448 needs 57 bytes
+        let x = Natural::from_bytes_be(&[&[1][..], &[0; 56]].concat());
+        let bytes = x.to_rlp();
+        assert_eq!(&bytes[..3], &[0xb8, 57, 1]);
+        assert_eq!(Natural::from_rlp(&bytes), Ok((x, 59)));
+    }
+
+    #[test]
+    fn round_trip() {
+        for s in ["0", "1", "127", "128", "255", "256", "65535", "123456789012345678901234567890"] {
+            let mut bytes = n(s).to_rlp();
+            let len = bytes.len();
+            bytes.push(0xc0);
+            assert_eq!(Natural::from_rlp(&bytes), Ok((n(s), len)));
+        }
+    }
+
+    #[test]
+    fn rejects() {
+        assert_eq!(Natural::from_rlp(&[]), Err(RlpError::Truncated));
+        assert_eq!(Natural::from_rlp(&[0x82, 1]), Err(RlpError::Truncated));
+        assert_eq!(Natural::from_rlp(&[0xc0]), Err(RlpError::List));
+        assert_eq!(Natural::from_rlp(&[0x81, 0x05]), Err(RlpError::NonCanonical));
+        assert_eq!(Natural::from_rlp(&[0x82, 0, 1]), Err(RlpError::NonCanonical));
+        assert_eq!(Natural::from_rlp(&[0x00]), Err(RlpError::NonCanonical));
+        assert_eq!(Natural::from_rlp(&[0xb8, 2, 1, 1]), Err(RlpError::NonCanonical));
+    }
+}
@@ -0,0 +1,192 @@
+//! Residue Number System (experimental)
+//!
+//! A value is held as its residues modulo a fixed set of pairwise coprime
+//! machine-word moduli. Addition, subtraction and multiplication work on
+//! each residue independently, with no carries between them; converting
+//! back to positional form uses Garner's mixed-radix form of the Chinese
+//! remainder theorem.
+
+use crate::error::RnsBasisError;
+use crate::natural::Natural;
+use std::sync::Arc;
+
+fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+// Inverse of a modulo m, for coprime a and m
+fn inverse(a: u64, m: u64) -> u64 {
+    let (mut r0, mut r1) = (m as i64, (a % m) as i64);
+    let (mut t0, mut t1) = (0_i64, 1_i64);
+    while r1 != 0 {
+        let q = r0 / r1;
+        (r0, r1) = (r1, r0 - q * r1);
+        (t0, t1) = (t1, t0 - q * t1);
+    }
+    t0.rem_euclid(m as i64) as u64
+}
+
+/// A validated set of moduli, shared by every number built from it.
+#[derive(Clone,Debug,PartialEq,Eq)]
+pub struct RnsBasis {
+    moduli: Arc<Vec<u32>>,
+    // garner[i][j] is the inverse of moduli[j] modulo moduli[i], for j < i
+    garner: Vec<Vec<u64>>
+}
+
+/// A value in residue form over some `RnsBasis`.
+#[derive(Clone,Debug,PartialEq,Eq)]
+pub struct RnsNumber {
+    moduli: Arc<Vec<u32>>,
+    residues: Vec<u32>
+}
+
+impl RnsBasis {
+    pub fn new(moduli: &[u32]) -> Result<Self, RnsBasisError> {
+        if moduli.is_empty() {
+            return Err(RnsBasisError::Empty);
+        }
+        for (i, a) in moduli.iter().enumerate() {
+            if *a < 2 {
+                return Err(RnsBasisError::TooSmall(*a));
+            }
+            for b in &moduli[..i] {
+                if gcd(u64::from(*a), u64::from(*b)) != 1 {
+                    return Err(RnsBasisError::NotCoprime(*b, *a));
+                }
+            }
+        }
+
+        let garner = moduli.iter().enumerate()
+            .map(|(i, mi)| {
+                moduli[..i].iter().map(|mj| inverse(u64::from(*mj), u64::from(*mi))).collect()
+            })
+            .collect();
+        Ok(Self{ moduli: Arc::new(moduli.to_vec()), garner })
+    }
+
+    pub fn moduli(&self) -> &[u32] {
+        &self.moduli
+    }
+
+    /// The product of the moduli; values are represented modulo this.
+    pub fn range(&self) -> Natural {
+        self.moduli.iter().fold(Natural::one(), |acc, m| acc.mul_small(*m))
+    }
+
+    /// Convert to residue form, reducing modulo `range()`.
+    pub fn encode(&self, n: &Natural) -> RnsNumber {
+        let residues = self.moduli.iter().map(|m| n.div_rem_small(*m).1).collect();
+        RnsNumber{ moduli: self.moduli.clone(), residues }
+    }
+
+    /// Convert back to positional form, in `0..range()`.
+    ///
+    /// Panics if `x` was built over a different basis.
+    pub fn decode(&self, x: &RnsNumber) -> Natural {
+        assert_eq!(self.moduli, x.moduli, "residue number from a different basis");
+        let mut mixed: Vec<u64> = vec![];
+        for (i, r) in x.residues.iter().enumerate() {
+            let m = u64::from(self.moduli[i]);
+            let mut v = u64::from(*r);
+            for (j, vj) in mixed.iter().enumerate() {
+                v = (v + m - vj % m) % m * self.garner[i][j] % m;
+            }
+            mixed.push(v);
+        }
+
+        let mut total = Natural::zero();
+        for (i, v) in mixed.iter().enumerate().rev() {
+            total = total.mul_small(self.moduli[i]).add_small(*v as u32);
+        }
+        total
+    }
+}
+
+impl RnsNumber {
+    pub fn residues(&self) -> &[u32] {
+        &self.residues
+    }
+
+    fn zip_with<F: Fn(u64, u64, u64) -> u64>(self, other: Self, f: F) -> Self {
+        assert_eq!(self.moduli, other.moduli, "residue numbers from different bases");
+        let residues = self.residues.iter().zip(&other.residues).zip(self.moduli.iter())
+            .map(|((a, b), m)| f(u64::from(*a), u64::from(*b), u64::from(*m)) as u32)
+            .collect();
+        Self{ moduli: self.moduli, residues }
+    }
+}
+
+impl std::ops::Add for RnsNumber {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self::Output {
+        self.zip_with(other, |a, b, m| (a + b) % m)
+    }
+}
+
+/// Subtraction wraps modulo the basis range.
+impl std::ops::Sub for RnsNumber {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self::Output {
+        self.zip_with(other, |a, b, m| (a + m - b) % m)
+    }
+}
+
+impl std::ops::Mul for RnsNumber {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self::Output {
+        self.zip_with(other, |a, b, m| a * b % m)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn n(s: &str) -> Natural {
+        s.parse().unwrap()
+    }
+
+    fn basis() -> RnsBasis {
+        RnsBasis::new(&[4294967291, 4294967279, 4294967231, 4294967197]).unwrap()
+    }
+
+    #[test]
+    fn round_trip() {
+        let b = basis();
+        let x = n("123456789012345678901234567890");
+        assert_eq!(b.decode(&b.encode(&x)), x);
+        assert_eq!(b.decode(&b.encode(&Natural::zero())), Natural::zero());
+    }
+
+    #[test]
+    fn arithmetic() {
+        let b = basis();
+        let x = n("98765432109876543210");
+        let y = n("12345678901234567");
+        assert_eq!(b.decode(&(b.encode(&x) + b.encode(&y))), x.clone() + y.clone());
+        assert_eq!(b.decode(&(b.encode(&x) - b.encode(&y))), x.clone() - y.clone());
+        assert_eq!(b.decode(&(b.encode(&x) * b.encode(&y))), x * y);
+    }
+
+    #[test]
+    fn wraps() {
+        let b = RnsBasis::new(&[3, 5, 7]).unwrap();
+        assert_eq!(b.range(), n("105"));
+        assert_eq!(b.decode(&b.encode(&n("106"))), n("1"));
+        assert_eq!(b.decode(&(b.encode(&n("2")) - b.encode(&n("3")))), n("104"));
+    }
+
+    #[test]
+    fn invalid() {
+        assert_eq!(RnsBasis::new(&[]), Err(RnsBasisError::Empty));
+        assert_eq!(RnsBasis::new(&[1, 3]), Err(RnsBasisError::TooSmall(1)));
+        assert_eq!(RnsBasis::new(&[6, 5, 9]), Err(RnsBasisError::NotCoprime(6, 9)));
+    }
+}
@@ -0,0 +1,117 @@
+//! Rounding to Significant Figures
+
+use crate::digit::Digit;
+use crate::natural::Natural;
+
+/// How to treat the digits dropped when rounding.
+#[derive(Clone,Copy,Debug,PartialEq,Eq)]
+pub enum RoundingMode {
+    /// Discard the dropped digits
+    Down,
+    /// Round up whenever any dropped digit is nonzero
+    Up,
+    /// Round to nearest, ties go up
+    HalfUp,
+    /// Round to nearest, ties go down
+    HalfDown,
+    /// Round to nearest, ties go to the even neighbour
+    HalfEven
+}
+
+impl Natural {
+    // Number of digits, which is none for zero
+    pub(crate) fn significant_len(&self) -> usize {
+        match self.digits.as_slice() {
+            [Digit::Zero] => 0,
+            digits => digits.len()
+        }
+    }
+
+    /// Keep the `figures` most significant digits, zeroing the rest.
+    ///
+    /// Returns the truncated value together with the dropped remainder, so
+    /// `truncated + remainder` is the original value.
+    pub fn truncate_to_digits(&self, figures: usize) -> (Natural, Natural) {
+        let len = self.significant_len();
+        if figures >= len {
+            return (Natural::from_digits(self.digits.clone()), Natural::zero());
+        }
+
+        let dropped = len - figures;
+        let mut digits = self.digits.clone();
+        let remainder = Natural::from_digits(digits[..dropped].to_vec());
+        for d in &mut digits[..dropped] {
+            *d = Digit::Zero;
+        }
+        (Natural::from_digits(digits), remainder)
+    }
+
+    /// Round to `figures` significant figures.
+    ///
+    /// Panics if `figures` is zero.
+    pub fn round_to_sig_figs(&self, figures: usize, mode: RoundingMode) -> Natural {
+        assert!(figures > 0, "cannot round to zero significant figures");
+        let len = self.significant_len();
+        let (truncated, remainder) = self.truncate_to_digits(figures);
+        if figures >= len || remainder == Natural::zero() {
+            return truncated;
+        }
+
+        let dropped = len - figures;
+        let first = self.digits[dropped - 1];
+        let rest = self.digits[..dropped - 1].iter().any(|d| *d != Digit::Zero);
+        let odd = self.digits[dropped].as_u8() % 2 == 1;
+        let round_up = match mode {
+            RoundingMode::Down => false,
+            RoundingMode::Up => true,
+            RoundingMode::HalfUp => first >= Digit::Five,
+            RoundingMode::HalfDown => first > Digit::Five || (first == Digit::Five && rest),
+            RoundingMode::HalfEven => first > Digit::Five || (first == Digit::Five && (rest || odd))
+        };
+
+        if round_up {
+            truncated + Natural::power_of_ten(dropped)
+        } else {
+            truncated
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn n(s: &str) -> Natural {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn truncate() {
+        let (t, r) = n("123456").truncate_to_digits(3);
+        assert_eq!(t, n("123000"));
+        assert_eq!(r, n("456"));
+    }
+
+    #[test]
+    fn truncate_short() {
+        let (t, r) = n("12").truncate_to_digits(3);
+        assert_eq!(t, n("12"));
+        assert_eq!(r, n("0"));
+    }
+
+    #[test]
+    fn round_modes() {
+        let x = n("12350");
+        assert_eq!(x.round_to_sig_figs(3, RoundingMode::Down), n("12300"));
+        assert_eq!(x.round_to_sig_figs(3, RoundingMode::Up), n("12400"));
+        assert_eq!(x.round_to_sig_figs(3, RoundingMode::HalfUp), n("12400"));
+        assert_eq!(x.round_to_sig_figs(3, RoundingMode::HalfDown), n("12300"));
+        assert_eq!(x.round_to_sig_figs(3, RoundingMode::HalfEven), n("12400"));
+        assert_eq!(n("12250").round_to_sig_figs(3, RoundingMode::HalfEven), n("12200"));
+    }
+
+    #[test]
+    fn round_carries() {
+        assert_eq!(n("99960").round_to_sig_figs(3, RoundingMode::HalfUp), n("100000"));
+    }
+}
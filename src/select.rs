@@ -0,0 +1,68 @@
+//! Selection Without Cloning
+//!
+//! These partition a slice in place, comparing through references and
+//! moving values only by swapping, so selecting from millions of large
+//! numbers never copies a digit. Each runs in expected linear time.
+
+use crate::natural::Natural;
+
+/// Reorder `values` so that the value at `n` is where it would be if the
+/// slice were sorted ascending, with nothing larger before it and nothing
+/// smaller after, and return it.
+///
+/// Panics if `n` is out of bounds.
+pub fn select_nth_unstable_natural(values: &mut [Natural], n: usize) -> &Natural {
+    values.select_nth_unstable(n).1
+}
+
+/// The `n`th largest value, counting from zero, so `nth_largest(v, 0)` is
+/// the maximum. Reorders `values` as `select_nth_unstable_natural` does,
+/// but descending.
+///
+/// Panics if `n` is out of bounds.
+pub fn nth_largest(values: &mut [Natural], n: usize) -> &Natural {
+    values.select_nth_unstable_by(n, |a, b| b.cmp(a)).1
+}
+
+/// Move the `n` largest values to the front, sorted largest first, and
+/// return them. All of `values` is returned when it has fewer than `n`.
+pub fn top_n(values: &mut [Natural], n: usize) -> &mut [Natural] {
+    let n = n.min(values.len());
+    if n > 0 && n < values.len() {
+        nth_largest(values, n - 1);
+    }
+    let top = &mut values[..n];
+    top.sort_unstable_by(|a, b| b.cmp(a));
+    top
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn v(xs: &[u32]) -> Vec<Natural> {
+        xs.iter().map(|x| x.to_string().parse().unwrap()).collect()
+    }
+
+    #[test]
+    fn nth() {
+        let mut values = v(&[50, 10, 40, 20, 30]);
+        assert_eq!(select_nth_unstable_natural(&mut values, 1), &v(&[20])[0]);
+        assert_eq!(nth_largest(&mut values, 0), &v(&[50])[0]);
+        assert_eq!(nth_largest(&mut values, 3), &v(&[20])[0]);
+    }
+
+    #[test]
+    fn top() {
+        let mut values: Vec<Natural> = v(&(0..1000).map(|i| (i * 7919) % 1000).collect::<Vec<_>>());
+        assert_eq!(top_n(&mut values, 3), &v(&[999, 998, 997])[..]);
+        assert_eq!(top_n(&mut v(&[2, 1]), 5), &v(&[2, 1])[..]);
+        assert!(top_n(&mut values, 0).is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn out_of_bounds() {
+        nth_largest(&mut v(&[1]), 1);
+    }
+}
@@ -0,0 +1,321 @@
+//! Serde Support
+//!
+//! The representations, which are version 1 of the schema and will only
+//! change with a new major version of the crate:
+//!
+//! - `Natural`: a string of decimal digits, such as `"1024"`
+//! - `Integer`: the same, with a leading `-` when negative
+//! - `Rational`: a string `"p/q"` in lowest terms, such as `"-3/4"`, or
+//!   with `#[serde(with = "bignum::serialize::rational_fields")]` a struct
+//!   of two such strings, `{"numerator": "-3", "denominator": "4"}`
+//! - `WorkUnit` and `WorkResult`: their one-line text form, such as
+//!   `"divisors 1000001 2 1000"`
+//!
+//! Strings keep every digit through formats such as JSON whose numbers
+//! are floating point. Deserializing a `Rational` accepts every form its
+//! `FromStr` does, and reduces a struct to lowest terms.
+
+use crate::integer::Integer;
+use crate::natural::Natural;
+use ::serde::de::{self, Deserialize, Deserializer, Visitor};
+use ::serde::ser::{Serialize, Serializer};
+use std::marker::PhantomData;
+
+// Deserialize any `FromStr` type from a string
+struct Parse<T>(PhantomData<T>, &'static str);
+
+impl<T> Visitor<'_> for Parse<T>
+where
+    T: std::str::FromStr,
+    T::Err: std::fmt::Display
+{
+    type Value = T;
+
+    fn expecting(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.1)
+    }
+
+    fn visit_str<E: de::Error>(self, s: &str) -> Result<T, E> {
+        s.parse().map_err(E::custom)
+    }
+}
+
+impl Serialize for Natural {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Natural {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_str(Parse(PhantomData, "a string of decimal digits"))
+    }
+}
+
+impl Serialize for Integer {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Integer {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_str(Parse(PhantomData, "a string of decimal digits with an optional sign"))
+    }
+}
+
+#[cfg(feature = "rational")]
+impl Serialize for crate::rational::Rational {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "rational")]
+impl<'de> Deserialize<'de> for crate::rational::Rational {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_str(Parse(PhantomData, "a fraction such as \"3/4\""))
+    }
+}
+
+#[cfg(feature = "shard")]
+impl Serialize for crate::shard::WorkUnit {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "shard")]
+impl<'de> Deserialize<'de> for crate::shard::WorkUnit {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_str(Parse(PhantomData, "a work unit such as \"product 1 1001\""))
+    }
+}
+
+#[cfg(feature = "shard")]
+impl Serialize for crate::shard::WorkResult {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "shard")]
+impl<'de> Deserialize<'de> for crate::shard::WorkResult {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_str(Parse(PhantomData, "a work result such as \"divisors 7 11 13\""))
+    }
+}
+
+/// A `Rational` as a struct of its numerator and denominator, for use
+/// with `#[serde(with = "bignum::serialize::rational_fields")]`.
+#[cfg(feature = "rational")]
+pub mod rational_fields {
+    use super::*;
+    use crate::rational::Rational;
+    use ::serde::de::{MapAccess, SeqAccess};
+    use ::serde::ser::SerializeStruct;
+
+    const FIELDS: &[&str] = &["numerator", "denominator"];
+
+    pub fn serialize<S: Serializer>(value: &Rational, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut s = serializer.serialize_struct("Rational", 2)?;
+        s.serialize_field("numerator", value.numerator())?;
+        s.serialize_field("denominator", value.denominator())?;
+        s.end()
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Rational, D::Error> {
+        deserializer.deserialize_struct("Rational", FIELDS, Fields)
+    }
+
+    struct Fields;
+
+    fn build<E: de::Error>(numerator: Integer, denominator: Natural) -> Result<Rational, E> {
+        if denominator == Natural::zero() {
+            return Err(E::custom("fraction has a zero denominator"));
+        }
+        Ok(Rational::new(numerator, denominator))
+    }
+
+    impl<'de> Visitor<'de> for Fields {
+        type Value = Rational;
+
+        fn expecting(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.write_str("a numerator and a denominator")
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Rational, A::Error> {
+            let numerator = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(0, &self))?;
+            let denominator = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(1, &self))?;
+            build(numerator, denominator)
+        }
+
+        fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Rational, A::Error> {
+            let (mut numerator, mut denominator) = (None, None);
+            while let Some(key) = map.next_key::<String>()? {
+                match key.as_str() {
+                    "numerator" if numerator.is_some() => return Err(de::Error::duplicate_field("numerator")),
+                    "denominator" if denominator.is_some() => return Err(de::Error::duplicate_field("denominator")),
+                    "numerator" => numerator = Some(map.next_value()?),
+                    "denominator" => denominator = Some(map.next_value()?),
+                    other => return Err(de::Error::unknown_field(other, FIELDS))
+                }
+            }
+            build(
+                numerator.ok_or_else(|| de::Error::missing_field("numerator"))?,
+                denominator.ok_or_else(|| de::Error::missing_field("denominator"))?
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::serde::de::value::{Error, StrDeserializer};
+    use ::serde::ser::{Impossible, SerializeStruct};
+
+    // Just enough of a serializer to see the output: strings as they are,
+    // structs as "{name: value, ...}"
+    struct Text;
+
+    struct Fields(Vec<String>);
+
+    macro_rules! unsupported {
+        ($($method:ident($($t:ty),*)),*) => {$(
+            fn $method(self, $(_: $t),*) -> Result<String, Error> {
+                Err(::serde::ser::Error::custom("unsupported"))
+            }
+        )*};
+    }
+
+    impl Serializer for Text {
+        type Ok = String;
+        type Error = Error;
+        type SerializeSeq = Impossible<String, Error>;
+        type SerializeTuple = Impossible<String, Error>;
+        type SerializeTupleStruct = Impossible<String, Error>;
+        type SerializeTupleVariant = Impossible<String, Error>;
+        type SerializeMap = Impossible<String, Error>;
+        type SerializeStruct = Fields;
+        type SerializeStructVariant = Impossible<String, Error>;
+
+        fn serialize_str(self, v: &str) -> Result<String, Error> {
+            Ok(v.to_string())
+        }
+
+        fn serialize_struct(self, _: &'static str, _: usize) -> Result<Fields, Error> {
+            Ok(Fields(vec![]))
+        }
+
+        unsupported!(
+            serialize_bool(bool), serialize_i8(i8), serialize_i16(i16), serialize_i32(i32),
+            serialize_i64(i64), serialize_u8(u8), serialize_u16(u16), serialize_u32(u32),
+            serialize_u64(u64), serialize_f32(f32), serialize_f64(f64), serialize_char(char),
+            serialize_bytes(&[u8]), serialize_none(), serialize_unit(),
+            serialize_unit_struct(&'static str), serialize_unit_variant(&'static str, u32, &'static str)
+        );
+
+        fn serialize_some<T: Serialize + ?Sized>(self, _: &T) -> Result<String, Error> {
+            Err(::serde::ser::Error::custom("unsupported"))
+        }
+
+        fn serialize_newtype_struct<T: Serialize + ?Sized>(self, _: &'static str, _: &T) -> Result<String, Error> {
+            Err(::serde::ser::Error::custom("unsupported"))
+        }
+
+        fn serialize_newtype_variant<T: Serialize + ?Sized>(self, _: &'static str, _: u32, _: &'static str, _: &T) -> Result<String, Error> {
+            Err(::serde::ser::Error::custom("unsupported"))
+        }
+
+        fn serialize_seq(self, _: Option<usize>) -> Result<Self::SerializeSeq, Error> {
+            Err(::serde::ser::Error::custom("unsupported"))
+        }
+
+        fn serialize_tuple(self, _: usize) -> Result<Self::SerializeTuple, Error> {
+            Err(::serde::ser::Error::custom("unsupported"))
+        }
+
+        fn serialize_tuple_struct(self, _: &'static str, _: usize) -> Result<Self::SerializeTupleStruct, Error> {
+            Err(::serde::ser::Error::custom("unsupported"))
+        }
+
+        fn serialize_tuple_variant(self, _: &'static str, _: u32, _: &'static str, _: usize) -> Result<Self::SerializeTupleVariant, Error> {
+            Err(::serde::ser::Error::custom("unsupported"))
+        }
+
+        fn serialize_map(self, _: Option<usize>) -> Result<Self::SerializeMap, Error> {
+            Err(::serde::ser::Error::custom("unsupported"))
+        }
+
+        fn serialize_struct_variant(self, _: &'static str, _: u32, _: &'static str, _: usize) -> Result<Self::SerializeStructVariant, Error> {
+            Err(::serde::ser::Error::custom("unsupported"))
+        }
+    }
+
+    impl SerializeStruct for Fields {
+        type Ok = String;
+        type Error = Error;
+
+        fn serialize_field<T: Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> Result<(), Error> {
+            self.0.push(format!("{}: {}", key, value.serialize(Text)?));
+            Ok(())
+        }
+
+        fn end(self) -> Result<String, Error> {
+            Ok(format!("{{{}}}", self.0.join(", ")))
+        }
+    }
+
+    fn from_str<'de, T: Deserialize<'de>>(s: &'de str) -> Result<T, Error> {
+        T::deserialize(StrDeserializer::<Error>::new(s))
+    }
+
+    // Golden values for version 1 of the schema
+    #[test]
+    fn natural_and_integer_v1() {
+        let n: Natural = "120".parse().unwrap();
+        assert_eq!(n.serialize(Text).unwrap(), "120");
+        assert_eq!("00120".parse::<Natural>().unwrap().serialize(Text).unwrap(), "120");
+        assert_eq!(from_str::<Natural>("0120").unwrap(), n);
+        assert!(from_str::<Natural>("-1").is_err());
+        let i: Integer = "-120".parse().unwrap();
+        assert_eq!(i.serialize(Text).unwrap(), "-120");
+        assert_eq!(from_str::<Integer>("-120").unwrap(), i);
+    }
+
+    #[cfg(feature = "rational")]
+    #[test]
+    fn rational_v1() {
+        use crate::rational::Rational;
+        use ::serde::de::value::MapDeserializer;
+        let q: Rational = "-6/8".parse().unwrap();
+        assert_eq!(q.serialize(Text).unwrap(), "-3/4");
+        assert_eq!(from_str::<Rational>("-3/4").unwrap(), q);
+        assert_eq!(from_str::<Rational>("-0.75").unwrap(), q);
+        assert!(from_str::<Rational>("1/0").is_err());
+
+        assert_eq!(rational_fields::serialize(&q, Text).unwrap(), "{numerator: -3, denominator: 4}");
+        let fields = |pairs: Vec<(&'static str, &'static str)>| {
+            rational_fields::deserialize(MapDeserializer::<_, Error>::new(pairs.into_iter()))
+        };
+        assert_eq!(fields(vec![("numerator", "-6"), ("denominator", "8")]).unwrap(), q);
+        assert!(fields(vec![("numerator", "1"), ("denominator", "0")]).is_err());
+        assert!(fields(vec![("numerator", "1")]).is_err());
+        assert!(fields(vec![("numerator", "1"), ("numerator", "2"), ("denominator", "3")]).is_err());
+        assert!(fields(vec![("numerator", "1"), ("denominator", "2"), ("extra", "3")]).is_err());
+    }
+
+    #[cfg(feature = "shard")]
+    #[test]
+    fn shard_v1() {
+        use crate::shard::{WorkResult, WorkUnit};
+        let unit = WorkUnit::Divisors{ n: "1000001".parse().unwrap(), start: 2, end: 1000 };
+        assert_eq!(unit.serialize(Text).unwrap(), "divisors 1000001 2 1000");
+        assert_eq!(from_str::<WorkUnit>("divisors 1000001 2 1000").unwrap(), unit);
+        assert!(from_str::<WorkUnit>("product 1").is_err());
+        let result = unit.execute();
+        assert_eq!(result.serialize(Text).unwrap(), "divisors 101");
+        assert_eq!(from_str::<WorkResult>("divisors 101").unwrap(), result);
+    }
+}
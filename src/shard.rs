@@ -0,0 +1,227 @@
+//! Sharded Computation
+//!
+//! A big product, sum or divisor search is split into `WorkUnit`s that
+//! can be executed anywhere, in any order, and their `WorkResult`s merged
+//! back into the answer. Splitting is deterministic, so every machine
+//! agrees on the shards, and both units and results have a one-line text
+//! form (their `Display` and `FromStr`) for sending between machines:
+//!
+//! ```text
+//! product 1 1001
+//! sum 12 345 6789
+//! divisors 1000001 2 1000
+//! ```
+//!
+//! The same form makes a long computation resumable: the units not yet
+//! executed and the results so far are its whole state, and with the
+//! `serde` feature both serialize as these strings, so a checkpoint can be
+//! saved and the work picked up again after a restart.
+
+use crate::error::ShardError;
+use crate::natural::Natural;
+
+#[derive(Clone,Debug,PartialEq,Eq)]
+pub enum WorkUnit {
+    /// The product of `start..end`
+    Product{ start: u32, end: u32 },
+    Sum{ terms: Vec<Natural> },
+    /// The divisors of `n` in `start..end`
+    Divisors{ n: Natural, start: u32, end: u32 }
+}
+
+#[derive(Clone,Debug,PartialEq,Eq)]
+pub enum WorkResult {
+    Product(Natural),
+    Sum(Natural),
+    /// In increasing order
+    Divisors(Vec<u32>)
+}
+
+// Split start..end into `shards` contiguous pieces, none of them empty
+fn split_range(start: u32, end: u32, shards: usize) -> Vec<(u32, u32)> {
+    let len = u64::from(end.saturating_sub(start));
+    let shards = (shards.max(1) as u64).min(len.max(1));
+    (0..shards)
+        .map(|i| (start + (len * i / shards) as u32, start + (len * (i + 1) / shards) as u32))
+        .filter(|(a, b)| a < b || len == 0)
+        .collect()
+}
+
+// Balanced product tree, so the big multiplications are between equals
+fn product(start: u32, end: u32) -> Natural {
+    if end - start <= 16 {
+        return (start..end).fold(Natural::one(), |acc, k| acc.mul_small(k));
+    }
+    let mid = start + (end - start) / 2;
+    product(start, mid) * product(mid, end)
+}
+
+impl WorkUnit {
+    /// Shards of the product of `start..end`, such as `1..n + 1` for n!.
+    pub fn split_product(start: u32, end: u32, shards: usize) -> Vec<WorkUnit> {
+        split_range(start, end, shards).into_iter()
+            .map(|(start, end)| WorkUnit::Product{ start, end })
+            .collect()
+    }
+
+    pub fn split_sum(terms: &[Natural], shards: usize) -> Vec<WorkUnit> {
+        split_range(0, terms.len() as u32, shards).into_iter()
+            .map(|(a, b)| WorkUnit::Sum{ terms: terms[a as usize..b as usize].to_vec() })
+            .collect()
+    }
+
+    /// Shards of a trial-division search for the divisors of `n` in
+    /// `start..end`.
+    pub fn split_divisors(n: &Natural, start: u32, end: u32, shards: usize) -> Vec<WorkUnit> {
+        split_range(start.max(1), end, shards).into_iter()
+            .map(|(start, end)| WorkUnit::Divisors{ n: n.clone(), start, end })
+            .collect()
+    }
+
+    pub fn execute(&self) -> WorkResult {
+        match self {
+            WorkUnit::Product{ start, end } => WorkResult::Product(product(*start, (*end).max(*start))),
+            WorkUnit::Sum{ terms } => WorkResult::Sum(terms.iter().sum()),
+            WorkUnit::Divisors{ n, start, end } => WorkResult::Divisors(
+                (*start..*end).filter(|d| *d > 0 && n.div_rem_small(*d).1 == 0).collect()
+            )
+        }
+    }
+
+    /// Combine the results of all the shards of one computation, in any
+    /// order.
+    pub fn merge(results: Vec<WorkResult>) -> Result<WorkResult, ShardError> {
+        let mut results = results.into_iter();
+        let first = results.next().ok_or(ShardError::Empty)?;
+        results.try_fold(first, |acc, r| match (acc, r) {
+            (WorkResult::Product(a), WorkResult::Product(b)) => Ok(WorkResult::Product(a * b)),
+            (WorkResult::Sum(a), WorkResult::Sum(b)) => Ok(WorkResult::Sum(a + b)),
+            (WorkResult::Divisors(mut a), WorkResult::Divisors(b)) => {
+                a.extend(b);
+                a.sort_unstable();
+                Ok(WorkResult::Divisors(a))
+            }
+            _ => Err(ShardError::Mismatched)
+        })
+    }
+}
+
+fn join<T: ToString>(items: &[T]) -> String {
+    items.iter().map(|x| x.to_string()).collect::<Vec<_>>().join(" ")
+}
+
+fn parse_all<T: std::str::FromStr>(words: &[&str]) -> Result<Vec<T>, ShardError> {
+    words.iter().map(|w| w.parse().map_err(|_| ShardError::InvalidFormat)).collect()
+}
+
+impl std::fmt::Display for WorkUnit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WorkUnit::Product{ start, end } => write!(f, "product {} {}", start, end),
+            WorkUnit::Sum{ terms } => write!(f, "sum {}", join(terms)),
+            WorkUnit::Divisors{ n, start, end } => write!(f, "divisors {} {} {}", n, start, end)
+        }
+    }
+}
+
+impl std::str::FromStr for WorkUnit {
+    type Err = ShardError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let words: Vec<&str> = s.split_whitespace().collect();
+        match words.as_slice() {
+            ["product", start, end] => {
+                let range: Vec<u32> = parse_all(&[start, end])?;
+                Ok(WorkUnit::Product{ start: range[0], end: range[1] })
+            }
+            ["sum", terms @ ..] => Ok(WorkUnit::Sum{ terms: parse_all(terms)? }),
+            ["divisors", n, start, end] => {
+                let range: Vec<u32> = parse_all(&[start, end])?;
+                let n = n.parse().map_err(|_| ShardError::InvalidFormat)?;
+                Ok(WorkUnit::Divisors{ n, start: range[0], end: range[1] })
+            }
+            _ => Err(ShardError::InvalidFormat)
+        }
+    }
+}
+
+impl std::fmt::Display for WorkResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WorkResult::Product(n) => write!(f, "product {}", n),
+            WorkResult::Sum(n) => write!(f, "sum {}", n),
+            WorkResult::Divisors(ds) => write!(f, "divisors {}", join(ds))
+        }
+    }
+}
+
+impl std::str::FromStr for WorkResult {
+    type Err = ShardError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let words: Vec<&str> = s.split_whitespace().collect();
+        let natural = |w: &str| w.parse().map_err(|_| ShardError::InvalidFormat);
+        match words.as_slice() {
+            ["product", n] => Ok(WorkResult::Product(natural(n)?)),
+            ["sum", n] => Ok(WorkResult::Sum(natural(n)?)),
+            ["divisors", ds @ ..] => Ok(WorkResult::Divisors(parse_all(ds)?)),
+            _ => Err(ShardError::InvalidFormat)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn n(s: &str) -> Natural {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn factorial() {
+        let units = WorkUnit::split_product(1, 31, 4);
+        assert_eq!(units.len(), 4);
+        let results = units.iter().rev().map(|u| u.execute()).collect();
+        assert_eq!(WorkUnit::merge(results), Ok(WorkResult::Product(n("265252859812191058636308480000000"))));
+    }
+
+    #[test]
+    fn sum_and_divisors() {
+        let terms: Vec<Natural> = (1..=100).map(|i: u32| n(&i.to_string())).collect();
+        let results = WorkUnit::split_sum(&terms, 7).iter().map(|u| u.execute()).collect();
+        assert_eq!(WorkUnit::merge(results), Ok(WorkResult::Sum(n("5050"))));
+
+        let results = WorkUnit::split_divisors(&n("360"), 1, 20, 3).iter().map(|u| u.execute()).collect();
+        assert_eq!(WorkUnit::merge(results), Ok(WorkResult::Divisors(vec![1, 2, 3, 4, 5, 6, 8, 9, 10, 12, 15, 18])));
+    }
+
+    #[test]
+    fn more_shards_than_work() {
+        assert_eq!(WorkUnit::split_product(5, 7, 10), vec![
+            WorkUnit::Product{ start: 5, end: 6 },
+            WorkUnit::Product{ start: 6, end: 7 }
+        ]);
+    }
+
+    #[test]
+    fn text_round_trip() {
+        for unit in WorkUnit::split_product(1, 100, 3).into_iter()
+            .chain(WorkUnit::split_sum(&[n("12"), n("345")], 1))
+            .chain(WorkUnit::split_divisors(&n("1000001"), 2, 1000, 2))
+        {
+            let line = unit.to_string();
+            assert_eq!(line.parse::<WorkUnit>(), Ok(unit.clone()));
+            let result = unit.execute();
+            assert_eq!(result.to_string().parse::<WorkResult>(), Ok(result));
+        }
+        assert_eq!("product 1".parse::<WorkUnit>(), Err(ShardError::InvalidFormat));
+    }
+
+    #[test]
+    fn mismatched() {
+        let results = vec![WorkResult::Sum(n("1")), WorkResult::Product(n("2"))];
+        assert_eq!(WorkUnit::merge(results), Err(ShardError::Mismatched));
+        assert_eq!(WorkUnit::merge(vec![]), Err(ShardError::Empty));
+    }
+}
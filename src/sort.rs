@@ -0,0 +1,109 @@
+//! Radix Sorting
+//!
+//! An LSD radix sort reads each digit once instead of comparing whole
+//! numbers O(n log n) times. Values are first bucketed by significant
+//! length, since a longer number is always larger, and then each bucket is
+//! sorted digit by digit, so a few very long values do not slow down the
+//! rest. The sort works on indices and only swaps the values into place at
+//! the end, never cloning them.
+
+use crate::natural::Natural;
+
+// Stable counting sort of `order` by `bucket`, which must be below `buckets`
+fn counting_sort(order: &mut [usize], scratch: &mut [usize], buckets: usize, bucket: impl Fn(usize) -> usize) {
+    let mut starts = vec![0; buckets + 1];
+    for i in order.iter() {
+        starts[bucket(*i) + 1] += 1;
+    }
+    for b in 0..buckets {
+        starts[b + 1] += starts[b];
+    }
+    for i in order.iter() {
+        let b = bucket(*i);
+        scratch[starts[b]] = *i;
+        starts[b] += 1;
+    }
+    order.copy_from_slice(scratch);
+}
+
+// Rearrange so that position j ends up holding what was at order[j]
+fn permute<T>(items: &mut [T], order: &[usize]) {
+    let mut done = vec![false; items.len()];
+    for start in 0..items.len() {
+        let mut j = start;
+        while !done[j] {
+            done[j] = true;
+            let k = order[j];
+            if k == start {
+                break;
+            }
+            items.swap(j, k);
+            j = k;
+        }
+    }
+}
+
+/// Sort ascending. Equal values keep their order.
+pub fn radix_sort(values: &mut [Natural]) {
+    radix_sort_by_key(values, |x| x);
+}
+
+/// Sort `items` ascending by a natural-number key. The sort is stable:
+/// items with equal keys keep their order.
+pub fn radix_sort_by_key<T>(items: &mut [T], key: impl Fn(&T) -> &Natural) {
+    let lengths: Vec<usize> = items.iter().map(|x| key(x).significant_len()).collect();
+    let longest = lengths.iter().copied().max().unwrap_or(0);
+    let mut order: Vec<usize> = (0..items.len()).collect();
+    let mut scratch = vec![0; items.len()];
+    counting_sort(&mut order, &mut scratch, longest + 1, |i| lengths[i]);
+
+    let mut start = 0;
+    while start < order.len() {
+        let len = lengths[order[start]];
+        let end = start + order[start..].iter().take_while(|i| lengths[**i] == len).count();
+        for p in 0..len {
+            counting_sort(&mut order[start..end], &mut scratch[start..end], 10, |i| {
+                usize::from(key(&items[i]).digits[p].as_u8())
+            });
+        }
+        start = end;
+    }
+
+    permute(items, &order);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn v(xs: &[&str]) -> Vec<Natural> {
+        xs.iter().map(|x| x.parse().unwrap()).collect()
+    }
+
+    #[test]
+    fn sorts() {
+        let mut values = v(&["310", "5", "0", "1000000", "42", "309", "5", "99"]);
+        radix_sort(&mut values);
+        assert_eq!(values, v(&["0", "5", "5", "42", "99", "309", "310", "1000000"]));
+    }
+
+    #[test]
+    fn matches_comparison_sort() {
+        let mut values: Vec<Natural> = (0..2000_u64)
+            .map(|i| (i * 2654435761 % 100_003).pow(i as u32 % 3 + 1).to_string().parse().unwrap())
+            .collect();
+        let mut expected = values.clone();
+        expected.sort();
+        radix_sort(&mut values);
+        assert_eq!(values, expected);
+    }
+
+    #[test]
+    fn stable() {
+        let mut records = vec![("a", 3), ("b", 1), ("c", 3), ("d", 0), ("e", 1)];
+        let keys: Vec<Natural> = (0..4).map(|k: u32| k.to_string().parse().unwrap()).collect();
+        radix_sort_by_key(&mut records, |r| &keys[r.1]);
+        let names: Vec<&str> = records.iter().map(|r| r.0).collect();
+        assert_eq!(names, ["d", "b", "e", "a", "c"]);
+    }
+}
@@ -0,0 +1,188 @@
+//! Spreadsheet Number Strings
+//!
+//! Numbers as spreadsheets write them, such as `"1,234.50"`, `"1.234,50"`
+//! in much of Europe, or `"(1,234)"` for a negative in accounting
+//! formats, read exactly and written back. Reading insists on groups of
+//! three between thousands separators, so a decimal comma misread as a
+//! thousands separator fails instead of giving a value a thousand times
+//! too large.
+
+use crate::error::ParseSpreadsheetError;
+use crate::integer::{Integer, Sign};
+use crate::natural::Natural;
+
+/// How spreadsheet strings separate digits and write negatives.
+#[derive(Clone,Copy,Debug,PartialEq,Eq)]
+pub struct SpreadsheetFormat {
+    /// Between groups of three whole digits
+    pub thousands: char,
+    /// Before the fractional digits
+    pub decimal: char,
+    /// Write negatives as `"(1,234)"` rather than `"-1,234"`; reading
+    /// accepts both
+    pub parentheses: bool
+}
+
+/// `"1,234.5"`, with a leading `-` for negatives.
+impl Default for SpreadsheetFormat {
+    fn default() -> Self {
+        Self{ thousands: ',', decimal: '.', parentheses: false }
+    }
+}
+
+// The sign, the whole digits and the fractional digits
+fn split(s: &str, format: SpreadsheetFormat) -> Result<(Sign, String, String), ParseSpreadsheetError> {
+    assert!(format.thousands != format.decimal, "thousands and decimal separators must differ");
+    let s = s.trim();
+    let (sign, s) = match (s.strip_prefix('('), s.strip_suffix(')')) {
+        (Some(_), Some(_)) => (Sign::Negative, &s[1..s.len() - 1]),
+        (Some(_), None) => return Err(ParseSpreadsheetError::InvalidCharacter('(')),
+        (None, Some(_)) => return Err(ParseSpreadsheetError::InvalidCharacter(')')),
+        (None, None) => match s.strip_prefix('-') {
+            Some(rest) => (Sign::Negative, rest),
+            None => (Sign::Positive, s.strip_prefix('+').unwrap_or(s))
+        }
+    };
+    let (whole, fraction) = s.split_once(format.decimal).unwrap_or((s, ""));
+    if whole.is_empty() && fraction.is_empty() {
+        return Err(ParseSpreadsheetError::Empty);
+    }
+    let groups: Vec<&str> = whole.split(format.thousands).collect();
+    if groups.len() > 1 && (!(1..=3).contains(&groups[0].len()) || groups[1..].iter().any(|g| g.len() != 3)) {
+        return Err(ParseSpreadsheetError::Grouping);
+    }
+    let whole = groups.concat();
+    if let Some(c) = whole.chars().chain(fraction.chars()).find(|c| !c.is_ascii_digit()) {
+        return Err(ParseSpreadsheetError::InvalidCharacter(c));
+    }
+    Ok((sign, whole, fraction.to_string()))
+}
+
+fn natural(digits: &str) -> Result<Natural, ParseSpreadsheetError> {
+    match digits {
+        "" => Ok(Natural::zero()),
+        _ => digits.parse().map_err(|_| ParseSpreadsheetError::TooLong)
+    }
+}
+
+// Group the whole digits and apply the sign
+fn write(negative: bool, whole: &str, fraction: Option<&str>, format: SpreadsheetFormat) -> String {
+    let mut s = String::new();
+    for (i, c) in whole.chars().enumerate() {
+        if i > 0 && (whole.len() - i).is_multiple_of(3) {
+            s.push(format.thousands);
+        }
+        s.push(c);
+    }
+    if let Some(fraction) = fraction {
+        s.push(format.decimal);
+        s.push_str(fraction);
+    }
+    match (negative, format.parentheses) {
+        (false, _) => s,
+        (true, true) => format!("({})", s),
+        (true, false) => format!("-{}", s)
+    }
+}
+
+impl Integer {
+    /// Read a spreadsheet string. A fraction is allowed only if it is all
+    /// zeros, as in `"12.00"`.
+    pub fn parse_spreadsheet(s: &str, format: SpreadsheetFormat) -> Result<Integer, ParseSpreadsheetError> {
+        let (sign, whole, fraction) = split(s, format)?;
+        if fraction.chars().any(|c| c != '0') {
+            return Err(ParseSpreadsheetError::NotInteger);
+        }
+        Ok(Integer::new(sign, natural(&whole)?))
+    }
+
+    pub fn to_spreadsheet(&self, format: SpreadsheetFormat) -> String {
+        write(self.is_negative(), &self.magnitude().to_string(), None, format)
+    }
+}
+
+#[cfg(feature = "rational")]
+impl crate::rational::Rational {
+    /// Read a spreadsheet string exactly.
+    pub fn parse_spreadsheet(s: &str, format: SpreadsheetFormat) -> Result<Self, ParseSpreadsheetError> {
+        let (sign, whole, fraction) = split(s, format)?;
+        let numerator = natural(&(whole + &fraction))?;
+        Ok(Self::new(Integer::new(sign, numerator), Natural::power_of_ten(fraction.len())))
+    }
+
+    /// Write with exactly `places` fractional digits, rounding half away
+    /// from zero as spreadsheets do.
+    pub fn to_spreadsheet(&self, places: usize, format: SpreadsheetFormat) -> String {
+        use crate::rational::Notation;
+        use crate::rounding::RoundingMode;
+        let s = self.format(Notation::Decimal{ places, mode: RoundingMode::HalfUp });
+        let (negative, s) = match s.strip_prefix('-') {
+            Some(s) => (true, s),
+            None => (false, s.as_str())
+        };
+        let (whole, fraction) = match s.split_once('.') {
+            Some((whole, fraction)) => (whole, Some(fraction)),
+            None => (s, None)
+        };
+        write(negative, whole, fraction, format)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EUROPEAN: SpreadsheetFormat = SpreadsheetFormat{ thousands: '.', decimal: ',', parentheses: false };
+    const ACCOUNTING: SpreadsheetFormat = SpreadsheetFormat{ thousands: ',', decimal: '.', parentheses: true };
+
+    fn i(s: &str) -> Integer {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn integers() {
+        let us = SpreadsheetFormat::default();
+        assert_eq!(Integer::parse_spreadsheet("1,234,567", us), Ok(i("1234567")));
+        assert_eq!(Integer::parse_spreadsheet(" (1,234) ", us), Ok(i("-1234")));
+        assert_eq!(Integer::parse_spreadsheet("-12.00", us), Ok(i("-12")));
+        assert_eq!(Integer::parse_spreadsheet("+7", us), Ok(i("7")));
+        assert_eq!(Integer::parse_spreadsheet("1.234.567", EUROPEAN), Ok(i("1234567")));
+        assert_eq!(i("-1234567").to_spreadsheet(us), "-1,234,567");
+        assert_eq!(i("-1234567").to_spreadsheet(ACCOUNTING), "(1,234,567)");
+        assert_eq!(i("999").to_spreadsheet(EUROPEAN), "999");
+        for s in ["0", "-1", "1000", "-123456789012345678901234567890"] {
+            assert_eq!(Integer::parse_spreadsheet(&i(s).to_spreadsheet(ACCOUNTING), ACCOUNTING), Ok(i(s)));
+        }
+    }
+
+    #[test]
+    fn rejects() {
+        let us = SpreadsheetFormat::default();
+        assert_eq!(Integer::parse_spreadsheet("  ", us), Err(ParseSpreadsheetError::Empty));
+        assert_eq!(Integer::parse_spreadsheet("-", us), Err(ParseSpreadsheetError::Empty));
+        assert_eq!(Integer::parse_spreadsheet("1,23", us), Err(ParseSpreadsheetError::Grouping));
+        assert_eq!(Integer::parse_spreadsheet("1234,567", us), Err(ParseSpreadsheetError::Grouping));
+        assert_eq!(Integer::parse_spreadsheet(",123", us), Err(ParseSpreadsheetError::Grouping));
+        assert_eq!(Integer::parse_spreadsheet("12.5", us), Err(ParseSpreadsheetError::NotInteger));
+        assert_eq!(Integer::parse_spreadsheet("(12", us), Err(ParseSpreadsheetError::InvalidCharacter('(')));
+        assert_eq!(Integer::parse_spreadsheet("(-12)", us), Err(ParseSpreadsheetError::InvalidCharacter('-')));
+        assert_eq!(Integer::parse_spreadsheet("$12", us), Err(ParseSpreadsheetError::InvalidCharacter('$')));
+        assert_eq!(Integer::parse_spreadsheet("1.2.3", us), Err(ParseSpreadsheetError::InvalidCharacter('.')));
+    }
+
+    #[cfg(feature = "rational")]
+    #[test]
+    fn rationals() {
+        use crate::rational::Rational;
+        let q = |s: &str| s.parse::<Rational>().unwrap();
+        let us = SpreadsheetFormat::default();
+        assert_eq!(Rational::parse_spreadsheet("1.234,50", EUROPEAN), Ok(q("1234.5")));
+        assert_eq!(Rational::parse_spreadsheet("(0.25)", us), Ok(q("-1/4")));
+        assert_eq!(Rational::parse_spreadsheet(",5", EUROPEAN), Ok(q("1/2")));
+        assert_eq!(Rational::parse_spreadsheet("1,50", us), Err(ParseSpreadsheetError::Grouping));
+        assert_eq!(q("1234.5").to_spreadsheet(2, EUROPEAN), "1.234,50");
+        assert_eq!(q("-2/3").to_spreadsheet(2, ACCOUNTING), "(0.67)");
+        assert_eq!(q("-0.125").to_spreadsheet(2, us), "-0.13");
+        assert_eq!(q("1234567/2").to_spreadsheet(0, us), "617,284");
+    }
+}
@@ -0,0 +1,179 @@
+//! Exact Statistics
+//!
+//! Sums, means, variances, medians and quantiles of `Natural` or `Integer`
+//! data as exact `Rational`s, so the same data always gives the same
+//! answer, with no rounding anywhere.
+
+use crate::integer::Integer;
+use crate::natural::Natural;
+use crate::rational::Rational;
+
+/// The sum of `values`, zero for an empty slice.
+pub fn sum(values: &[Natural]) -> Natural {
+    values.iter().sum()
+}
+
+/// The arithmetic mean. `None` for an empty slice.
+pub fn mean<T: Clone + Into<Rational>>(values: &[T]) -> Option<Rational> {
+    if values.is_empty() {
+        return None;
+    }
+    let total = values.iter().fold(Rational::zero(), |total, x| total + x.clone().into());
+    Some(total / Rational::from(Natural::from(values.len())))
+}
+
+// The sum of squared deviations from the mean
+fn squared_deviations<T: Clone + Into<Rational>>(values: &[T]) -> Option<Rational> {
+    let mean = mean(values)?;
+    Some(values.iter().fold(Rational::zero(), |total, x| {
+        let deviation = x.clone().into() - mean.clone();
+        total + deviation.clone() * deviation
+    }))
+}
+
+/// The population variance, the mean squared deviation from the mean.
+/// `None` for an empty slice.
+pub fn variance<T: Clone + Into<Rational>>(values: &[T]) -> Option<Rational> {
+    let total = squared_deviations(values)?;
+    Some(total / Rational::from(Natural::from(values.len())))
+}
+
+/// The sample variance, which divides by `len - 1` to be unbiased. `None`
+/// for fewer than two values.
+pub fn sample_variance<T: Clone + Into<Rational>>(values: &[T]) -> Option<Rational> {
+    if values.len() < 2 {
+        return None;
+    }
+    let total = squared_deviations(values)?;
+    Some(total / Rational::from(Natural::from(values.len() - 1)))
+}
+
+/// The median, averaging the two middle values of an even-length slice.
+/// `None` for an empty slice.
+pub fn median<T: Ord + Clone + Into<Rational>>(values: &[T]) -> Option<Rational> {
+    quantile(values, Rational::new(Integer::one(), Natural::from(2u32)))
+}
+
+/// The `p` quantile, interpolating linearly between the two nearest
+/// values: with the values sorted, position `(len - 1) * p`, where a
+/// position between indices takes the matching fraction of the gap. This
+/// is the default method of NumPy and of R. `None` for an empty slice.
+///
+/// Selection works on references in expected linear time, without sorting
+/// or cloning the data. Panics unless `p` is between zero and one.
+pub fn quantile<T: Ord + Clone + Into<Rational>>(values: &[T], p: Rational) -> Option<Rational> {
+    assert!(p >= Rational::zero() && p <= Rational::one(), "quantile outside 0..=1");
+    if values.is_empty() {
+        return None;
+    }
+
+    // The whole part of the position, and the fraction of the gap after it
+    let last = Natural::from(values.len() - 1);
+    let position = Rational::from(last) * p;
+    let (whole, part) = position.numerator().magnitude().div_rem(position.denominator());
+    let index = usize::try_from(&whole).unwrap();
+    let fraction = Rational::new(Integer::from(part), position.denominator().clone());
+
+    let mut refs: Vec<&T> = values.iter().collect();
+    let (_, low, above) = refs.select_nth_unstable(index);
+    let low: Rational = (*low).clone().into();
+    if fraction == Rational::zero() {
+        return Some(low);
+    }
+    let high: Rational = (**above.iter().min().unwrap()).clone().into();
+    Some(low.clone() + fraction * (high - low))
+}
+
+/// The mean of `values` weighted by `weights`, `sum(w * x) / sum(w)`.
+/// `None` when the weights sum to zero, including when there are none.
+///
+/// Panics if the slices have different lengths.
+pub fn weighted_mean<T: Clone + Into<Rational>>(values: &[T], weights: &[Rational]) -> Option<Rational> {
+    assert_eq!(values.len(), weights.len(), "one weight per value");
+    let (total, weight) = values.iter().zip(weights).fold(
+        (Rational::zero(), Rational::zero()),
+        |(total, weight), (x, w)| (total + w.clone() * x.clone().into(), weight + w.clone())
+    );
+    if weight == Rational::zero() {
+        return None;
+    }
+    Some(total / weight)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn n(s: &str) -> Natural {
+        s.parse().unwrap()
+    }
+
+    fn q(s: &str) -> Rational {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn sums_and_means() {
+        let huge = n(&"9".repeat(40));
+        let values = [huge.clone(), n("1"), n("2")];
+        assert_eq!(sum(&values), huge.clone() + n("3"));
+        assert_eq!(sum(&[]), Natural::zero());
+        assert_eq!(mean(&values), Some(Rational::from(huge + n("3")) / q("3")));
+        assert_eq!(mean(&[n("1"), n("2")]), Some(q("3/2")));
+        assert_eq!(mean::<Natural>(&[]), None);
+        let values: Vec<Integer> = ["-4", "1"].iter().map(|s| s.parse().unwrap()).collect();
+        assert_eq!(mean(&values), Some(q("-3/2")));
+    }
+
+    #[test]
+    fn variances() {
+        let values = [n("2"), n("4"), n("4"), n("4"), n("5"), n("5"), n("7"), n("9")];
+        assert_eq!(variance(&values), Some(q("4")));
+        assert_eq!(sample_variance(&values), Some(q("32/7")));
+        assert_eq!(variance(&[n("1"), n("2")]), Some(q("1/4")));
+        assert_eq!(variance(&[n("5")]), Some(Rational::zero()));
+        assert_eq!(sample_variance(&[n("5")]), None);
+        assert_eq!(variance::<Natural>(&[]), None);
+        // A float would lose the 1 next to 10^30
+        let big = n(&format!("1{}", "0".repeat(30)));
+        assert_eq!(variance(&[big.clone(), big + n("1")]), Some(q("1/4")));
+    }
+
+    #[test]
+    fn medians() {
+        assert_eq!(median(&[n("3"), n("1"), n("2")]), Some(q("2")));
+        assert_eq!(median(&[n("4"), n("1"), n("2"), n("3")]), Some(q("5/2")));
+        let huge = n(&"9".repeat(50));
+        assert_eq!(median(&[huge.clone(), n("0")]), Some(Rational::from(huge) / q("2")));
+        assert_eq!(median::<Natural>(&[]), None);
+    }
+
+    #[test]
+    fn integer_quantiles() {
+        let values: Vec<Integer> = ["-5", "10", "0", "-1", "7"].iter().map(|s| s.parse().unwrap()).collect();
+        assert_eq!(quantile(&values, q("0")), Some(q("-5")));
+        assert_eq!(quantile(&values, q("1")), Some(q("10")));
+        assert_eq!(quantile(&values, q("1/2")), Some(q("0")));
+        // Position 4 * 0.3 = 1.2, a fifth of the way from -1 to 0
+        assert_eq!(quantile(&values, q("0.3")), Some(q("-4/5")));
+        assert_eq!(quantile(&values, q("0.9")), Some(q("8.8")));
+    }
+
+    #[test]
+    #[should_panic(expected = "quantile outside 0..=1")]
+    fn out_of_range() {
+        quantile(&[n("1")], q("3/2"));
+    }
+
+    #[test]
+    fn weighted_means() {
+        let values = [n("100"), n("200"), n("400")];
+        let weights = [q("1/2"), q("1/3"), q("1/6")];
+        assert_eq!(weighted_mean(&values, &weights), Some(q("550/3")));
+        assert_eq!(weighted_mean(&values, &[q("1"), q("1"), q("1")]), Some(q("700/3")));
+        assert_eq!(weighted_mean(&values, &[q("1"), q("-1"), q("0")]), None);
+        assert_eq!(weighted_mean::<Natural>(&[], &[]), None);
+        let values: Vec<Integer> = vec!["-3".parse().unwrap(), "5".parse().unwrap()];
+        assert_eq!(weighted_mean(&values, &[q("0.75"), q("0.25")]), Some(q("-1")));
+    }
+}
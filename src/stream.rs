@@ -0,0 +1,348 @@
+//! Running Totals
+//!
+//! Prefix sums and products over iterators, a signed accumulator, and sums
+//! and means over a sliding window of a stream. Each keeps its running
+//! totals and updates them in place, so their digit buffers are reused from
+//! step to step; only the value handed out is a copy.
+
+use crate::integer::Integer;
+use crate::natural::Natural;
+#[cfg(feature = "rational")]
+use crate::rational::Rational;
+use std::collections::VecDeque;
+
+/// Prefix sums and products of any iterator, such as
+/// `counts.into_iter().cumulative_sum::<Natural>()`.
+pub trait Cumulative: Iterator + Sized {
+    /// The running sum, in any type that can start from an item and have
+    /// items added to it.
+    fn cumulative_sum<T: From<Self::Item> + std::ops::AddAssign<Self::Item> + Clone>(self) -> CumulativeSum<Self, T> {
+        CumulativeSum{ iter: self, total: None }
+    }
+
+    /// The running product, in any type that can start from an item and be
+    /// multiplied by items.
+    fn cumulative_product<T: From<Self::Item> + std::ops::MulAssign<Self::Item> + Clone>(self) -> CumulativeProduct<Self, T> {
+        CumulativeProduct{ iter: self, total: None }
+    }
+}
+
+impl<I: Iterator> Cumulative for I {}
+
+#[derive(Clone,Debug)]
+pub struct CumulativeSum<I, T> {
+    iter: I,
+    total: Option<T>
+}
+
+impl<I, T> Iterator for CumulativeSum<I, T>
+where
+    I: Iterator,
+    T: From<I::Item> + std::ops::AddAssign<I::Item> + Clone
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        let item = self.iter.next()?;
+        match &mut self.total {
+            Some(total) => *total += item,
+            None => self.total = Some(T::from(item))
+        }
+        self.total.clone()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+#[derive(Clone,Debug)]
+pub struct CumulativeProduct<I, T> {
+    iter: I,
+    total: Option<T>
+}
+
+impl<I, T> Iterator for CumulativeProduct<I, T>
+where
+    I: Iterator,
+    T: From<I::Item> + std::ops::MulAssign<I::Item> + Clone
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        let item = self.iter.next()?;
+        match &mut self.total {
+            Some(total) => *total *= item,
+            None => self.total = Some(T::from(item))
+        }
+        self.total.clone()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+/// The exact sum of the last `k` values pushed.
+///
+/// Each push adds the new value and subtracts the one leaving the window,
+/// so the cost of a push does not depend on `k`.
+#[derive(Clone,Debug,PartialEq,Eq)]
+pub struct WindowedSum {
+    window: VecDeque<Natural>,
+    capacity: usize,
+    sum: Natural
+}
+
+impl WindowedSum {
+    /// Panics if `k` is zero.
+    pub fn new(k: usize) -> Self {
+        assert!(k > 0, "empty window");
+        Self{ window: VecDeque::with_capacity(k + 1), capacity: k, sum: Natural::zero() }
+    }
+
+    /// Add `value` to the window, returning the value it pushes out once
+    /// the window is full.
+    pub fn push(&mut self, value: Natural) -> Option<Natural> {
+        self.sum += &value;
+        self.window.push_back(value);
+        if self.window.len() <= self.capacity {
+            return None;
+        }
+        let expired = self.window.pop_front().unwrap();
+        self.sum -= &expired;
+        Some(expired)
+    }
+
+    /// The sum of the values in the window, zero before any push.
+    pub fn sum(&self) -> &Natural {
+        &self.sum
+    }
+
+    /// How many values are in the window, at most `k`.
+    pub fn len(&self) -> usize {
+        self.window.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.window.is_empty()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// The values in the window, oldest first.
+    pub fn values(&self) -> impl Iterator<Item = &Natural> {
+        self.window.iter()
+    }
+}
+
+/// An exact running total that may go negative, for streaming
+/// aggregation.
+///
+/// Additions and subtractions go into two separate `Natural` totals, each
+/// updated in place, so an update allocates only when a total outgrows its
+/// digit buffer. The signed result is worked out only for a `snapshot`.
+/// Partial accumulators from separate parts of a stream can be `merge`d,
+/// in any order, for map-reduce.
+#[derive(Clone,Debug)]
+pub struct Accumulator {
+    added: Natural,
+    subtracted: Natural
+}
+
+impl Default for Accumulator {
+    fn default() -> Self {
+        Self{ added: Natural::zero(), subtracted: Natural::zero() }
+    }
+}
+
+impl Accumulator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The total so far.
+    pub fn snapshot(&self) -> Integer {
+        Integer::from(self.added.clone()) - Integer::from(self.subtracted.clone())
+    }
+
+    // Add `magnitude` to the total it belongs in
+    fn update(&mut self, negative: bool, magnitude: &Natural) {
+        match negative {
+            true => self.subtracted += magnitude,
+            false => self.added += magnitude
+        }
+    }
+
+    /// Add in the total of `other`.
+    pub fn merge(&mut self, other: &Accumulator) {
+        self.added += &other.added;
+        self.subtracted += &other.subtracted;
+    }
+}
+
+impl std::ops::AddAssign<&Natural> for Accumulator {
+    fn add_assign(&mut self, value: &Natural) {
+        self.update(false, value);
+    }
+}
+
+impl std::ops::SubAssign<&Natural> for Accumulator {
+    fn sub_assign(&mut self, value: &Natural) {
+        self.update(true, value);
+    }
+}
+
+impl std::ops::AddAssign<&Integer> for Accumulator {
+    fn add_assign(&mut self, value: &Integer) {
+        self.update(value.is_negative(), value.magnitude());
+    }
+}
+
+impl std::ops::SubAssign<&Integer> for Accumulator {
+    fn sub_assign(&mut self, value: &Integer) {
+        self.update(!value.is_negative(), value.magnitude());
+    }
+}
+
+/// The exact mean of the last `k` values pushed, kept as a `WindowedSum`
+/// and divided only when asked.
+#[cfg(feature = "rational")]
+#[derive(Clone,Debug,PartialEq,Eq)]
+pub struct WindowedMean {
+    sum: WindowedSum
+}
+
+#[cfg(feature = "rational")]
+impl WindowedMean {
+    /// Panics if `k` is zero.
+    pub fn new(k: usize) -> Self {
+        Self{ sum: WindowedSum::new(k) }
+    }
+
+    /// Add `value` to the window, returning the value it pushes out once
+    /// the window is full.
+    pub fn push(&mut self, value: Natural) -> Option<Natural> {
+        self.sum.push(value)
+    }
+
+    /// The mean of the values in the window, or `None` before any push.
+    pub fn mean(&self) -> Option<Rational> {
+        match self.sum.len() {
+            0 => None,
+            len => Some(Rational::new(Integer::from(self.sum.sum().clone()), Natural::from(len)))
+        }
+    }
+
+    /// The window itself.
+    pub fn windowed_sum(&self) -> &WindowedSum {
+        &self.sum
+    }
+
+    /// Continue this window with the values of `later`, the window over
+    /// the part of the stream that follows this one, as if they had been
+    /// pushed here in turn. This keeps its own `k`, so only the last `k`
+    /// values of the two together remain.
+    pub fn merge(&mut self, later: &WindowedMean) {
+        for value in later.sum.values() {
+            self.push(value.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn n(s: &str) -> Natural {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn sums() {
+        let counts = vec![n("999"), n("1"), n("99000"), n("0")];
+        let sums: Vec<Natural> = counts.into_iter().cumulative_sum::<Natural>().collect();
+        assert_eq!(sums, vec![n("999"), n("1000"), n("100000"), n("100000")]);
+        assert_eq!(Vec::<Natural>::new().into_iter().cumulative_sum::<Natural>().next(), None);
+
+        // Natural items into an Integer total
+        let sums: Vec<String> = vec![n("3"), n("4")].into_iter().cumulative_sum::<Integer>().map(|i| i.to_string()).collect();
+        assert_eq!(sums, vec!["3", "7"]);
+    }
+
+    #[test]
+    fn factorials() {
+        let factorials: Vec<Natural> = (1..=25u32).map(Natural::from).cumulative_product().collect();
+        assert_eq!(factorials[4], n("120"));
+        assert_eq!(factorials[24], n("15511210043330985984000000"));
+    }
+
+    #[test]
+    fn accumulator() {
+        let i = |s: &str| s.parse::<Integer>().unwrap();
+        let mut total = Accumulator::new();
+        assert_eq!(total.snapshot(), Integer::zero());
+        total += &n("5");
+        total -= &n("12");
+        assert_eq!(total.snapshot(), i("-7"));
+        total += &i("-3");
+        total -= &i("-20");
+        assert_eq!(total.snapshot(), i("10"));
+        total -= &n("10");
+        assert_eq!(total.snapshot(), Integer::zero());
+
+        // Partial totals over two halves merge to the total of the whole
+        let values: Vec<Integer> = ["123456789012345678901234567890", "-1", "-999999999999999999999999999999", "42"]
+            .iter().map(|s| i(s)).collect();
+        let partial = |part: &[Integer]| {
+            let mut total = Accumulator::new();
+            part.iter().for_each(|x| total += x);
+            total
+        };
+        let whole = partial(&values);
+        let (mut first, mut second) = (partial(&values[..2]), partial(&values[2..]));
+        second.merge(&first);
+        first.merge(&partial(&values[2..]));
+        assert_eq!(first.snapshot(), whole.snapshot());
+        assert_eq!(second.snapshot(), whole.snapshot());
+        assert_eq!(whole.snapshot(), i("-876543210987654321098765432068"));
+    }
+
+    #[test]
+    fn windowed_sum() {
+        let mut w = WindowedSum::new(3);
+        assert_eq!(w.sum(), &Natural::zero());
+        let mut sums = vec![];
+        for x in ["999", "1", "5000", "7", "0", "123456789012345678901234567890"] {
+            w.push(n(x));
+            sums.push(w.sum().to_string());
+        }
+        assert_eq!(sums, ["999", "1000", "6000", "5008", "5007", "123456789012345678901234567897"]);
+        assert_eq!(w.len(), 3);
+        assert_eq!(w.push(n("1")), Some(n("7")));
+        assert_eq!(w.values().cloned().collect::<Vec<_>>(), vec![n("0"), n("123456789012345678901234567890"), n("1")]);
+    }
+
+    #[cfg(feature = "rational")]
+    #[test]
+    fn windowed_mean() {
+        let q = |s: &str| s.parse::<Rational>().unwrap();
+        let mut w = WindowedMean::new(3);
+        assert_eq!(w.mean(), None);
+        w.push(n("1"));
+        w.push(n("2"));
+        assert_eq!(w.mean(), Some(q("3/2")));
+        w.push(n("4"));
+        w.push(n("10"));
+        assert_eq!(w.mean(), Some(q("16/3")));
+
+        let mut later = WindowedMean::new(2);
+        later.push(n("7"));
+        let mut merged = w.clone();
+        merged.merge(&later);
+        assert_eq!(merged.mean(), Some(q("7")));
+        assert_eq!(merged.windowed_sum().values().cloned().collect::<Vec<_>>(), vec![n("4"), n("10"), n("7")]);
+    }
+}
@@ -0,0 +1,316 @@
+//! Futures for Long Computations
+//!
+//! `spawn` runs a computation on a thread of its own and returns a `Task`,
+//! a future for its result that any executor can await without blocking.
+//! There is no thread pool and no runtime dependency: it is the
+//! `spawn_blocking` pattern with a plain `std::thread`.
+//!
+//! Cancellation is cooperative. Cancelling a task, or dropping it, sets
+//! its `CancelToken`, and the computation is expected to check the token
+//! between steps and give up by returning `None`.
+
+use crate::digit::Digit;
+use crate::natural::Natural;
+use crate::thresholds::Thresholds;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+
+#[derive(Clone,Debug,Default)]
+pub struct CancelToken {
+    cancelled: Arc<AtomicBool>
+}
+
+impl CancelToken {
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+}
+
+struct State<T> {
+    result: Option<std::thread::Result<Option<T>>>,
+    waker: Option<Waker>
+}
+
+/// A computation running on its own thread.
+///
+/// Resolves to `None` if the computation was cancelled. A panic in the
+/// computation is resumed in whoever polls the task.
+pub struct Task<T> {
+    state: Arc<Mutex<State<T>>>,
+    token: CancelToken
+}
+
+impl<T> Task<T> {
+    /// Ask the computation to stop. It may still finish if it has no more
+    /// checks of the token left to make.
+    pub fn cancel(&self) {
+        self.token.cancel();
+    }
+}
+
+impl<T> Future for Task<T> {
+    type Output = Option<T>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.state.lock().unwrap();
+        match state.result.take() {
+            Some(Ok(output)) => Poll::Ready(output),
+            Some(Err(panic)) => std::panic::resume_unwind(panic),
+            None => {
+                state.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl<T> Drop for Task<T> {
+    fn drop(&mut self) {
+        self.token.cancel();
+    }
+}
+
+/// Run `work` on a new thread, passing it the task's cancellation token.
+pub fn spawn<T, F>(work: F) -> Task<T>
+where
+    T: Send + 'static,
+    F: FnOnce(&CancelToken) -> Option<T> + Send + 'static
+{
+    let state = Arc::new(Mutex::new(State{ result: None, waker: None }));
+    let token = CancelToken::default();
+    let (shared, worker_token) = (state.clone(), token.clone());
+    std::thread::spawn(move || {
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| work(&worker_token)));
+        let mut state = shared.lock().unwrap();
+        state.result = Some(result);
+        if let Some(waker) = state.waker.take() {
+            waker.wake();
+        }
+    });
+    Task{ state, token }
+}
+
+// Products this short run between checks of the token
+const UNCHECKED: usize = 1000;
+
+// Karatsuba's method as `Natural` multiplies, checking for cancellation
+// before each of the three half-size products
+fn mul_cancellable(a: &Natural, b: &Natural, token: &CancelToken) -> Option<Natural> {
+    if token.is_cancelled() {
+        return None;
+    }
+    let threshold = Thresholds::current().karatsuba;
+    if a.digits.len().max(b.digits.len()) < UNCHECKED || a.digits.len().min(b.digits.len()) < threshold.max(2) {
+        return Some(a.mul_karatsuba(b, threshold));
+    }
+    let m = a.digits.len().max(b.digits.len()) / 2;
+    let (low_a, high_a) = a.split_at(m);
+    let (low_b, high_b) = b.split_at(m);
+    let high = mul_cancellable(&high_a, &high_b, token)?;
+    let low = mul_cancellable(&low_a, &low_b, token)?;
+    let middle = mul_cancellable(&(high_a + low_a), &(high_b + low_b), token)? - high.clone() - low.clone();
+    Some(high.shifted(2 * m) + middle.shifted(m) + low)
+}
+
+// Long division a chunk of digits of `a` at a time, checking for
+// cancellation between chunks
+fn div_rem_cancellable(a: &Natural, b: &Natural, token: &CancelToken) -> Option<(Natural, Natural)> {
+    assert!(*b != Natural::zero(), "attempt to divide by zero");
+    let mut chunks = vec![];
+    let mut remainder = Natural::zero();
+    let mut end = a.digits.len();
+    while end > 0 {
+        if token.is_cancelled() {
+            return None;
+        }
+        let start = end.saturating_sub(UNCHECKED);
+        let part = Natural::from_digits(a.digits[start..end].to_vec());
+        // The remainder is below b, so this quotient has at most the
+        // chunk's length in digits
+        let (mut q, r) = (remainder.shifted(end - start) + part).div_rem(b);
+        q.digits.resize(end - start, Digit::Zero);
+        chunks.push(q.digits);
+        remainder = r;
+        end = start;
+    }
+    let digits = chunks.into_iter().rev().flatten().collect();
+    Some((Natural::from_digits(digits), remainder))
+}
+
+// Square and multiply, as `Natural::pow`
+fn pow_cancellable(base: &Natural, exp: usize, token: &CancelToken) -> Option<Natural> {
+    let mut result = Natural::one();
+    let mut base = base.clone();
+    let mut exp = exp;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = mul_cancellable(&result, &base, token)?;
+        }
+        exp >>= 1;
+        if exp > 0 {
+            base = mul_cancellable(&base, &base, token)?;
+        }
+    }
+    Some(result)
+}
+
+// Square and multiply with a reduction after every step, as
+// `Natural::mod_pow`
+fn mod_pow_cancellable(base: &Natural, exp: &Natural, modulus: &Natural, token: &CancelToken) -> Option<Natural> {
+    let reduce = |x: Natural| div_rem_cancellable(&x, modulus, token).map(|(_, r)| r);
+    let mut base = reduce(base.clone())?;
+    let mut result = reduce(Natural::one())?;
+    for bit in exp.to_power_of_two_le(1) {
+        if bit == 1 {
+            result = reduce(mul_cancellable(&result, &base, token)?)?;
+        }
+        base = reduce(mul_cancellable(&base, &base, token)?)?;
+    }
+    Some(result)
+}
+
+// Newton's method from above, as `Natural::nth_root`
+fn nth_root_cancellable(value: &Natural, n: usize, token: &CancelToken) -> Option<Natural> {
+    assert!(n > 0, "zeroth root");
+    let len = value.digits.len();
+    if n == 1 || *value <= Natural::one() {
+        return Some(value.clone());
+    }
+    if n >= 4 * len {
+        return Some(Natural::one());
+    }
+    let degree = Natural::from(n);
+    let below = Natural::from(n - 1);
+    let mut x = Natural::power_of_ten(len.div_ceil(n));
+    loop {
+        let (part, _) = div_rem_cancellable(value, &pow_cancellable(&x, n - 1, token)?, token)?;
+        let y = (mul_cancellable(&x, &below, token)? + part) / &degree;
+        if y >= x {
+            return Some(x);
+        }
+        x = y;
+    }
+}
+
+/// `a * b` on its own thread, cancellable between the halves of
+/// Karatsuba's method.
+pub fn mul(a: Natural, b: Natural) -> Task<Natural> {
+    spawn(move |token| mul_cancellable(&a, &b, token))
+}
+
+/// `a.div_rem(&b)` on its own thread, cancellable between chunks of the
+/// digits of `a`. Panics when polled if `b` is zero.
+pub fn div_rem(a: Natural, b: Natural) -> Task<(Natural, Natural)> {
+    spawn(move |token| div_rem_cancellable(&a, &b, token))
+}
+
+/// `base.pow(exp)` on its own thread, cancellable between and within its
+/// multiplications.
+pub fn pow(base: Natural, exp: usize) -> Task<Natural> {
+    spawn(move |token| pow_cancellable(&base, exp, token))
+}
+
+/// `base.mod_pow(&exp, &modulus)` on its own thread, cancellable between
+/// and within its multiplications and reductions. Panics when polled if
+/// `modulus` is zero.
+pub fn mod_pow(base: Natural, exp: Natural, modulus: Natural) -> Task<Natural> {
+    spawn(move |token| mod_pow_cancellable(&base, &exp, &modulus, token))
+}
+
+/// `value.nth_root(n)` on its own thread, cancellable between and within
+/// the steps of Newton's method. Panics when polled if `n` is zero.
+pub fn nth_root(value: Natural, n: usize) -> Task<Natural> {
+    spawn(move |token| nth_root_cancellable(&value, n, token))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::task::Wake;
+
+    struct Unpark(std::thread::Thread);
+
+    impl Wake for Unpark {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    // The smallest possible executor
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let mut future = std::pin::pin!(future);
+        let waker = Waker::from(Arc::new(Unpark(std::thread::current())));
+        let mut cx = Context::from_waker(&waker);
+        loop {
+            if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+                return output;
+            }
+            std::thread::park();
+        }
+    }
+
+    #[test]
+    fn multiply() {
+        let a: Natural = "123456789123456789".parse().unwrap();
+        let b: Natural = "987654321987654321".parse().unwrap();
+        assert_eq!(block_on(mul(a.clone(), b.clone())), Some(a * b));
+        // Long enough to split, unevenly
+        let a = Natural::from(7u32).pow(3000);
+        let b = Natural::from(3u32).pow(4000) + Natural::one();
+        assert_eq!(block_on(mul(a.clone(), b.clone())), Some(&a * &b));
+        assert_eq!(block_on(mul(a.clone(), Natural::from(3u32))), Some(a.mul_small(3)));
+    }
+
+    #[test]
+    fn operations() {
+        let a = Natural::from(7u32).pow(1800) + Natural::from(12345u32);
+        let b = Natural::from(3u32).pow(500) + Natural::one();
+        assert_eq!(block_on(div_rem(a.clone(), b.clone())), Some(a.div_rem(&b)));
+        assert_eq!(block_on(div_rem(b.clone(), a.clone())), Some((Natural::zero(), b.clone())));
+        assert_eq!(block_on(pow(b.clone(), 5)), Some(b.pow(5)));
+        assert_eq!(block_on(pow(b.clone(), 0)), Some(Natural::one()));
+        let (e, m) = (Natural::from(65537u32), Natural::from(10u32).pow(100) + Natural::from(267u32));
+        assert_eq!(block_on(mod_pow(b.clone(), e.clone(), m.clone())), Some(b.mod_pow(&e, &m)));
+        for n in [1, 2, 3, 7, 20000] {
+            assert_eq!(block_on(nth_root(a.clone(), n)), Some(a.nth_root(n)));
+        }
+    }
+
+    #[test]
+    fn cancelled_operations() {
+        let token = CancelToken::default();
+        token.cancel();
+        let a = Natural::from(7u32).pow(1800);
+        let b = Natural::from(3u32).pow(1500);
+        assert_eq!(mul_cancellable(&a, &b, &token), None);
+        assert_eq!(div_rem_cancellable(&a, &b, &token), None);
+        assert_eq!(pow_cancellable(&b, 3, &token), None);
+        assert_eq!(mod_pow_cancellable(&a, &b, &b, &token), None);
+        assert_eq!(nth_root_cancellable(&a, 3, &token), None);
+    }
+
+    #[test]
+    fn cancel() {
+        let task = spawn(|token| {
+            while !token.is_cancelled() {
+                std::thread::yield_now();
+            }
+            None::<()>
+        });
+        task.cancel();
+        assert_eq!(block_on(task), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "boom")]
+    fn panics_propagate() {
+        block_on(spawn(|_| -> Option<()> { panic!("boom") }));
+    }
+}
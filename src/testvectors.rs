@@ -0,0 +1,194 @@
+//! Golden Test Vectors
+//!
+//! Every release ships the same input/output files under `testvectors/`,
+//! one per `Operation`, and `run` checks the arithmetic of the running
+//! build against them: the basic operations and comparison, powers,
+//! modular powers, GCDs, roots, Fibonacci and Lucas numbers and radix
+//! conversion of `Natural`s, and the basic operations of `Integer`s. Any
+//! platform that passes computes the same results as every other.
+
+use crate::error::TestVectorError;
+use crate::integer::Integer;
+use crate::natural::Natural;
+
+#[derive(Clone,Copy,Debug,PartialEq,Eq)]
+pub enum Operation {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+    /// Expected value is one of `<`, `=` or `>`
+    Cmp,
+    Pow,
+    /// `a^b mod m`, the one operation with three operands
+    ModPow,
+    Gcd,
+    /// The floor of the `b`th root of `a`
+    NthRoot,
+    /// `F(n)`, from the single operand `n`
+    Fibonacci,
+    /// `L(n)`, from the single operand `n`
+    Lucas,
+    /// `a` written in radix `b`
+    Radix,
+    /// The integer operations take signed operands, and divide toward zero
+    IntegerAdd,
+    IntegerSub,
+    IntegerMul,
+    IntegerDiv,
+    IntegerRem
+}
+
+impl Operation {
+    fn operands(self) -> usize {
+        match self {
+            Operation::Fibonacci | Operation::Lucas => 1,
+            Operation::ModPow => 3,
+            _ => 2
+        }
+    }
+}
+
+const FILES: [(Operation, &str); 18] = [
+    (Operation::Add, include_str!("../testvectors/add.txt")),
+    (Operation::Sub, include_str!("../testvectors/sub.txt")),
+    (Operation::Mul, include_str!("../testvectors/mul.txt")),
+    (Operation::Div, include_str!("../testvectors/div.txt")),
+    (Operation::Rem, include_str!("../testvectors/rem.txt")),
+    (Operation::Cmp, include_str!("../testvectors/cmp.txt")),
+    (Operation::Pow, include_str!("../testvectors/pow.txt")),
+    (Operation::ModPow, include_str!("../testvectors/mod_pow.txt")),
+    (Operation::Gcd, include_str!("../testvectors/gcd.txt")),
+    (Operation::NthRoot, include_str!("../testvectors/nth_root.txt")),
+    (Operation::Fibonacci, include_str!("../testvectors/fibonacci.txt")),
+    (Operation::Lucas, include_str!("../testvectors/lucas.txt")),
+    (Operation::Radix, include_str!("../testvectors/radix.txt")),
+    (Operation::IntegerAdd, include_str!("../testvectors/integer_add.txt")),
+    (Operation::IntegerSub, include_str!("../testvectors/integer_sub.txt")),
+    (Operation::IntegerMul, include_str!("../testvectors/integer_mul.txt")),
+    (Operation::IntegerDiv, include_str!("../testvectors/integer_div.txt")),
+    (Operation::IntegerRem, include_str!("../testvectors/integer_rem.txt"))
+];
+
+#[derive(Clone,Debug,PartialEq,Eq)]
+pub struct Vector {
+    pub operation: Operation,
+    pub operands: Vec<String>,
+    pub expected: String
+}
+
+#[derive(Clone,Debug,PartialEq,Eq)]
+pub struct Failure {
+    pub vector: Vector,
+    pub actual: String
+}
+
+impl Vector {
+    /// Compute this vector's operation, returning the result as text.
+    pub fn evaluate(&self) -> Result<String, TestVectorError> {
+        if self.operands.len() != self.operation.operands() {
+            return Err(TestVectorError::Operands);
+        }
+        let natural = |i: usize| self.operands[i].parse::<Natural>().map_err(TestVectorError::Invalid);
+        let integer = |i: usize| self.operands[i].parse::<Integer>().map_err(TestVectorError::Invalid);
+        let small = |i: usize| usize::try_from(&natural(i)?).map_err(|_| TestVectorError::OutOfRange);
+        let result = match self.operation {
+            Operation::Add => (natural(0)? + natural(1)?).to_string(),
+            Operation::Sub => (natural(0)? - natural(1)?).to_string(),
+            Operation::Mul => (natural(0)? * natural(1)?).to_string(),
+            Operation::Div => (natural(0)? / natural(1)?).to_string(),
+            Operation::Rem => (natural(0)? % natural(1)?).to_string(),
+            Operation::Cmp => match natural(0)?.cmp(&natural(1)?) {
+                std::cmp::Ordering::Less => "<".to_string(),
+                std::cmp::Ordering::Equal => "=".to_string(),
+                std::cmp::Ordering::Greater => ">".to_string()
+            },
+            Operation::Pow => natural(0)?.pow(small(1)?).to_string(),
+            Operation::ModPow => natural(0)?.mod_pow(&natural(1)?, &natural(2)?).to_string(),
+            Operation::Gcd => natural(0)?.gcd(&natural(1)?).to_string(),
+            Operation::NthRoot => match small(1)? {
+                0 => return Err(TestVectorError::OutOfRange),
+                n => natural(0)?.nth_root(n).to_string()
+            },
+            Operation::Fibonacci => Natural::fibonacci(small(0)? as u64).to_string(),
+            Operation::Lucas => Natural::lucas(small(0)? as u64).to_string(),
+            Operation::Radix => match small(1)? {
+                radix @ 2..=36 => natural(0)?.to_str_radix(radix as u32),
+                _ => return Err(TestVectorError::OutOfRange)
+            },
+            Operation::IntegerAdd => (integer(0)? + integer(1)?).to_string(),
+            Operation::IntegerSub => (integer(0)? - integer(1)?).to_string(),
+            Operation::IntegerMul => (integer(0)? * integer(1)?).to_string(),
+            Operation::IntegerDiv => (integer(0)? / integer(1)?).to_string(),
+            Operation::IntegerRem => (integer(0)? % integer(1)?).to_string()
+        };
+        Ok(result)
+    }
+}
+
+/// All shipped vectors, in file order.
+pub fn vectors() -> Vec<Vector> {
+    let mut vectors = vec![];
+    for (operation, text) in FILES {
+        for line in text.lines() {
+            if line.starts_with('#') || line.trim().is_empty() {
+                continue;
+            }
+            let mut fields: Vec<String> = line.split_whitespace().map(String::from).collect();
+            let expected = fields.pop().unwrap();
+            vectors.push(Vector{ operation, operands: fields, expected });
+        }
+    }
+    vectors
+}
+
+/// Check every vector, returning how many passed or the ones that failed.
+pub fn run() -> Result<usize, Vec<Failure>> {
+    let vectors = vectors();
+    let count = vectors.len();
+    let failures: Vec<Failure> = vectors.into_iter()
+        .filter_map(|vector| {
+            let actual = vector.evaluate().unwrap_or_else(|e| e.to_string());
+            (actual != vector.expected).then_some(Failure{ vector, actual })
+        })
+        .collect();
+
+    if failures.is_empty() {
+        Ok(count)
+    } else {
+        Err(failures)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_operation_has_vectors() {
+        let vectors = vectors();
+        for (operation, _) in FILES {
+            assert!(vectors.iter().filter(|v| v.operation == operation).count() >= 40);
+        }
+    }
+
+    #[test]
+    fn all_pass() {
+        assert_eq!(run(), Ok(vectors().len()));
+    }
+
+    #[test]
+    fn malformed() {
+        let vector = |operation, operands: &[&str]| Vector{
+            operation,
+            operands: operands.iter().map(|s| s.to_string()).collect(),
+            expected: String::new()
+        };
+        assert_eq!(vector(Operation::ModPow, &["2", "3"]).evaluate(), Err(TestVectorError::Operands));
+        assert_eq!(vector(Operation::Radix, &["10", "37"]).evaluate(), Err(TestVectorError::OutOfRange));
+        assert_eq!(vector(Operation::NthRoot, &["10", "0"]).evaluate(), Err(TestVectorError::OutOfRange));
+        assert_eq!(vector(Operation::Pow, &["2", &"9".repeat(30)]).evaluate(), Err(TestVectorError::OutOfRange));
+        assert!(matches!(vector(Operation::IntegerAdd, &["1", "x"]).evaluate(), Err(TestVectorError::Invalid(_))));
+    }
+}
@@ -0,0 +1,97 @@
+//! Algorithm Thresholds
+//!
+//! Operations switch to asymptotically faster algorithms once their
+//! operands are long enough. The crossover depends on the machine, so the
+//! thresholds live in one process-wide setting that can be replaced, for
+//! instance with the output of `calibrate::calibrate`. The text form is
+//! one `name = digits` line per threshold, with `#` comments.
+
+use crate::error::ParseThresholdsError;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+const DEFAULT_KARATSUBA: usize = 48;
+const DEFAULT_HALF_GCD: usize = 3000;
+
+static KARATSUBA: AtomicUsize = AtomicUsize::new(DEFAULT_KARATSUBA);
+static HALF_GCD: AtomicUsize = AtomicUsize::new(DEFAULT_HALF_GCD);
+
+/// Operand lengths, in digits, at which algorithms switch.
+#[derive(Clone,Copy,Debug,PartialEq,Eq)]
+pub struct Thresholds {
+    /// Multiply by Karatsuba's method once both operands are this long
+    pub karatsuba: usize,
+    /// Take gcds and rational reconstructions by half-GCD steps once the
+    /// operands are this long
+    pub half_gcd: usize
+}
+
+impl Default for Thresholds {
+    fn default() -> Self {
+        Self{ karatsuba: DEFAULT_KARATSUBA, half_gcd: DEFAULT_HALF_GCD }
+    }
+}
+
+impl Thresholds {
+    /// The thresholds in use.
+    pub fn current() -> Self {
+        Self{ karatsuba: KARATSUBA.load(Ordering::Relaxed), half_gcd: HALF_GCD.load(Ordering::Relaxed) }
+    }
+
+    /// Use these thresholds for every later operation in the process.
+    pub fn install(self) {
+        KARATSUBA.store(self.karatsuba, Ordering::Relaxed);
+        HALF_GCD.store(self.half_gcd, Ordering::Relaxed);
+    }
+}
+
+impl std::fmt::Display for Thresholds {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "karatsuba = {}", self.karatsuba)?;
+        writeln!(f, "half_gcd = {}", self.half_gcd)
+    }
+}
+
+/// Names that are not given keep their default.
+impl std::str::FromStr for Thresholds {
+    type Err = ParseThresholdsError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut thresholds = Self::default();
+        for (i, line) in s.lines().enumerate() {
+            let line = line.split('#').next().unwrap().trim();
+            if line.is_empty() {
+                continue;
+            }
+            let invalid = || ParseThresholdsError::InvalidLine(i + 1);
+            let (name, value) = line.split_once('=').ok_or_else(invalid)?;
+            let value = value.trim().parse().map_err(|_| invalid())?;
+            match name.trim() {
+                "karatsuba" => thresholds.karatsuba = value,
+                "half_gcd" => thresholds.half_gcd = value,
+                name => return Err(ParseThresholdsError::UnknownName(name.to_string()))
+            }
+        }
+        Ok(thresholds)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn text_form() {
+        let t = Thresholds{ karatsuba: 30, half_gcd: 500 };
+        assert_eq!(t.to_string().parse(), Ok(t));
+        let tuned = Thresholds{ karatsuba: 64, ..Thresholds::default() };
+        assert_eq!("# tuned\n\n karatsuba=64 # on the build server".parse(), Ok(tuned));
+        assert_eq!("".parse(), Ok(Thresholds::default()));
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!("karatsuba 64".parse::<Thresholds>(), Err(ParseThresholdsError::InvalidLine(1)));
+        assert_eq!("\nkaratsuba = x".parse::<Thresholds>(), Err(ParseThresholdsError::InvalidLine(2)));
+        assert_eq!("toom = 3".parse::<Thresholds>(), Err(ParseThresholdsError::UnknownName("toom".to_string())));
+    }
+}
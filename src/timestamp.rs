@@ -0,0 +1,181 @@
+//! Timestamps Beyond 2038
+//!
+//! A `BigTimestamp` counts seconds from the Unix epoch, 1970-01-01
+//! 00:00:00 UTC, as an `Integer`, so it never overflows and reaches as far
+//! before the epoch as after it. Dates are proleptic Gregorian, the
+//! current calendar extended in both directions, with astronomical year
+//! numbering: the year before 1 is 0, and before that -1. Like Unix time,
+//! every day has 86 400 seconds.
+
+use crate::integer::Integer;
+use crate::natural::Natural;
+
+const SECS_PER_DAY: u32 = 86_400;
+// The calendar repeats every 400 years, which are exactly this many days
+const DAYS_PER_ERA: u32 = 146_097;
+// From 0000-03-01, where eras start so that leap days end them, to the epoch
+const EPOCH_DAYS: u32 = 719_468;
+
+fn int(x: u32) -> Integer {
+    Integer::from(Natural::from(x))
+}
+
+// The remainder of a Euclidean division by a small positive divisor
+fn small_rem(value: &Integer, divisor: u32) -> u32 {
+    u32::try_from(value.rem_euclid(&int(divisor)).magnitude()).unwrap()
+}
+
+/// A date and time of day in UTC.
+#[derive(Clone,Debug,PartialEq,Eq)]
+pub struct DateTime {
+    /// Astronomical: 0 is 1 BC
+    pub year: Integer,
+    /// 1 to 12
+    pub month: u8,
+    /// 1 to 31
+    pub day: u8,
+    pub hour: u8,
+    pub minute: u8,
+    pub second: u8
+}
+
+fn is_leap_year(year: &Integer) -> bool {
+    let cycle = small_rem(year, 400);
+    cycle.is_multiple_of(4) && (!cycle.is_multiple_of(100) || cycle == 0)
+}
+
+fn days_in_month(year: &Integer, month: u8) -> u8 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31
+    }
+}
+
+/// Seconds from the Unix epoch, which are negative before it.
+#[derive(Clone,Debug,PartialEq,Eq,PartialOrd,Ord)]
+pub struct BigTimestamp {
+    secs: Integer
+}
+
+impl BigTimestamp {
+    pub fn from_secs(secs: Integer) -> Self {
+        Self{ secs }
+    }
+
+    pub fn as_secs(&self) -> &Integer {
+        &self.secs
+    }
+
+    /// The timestamp of a date and time, or `None` if it does not exist,
+    /// such as February 30 or 24:00.
+    pub fn from_date_time(date: &DateTime) -> Option<Self> {
+        if !(1..=12).contains(&date.month) || date.day == 0 || date.day > days_in_month(&date.year, date.month)
+            || date.hour > 23 || date.minute > 59 || date.second > 59 {
+            return None;
+        }
+        // Count years from March, so that a leap day is the last of its year
+        let month = u32::from(date.month);
+        let year = match month {
+            1 | 2 => date.year.clone() - Integer::one(),
+            _ => date.year.clone()
+        };
+        let era = year.div_euclid(&int(400));
+        let year_of_era = small_rem(&year, 400);
+        let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + u32::from(date.day) - 1;
+        let day_of_era = 365 * year_of_era + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        let days = era * int(DAYS_PER_ERA) + int(day_of_era) - int(EPOCH_DAYS);
+        let secs_of_day = 3600 * u32::from(date.hour) + 60 * u32::from(date.minute) + u32::from(date.second);
+        Some(Self{ secs: days * int(SECS_PER_DAY) + int(secs_of_day) })
+    }
+
+    pub fn to_date_time(&self) -> DateTime {
+        let days = self.secs.div_euclid(&int(SECS_PER_DAY)) + int(EPOCH_DAYS);
+        let secs_of_day = small_rem(&self.secs, SECS_PER_DAY);
+        let era = days.div_euclid(&int(DAYS_PER_ERA));
+        let day_of_era = small_rem(&days, DAYS_PER_ERA);
+        let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        // Months from March
+        let shifted = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * shifted + 2) / 5 + 1;
+        let month = (shifted + 2) % 12 + 1;
+        let mut year = era * int(400) + int(year_of_era);
+        if month <= 2 {
+            year += Integer::one();
+        }
+        DateTime{
+            year,
+            month: month as u8,
+            day: day as u8,
+            hour: (secs_of_day / 3600) as u8,
+            minute: (secs_of_day / 60 % 60) as u8,
+            second: (secs_of_day % 60) as u8
+        }
+    }
+}
+
+/// ISO 8601, such as `2038-01-19T03:14:08Z`, with years outside 0 to 9999
+/// in its expanded form, such as `+10000-01-01T00:00:00Z`.
+impl std::fmt::Display for BigTimestamp {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let date = self.to_date_time();
+        let digits = format!("{:0>4}", date.year.magnitude().to_string());
+        let sign = match (date.year.is_negative(), digits.len() > 4) {
+            (true, _) => "-",
+            (false, true) => "+",
+            (false, false) => ""
+        };
+        write!(f, "{}{}-{:02}-{:02}T{:02}:{:02}:{:02}Z", sign, digits, date.month, date.day, date.hour, date.minute, date.second)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(secs: &str) -> BigTimestamp {
+        BigTimestamp::from_secs(secs.parse().unwrap())
+    }
+
+    fn date(year: &str, month: u8, day: u8) -> DateTime {
+        DateTime{ year: year.parse().unwrap(), month, day, hour: 0, minute: 0, second: 0 }
+    }
+
+    #[test]
+    fn formats() {
+        assert_eq!(at("0").to_string(), "1970-01-01T00:00:00Z");
+        assert_eq!(at("-1").to_string(), "1969-12-31T23:59:59Z");
+        assert_eq!(at("951782400").to_string(), "2000-02-29T00:00:00Z");
+        assert_eq!(at("2147483648").to_string(), "2038-01-19T03:14:08Z");
+        assert_eq!(at("253402300800").to_string(), "+10000-01-01T00:00:00Z");
+        assert_eq!(at("-62135596800").to_string(), "0001-01-01T00:00:00Z");
+        assert_eq!(at("-62162121600").to_string(), "0000-02-29T00:00:00Z");
+        assert_eq!(at("9223372036854775807").to_string(), "+292277026596-12-04T15:30:07Z");
+        assert_eq!(at("31494784780800").to_string(), "+1000000-01-01T00:00:00Z");
+        assert_eq!(at("-31619114035200").to_string(), "-1000000-03-01T00:00:00Z");
+    }
+
+    #[test]
+    fn dates() {
+        assert_eq!(BigTimestamp::from_date_time(&date("1970", 1, 1)), Some(at("0")));
+        assert_eq!(BigTimestamp::from_date_time(&date("1000000", 1, 1)), Some(at("31494784780800")));
+        let time = DateTime{ hour: 3, minute: 14, second: 8, ..date("2038", 1, 19) };
+        assert_eq!(BigTimestamp::from_date_time(&time), Some(at("2147483648")));
+        assert_eq!(BigTimestamp::from_date_time(&date("1900", 2, 29)), None);
+        assert_eq!(BigTimestamp::from_date_time(&date("2023", 4, 31)), None);
+        assert_eq!(BigTimestamp::from_date_time(&date("2023", 13, 1)), None);
+        assert_eq!(BigTimestamp::from_date_time(&DateTime{ hour: 24, ..date("2023", 1, 1) }), None);
+    }
+
+    #[test]
+    fn round_trips() {
+        let far = format!("-{}", "7".repeat(30));
+        for secs in ["0", "-1", "86399", "-86400", "-86401", "1234567890", "-99999999999", &far] {
+            let t = at(secs);
+            assert_eq!(BigTimestamp::from_date_time(&t.to_date_time()), Some(t));
+        }
+        assert!(at(&far).to_date_time().year.is_negative());
+    }
+}
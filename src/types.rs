@@ -0,0 +1,88 @@
+//! Stable Public Types
+//!
+//! Import from here rather than from the defining modules; this list is the
+//! crate's compatibility promise, however the modules behind it are
+//! reorganized.
+
+pub use crate::digit::{BorrowDifference, CarryProduct, CarrySum, Digit};
+pub use crate::natural::{Natural, ParseOptions};
+pub use crate::integer::{Integer, Sign};
+pub use crate::rounding::RoundingMode;
+pub use crate::digest::{Digest, Fnv1a64};
+pub use crate::random::{RandomSource, SplitMix64};
+pub use crate::thresholds::Thresholds;
+pub use crate::limits::Limits;
+pub use crate::error::{FromChunksError, ParseNaturalError, ParseThresholdsError, TryFromDigitError, TryFromNaturalError};
+
+#[cfg(feature = "format")]
+pub use crate::words::{English, Language};
+#[cfg(feature = "format")]
+pub use crate::humanize::Units;
+#[cfg(feature = "format")]
+pub use crate::bytesize::ByteSize;
+#[cfg(feature = "format")]
+pub use crate::typeset::Typeset;
+#[cfg(feature = "format")]
+pub use crate::negative::{NegativeStyle, SignFormat};
+#[cfg(feature = "time")]
+pub use crate::duration::{BigDuration, Components};
+#[cfg(feature = "time")]
+pub use crate::timestamp::{BigTimestamp, DateTime};
+#[cfg(feature = "cache")]
+pub use crate::cache::{Cache, DirectoryStore, Entry, Key, MemoryLru, Store};
+#[cfg(feature = "testvectors")]
+pub use crate::testvectors::{Failure, Operation, Vector};
+#[cfg(feature = "testvectors")]
+pub use crate::error::TestVectorError;
+#[cfg(feature = "balanced")]
+pub use crate::balanced::BalancedDecimal;
+#[cfg(feature = "rns")]
+pub use crate::rns::{RnsBasis, RnsNumber};
+#[cfg(feature = "rns")]
+pub use crate::error::RnsBasisError;
+#[cfg(feature = "interval")]
+pub use crate::interval::Interval;
+#[cfg(feature = "padic")]
+pub use crate::padic::PAdic;
+#[cfg(feature = "varint")]
+pub use crate::error::VarintError;
+#[cfg(feature = "wire")]
+pub use crate::error::WireError;
+#[cfg(feature = "jwk")]
+pub use crate::error::Base64Error;
+#[cfg(feature = "compact")]
+pub use crate::error::CompactTargetError;
+#[cfg(feature = "rlp")]
+pub use crate::error::RlpError;
+#[cfg(feature = "compress")]
+pub use crate::compress::CompressedNatural;
+#[cfg(feature = "view")]
+pub use crate::view::NaturalView;
+#[cfg(feature = "outofcore")]
+pub use crate::outofcore::DiskNatural;
+#[cfg(feature = "rational")]
+pub use crate::rational::{Notation, Rational};
+#[cfg(feature = "rational")]
+pub use crate::error::ParseRationalError;
+#[cfg(feature = "async")]
+pub use crate::task::{CancelToken, Task};
+#[cfg(feature = "shard")]
+pub use crate::shard::{WorkResult, WorkUnit};
+#[cfg(feature = "shard")]
+pub use crate::error::ShardError;
+#[cfg(feature = "crypto-demos")]
+pub use crate::crypto::{PrimeSize, RsaKeyPair};
+#[cfg(feature = "stream")]
+pub use crate::stream::{Accumulator, Cumulative, CumulativeProduct, CumulativeSum, WindowedSum};
+#[cfg(all(feature = "stream", feature = "rational"))]
+pub use crate::stream::WindowedMean;
+#[cfg(feature = "packed")]
+pub use crate::error::PackedBcdError;
+#[cfg(feature = "spreadsheet")]
+pub use crate::spreadsheet::SpreadsheetFormat;
+#[cfg(feature = "spreadsheet")]
+pub use crate::error::ParseSpreadsheetError;
+#[cfg(feature = "apportion")]
+pub use crate::apportion::{Apportionment, DivisorMethod};
+#[cfg(feature = "bitset")]
+pub use crate::bitset::BitSet;
@@ -0,0 +1,121 @@
+//! LaTeX and MathML
+//!
+//! Exact values typeset for papers and web pages, either in full or in
+//! scientific notation, with digits optionally grouped in threes by thin
+//! spaces.
+
+use crate::natural::Natural;
+use crate::rounding::RoundingMode;
+
+/// How `to_latex` and `to_mathml` lay out a number.
+#[derive(Clone,Copy,Debug,Default,PartialEq,Eq)]
+pub struct Typeset {
+    /// Separate groups of three digits with thin spaces
+    pub group_digits: bool,
+    /// Round half to even to this many significant figures and write
+    /// `m × 10^e`
+    pub scientific: Option<usize>
+}
+
+// `separator` between groups of three digits, counted from the right
+fn grouped(digits: &str, separator: &str) -> String {
+    let mut s = String::new();
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            s.push_str(separator);
+        }
+        s.push(c);
+    }
+    s
+}
+
+impl Natural {
+    // The mantissa and exponent, or just the digits outside scientific form
+    fn typeset_parts(&self, options: Typeset, separator: &str) -> (String, Option<String>) {
+        let group = |digits: &str| match options.group_digits {
+            true => grouped(digits, separator),
+            false => digits.to_string()
+        };
+        let figures = match options.scientific {
+            Some(figures) => figures,
+            None => return (group(&self.to_string()), None)
+        };
+        let rounded = self.round_to_sig_figs(figures, RoundingMode::HalfEven);
+        let digits = rounded.to_string();
+        let exponent = digits.len() - 1;
+        if exponent == 0 {
+            return (digits, None);
+        }
+        // Rounding leaves at most `figures` significant digits
+        let kept = digits[1..figures.min(digits.len())].trim_end_matches('0');
+        let mantissa = match kept {
+            "" => digits[..1].to_string(),
+            kept => format!("{}.{}", &digits[..1], kept)
+        };
+        (mantissa, Some(group(&exponent.to_string())))
+    }
+
+    /// LaTeX math-mode source, such as `1\,234\,567` or
+    /// `1.23 \times 10^{6}`.
+    ///
+    /// Panics if `options.scientific` is `Some(0)`.
+    pub fn to_latex(&self, options: Typeset) -> String {
+        match self.typeset_parts(options, "\\,") {
+            (mantissa, None) => mantissa,
+            (mantissa, Some(exponent)) => format!("{} \\times 10^{{{}}}", mantissa, exponent)
+        }
+    }
+
+    /// A MathML `<mn>`, or for scientific notation an `<mrow>` of the
+    /// mantissa, a times sign and a power of ten.
+    ///
+    /// Panics if `options.scientific` is `Some(0)`.
+    pub fn to_mathml(&self, options: Typeset) -> String {
+        match self.typeset_parts(options, "&#x2009;") {
+            (mantissa, None) => format!("<mn>{}</mn>", mantissa),
+            (mantissa, Some(exponent)) => format!(
+                "<mrow><mn>{}</mn><mo>&#xD7;</mo><msup><mn>10</mn><mn>{}</mn></msup></mrow>",
+                mantissa, exponent
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn n(s: &str) -> Natural {
+        s.parse().unwrap()
+    }
+
+    const GROUPED: Typeset = Typeset{ group_digits: true, scientific: None };
+
+    fn scientific(figures: usize) -> Typeset {
+        Typeset{ group_digits: false, scientific: Some(figures) }
+    }
+
+    #[test]
+    fn latex() {
+        assert_eq!(n("1234567").to_latex(Typeset::default()), "1234567");
+        assert_eq!(n("1234567").to_latex(GROUPED), "1\\,234\\,567");
+        assert_eq!(n("123").to_latex(GROUPED), "123");
+        assert_eq!(n("1234567").to_latex(scientific(3)), "1.23 \\times 10^{6}");
+        assert_eq!(n("1000000").to_latex(scientific(3)), "1 \\times 10^{6}");
+        assert_eq!(n("9996").to_latex(scientific(3)), "1 \\times 10^{4}");
+        assert_eq!(n("7").to_latex(scientific(3)), "7");
+        let huge = n(&format!("25{}", "0".repeat(1233)));
+        let options = Typeset{ group_digits: true, scientific: Some(2) };
+        assert_eq!(huge.to_latex(options), "2.5 \\times 10^{1\\,234}");
+    }
+
+    #[test]
+    fn mathml() {
+        assert_eq!(n("0").to_mathml(Typeset::default()), "<mn>0</mn>");
+        assert_eq!(n("1234567").to_mathml(GROUPED), "<mn>1&#x2009;234&#x2009;567</mn>");
+        assert_eq!(
+            n("1250").to_mathml(scientific(2)),
+            "<mrow><mn>1.2</mn><mo>&#xD7;</mo><msup><mn>10</mn><mn>3</mn></msup></mrow>"
+        );
+    }
+}
@@ -0,0 +1,134 @@
+//! Variable-length Integer Codecs
+//!
+//! Both formats carry seven bits per byte and use the high bit to say that
+//! more bytes follow. LEB128 (protobuf, WebAssembly, DWARF) puts the least
+//! significant group first; VLQ (MIDI, git) puts the most significant group
+//! first. Decoders return the value and the number of bytes consumed, so
+//! they can walk a stream of concatenated varints. Signed values are
+//! zigzag encoded into LEB128, as protobuf's `sint` types are.
+
+use crate::error::VarintError;
+use crate::integer::{Integer, Sign};
+use crate::natural::Natural;
+
+const CONTINUE: u8 = 0x80;
+
+// Collect seven-bit groups up to and including the first byte without the
+// continuation bit
+fn groups(bytes: &[u8]) -> Result<(Vec<u32>, usize), VarintError> {
+    let end = bytes.iter().position(|b| b & CONTINUE == 0).ok_or(VarintError::Truncated)?;
+    let groups = bytes[..=end].iter().map(|b| u32::from(b & !CONTINUE)).collect();
+    Ok((groups, end + 1))
+}
+
+impl Natural {
+    pub fn to_leb128(&self) -> Vec<u8> {
+        let mut groups = self.to_power_of_two_le(7);
+        if groups.is_empty() {
+            groups.push(0);
+        }
+        let last = groups.len() - 1;
+        groups.iter().enumerate()
+            .map(|(i, g)| if i < last { *g as u8 | CONTINUE } else { *g as u8 })
+            .collect()
+    }
+
+    pub fn from_leb128(bytes: &[u8]) -> Result<(Natural, usize), VarintError> {
+        let (groups, used) = groups(bytes)?;
+        Ok((Natural::from_power_of_two_le(&groups, 7), used))
+    }
+
+    pub fn to_vlq(&self) -> Vec<u8> {
+        let mut bytes = self.to_leb128();
+        bytes.reverse();
+        let last = bytes.len() - 1;
+        bytes[0] |= CONTINUE;
+        bytes[last] &= !CONTINUE;
+        bytes
+    }
+
+    pub fn from_vlq(bytes: &[u8]) -> Result<(Natural, usize), VarintError> {
+        let (mut groups, used) = groups(bytes)?;
+        groups.reverse();
+        Ok((Natural::from_power_of_two_le(&groups, 7), used))
+    }
+}
+
+impl Integer {
+    /// LEB128 of the zigzag mapping, which interleaves negatives with the
+    /// non-negatives (0, -1, 1, -2, 2 become 0, 1, 2, 3, 4) so that small
+    /// magnitudes stay short whatever their sign.
+    pub fn to_varint_zigzag(&self) -> Vec<u8> {
+        let doubled = self.magnitude().mul_small(2);
+        let zigzag = match self.sign() {
+            Sign::Negative => doubled - Natural::one(),
+            _ => doubled
+        };
+        zigzag.to_leb128()
+    }
+
+    pub fn from_varint_zigzag(bytes: &[u8]) -> Result<(Integer, usize), VarintError> {
+        let (zigzag, used) = Natural::from_leb128(bytes)?;
+        let (half, odd) = zigzag.div_rem_small(2);
+        let value = match odd {
+            1 => -Integer::from(half + Natural::one()),
+            _ => Integer::from(half)
+        };
+        Ok((value, used))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn n(s: &str) -> Natural {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn leb128() {
+        assert_eq!(n("624485").to_leb128(), vec![0xe5, 0x8e, 0x26]);
+        assert_eq!(Natural::zero().to_leb128(), vec![0]);
+        assert_eq!(Natural::from_leb128(&[0xe5, 0x8e, 0x26, 0x01]), Ok((n("624485"), 3)));
+    }
+
+    #[test]
+    fn vlq() {
+        assert_eq!(n("16383").to_vlq(), vec![0xff, 0x7f]);
+        assert_eq!(n("2097152").to_vlq(), vec![0x81, 0x80, 0x80, 0x00]);
+        assert_eq!(n("127").to_vlq(), vec![0x7f]);
+        assert_eq!(Natural::from_vlq(&[0x81, 0x80, 0x80, 0x00]), Ok((n("2097152"), 4)));
+    }
+
+    #[test]
+    fn beyond_u64() {
+        // 2^100 + 12345
+        let x = n("1267650600228229401496703217721");
+        assert_eq!(Natural::from_leb128(&x.to_leb128()).unwrap().0, x);
+        assert_eq!(Natural::from_vlq(&x.to_vlq()).unwrap().0, x);
+    }
+
+    #[test]
+    fn zigzag() {
+        let i = |s: &str| s.parse::<Integer>().unwrap();
+        assert_eq!(Integer::zero().to_varint_zigzag(), vec![0]);
+        assert_eq!(i("-1").to_varint_zigzag(), vec![1]);
+        assert_eq!(i("1").to_varint_zigzag(), vec![2]);
+        assert_eq!(i("-64").to_varint_zigzag(), vec![0x7f]);
+        assert_eq!(i("64").to_varint_zigzag(), vec![0x80, 0x01]);
+        // i32::MIN, as protobuf encodes it for sint32
+        assert_eq!(i("-2147483648").to_varint_zigzag(), vec![0xff, 0xff, 0xff, 0xff, 0x0f]);
+        for s in ["0", "-1", "1", "-64", "64", "-1267650600228229401496703217721", "1267650600228229401496703217721"] {
+            let bytes = i(s).to_varint_zigzag();
+            assert_eq!(Integer::from_varint_zigzag(&bytes), Ok((i(s), bytes.len())));
+        }
+        assert_eq!(Integer::from_varint_zigzag(&[0x80]), Err(VarintError::Truncated));
+    }
+
+    #[test]
+    fn truncated() {
+        assert_eq!(Natural::from_leb128(&[0xe5, 0x8e]), Err(VarintError::Truncated));
+        assert_eq!(Natural::from_vlq(&[]), Err(VarintError::Truncated));
+    }
+}
@@ -0,0 +1,144 @@
+//! Borrowed Views of Stored Digits
+//!
+//! A `NaturalView` reads a number directly from a byte buffer of ASCII
+//! decimal digits, most significant first, as a digits file would hold
+//! them. The buffer is checked once and never copied, so it can be a
+//! memory-mapped multi-gigabyte constant; only results of arithmetic are
+//! materialized as `Natural`.
+
+use crate::digit::Digit;
+use crate::error::ParseNaturalError;
+use crate::natural::Natural;
+use std::cmp::Ordering;
+
+#[derive(Clone,Copy,Debug,PartialEq,Eq)]
+pub struct NaturalView<'a> {
+    // Validated digits with leading zeros skipped, but never empty
+    digits: &'a [u8]
+}
+
+impl<'a> NaturalView<'a> {
+    /// Check that `bytes` is a nonempty run of ASCII digits and wrap it.
+    pub fn new(bytes: &'a [u8]) -> Result<Self, ParseNaturalError> {
+        if let Some(b) = bytes.iter().find(|b| !b.is_ascii_digit()) {
+            return Err(ParseNaturalError::InvalidCharacter(char::from(*b)));
+        }
+        if bytes.is_empty() {
+            return Err(ParseNaturalError::Empty);
+        }
+        let zeros = bytes.iter().take_while(|b| **b == b'0').count().min(bytes.len() - 1);
+        Ok(Self{ digits: &bytes[zeros..] })
+    }
+
+    /// The number of significant digits, at least one.
+    pub fn digit_count(&self) -> usize {
+        self.digits.len()
+    }
+
+    pub fn coefficient(&self, power: usize) -> Digit {
+        if power >= self.digits.len() {
+            return Digit::Zero;
+        }
+        (self.digits[self.digits.len() - 1 - power] - b'0').try_into().unwrap()
+    }
+
+    pub fn to_natural(&self) -> Natural {
+        let digits = (0..self.digits.len()).map(|p| self.coefficient(p)).collect();
+        Natural::from_digits(digits)
+    }
+
+    pub fn cmp_natural(&self, other: &Natural) -> Ordering {
+        let len = other.significant_len().max(1);
+        self.digits.len().cmp(&len).then_with(|| {
+            let theirs = other.digits[..len].iter().rev().map(|d| d.as_u8() + b'0');
+            self.digits.iter().copied().cmp(theirs)
+        })
+    }
+
+    // Sum digit by digit with any source of the other operand's digits
+    fn add_digits(&self, len: usize, other: impl Fn(usize) -> Digit) -> Natural {
+        let n = self.digits.len().max(len);
+        let mut digits = Vec::with_capacity(n + 1);
+        let mut carry = 0;
+        for p in 0..n {
+            let s = self.coefficient(p).as_u8() + other(p).as_u8() + carry;
+            digits.push((s % 10).try_into().unwrap());
+            carry = s / 10;
+        }
+        digits.push(carry.try_into().unwrap());
+        Natural::from_digits(digits)
+    }
+
+    pub fn add_natural(&self, other: &Natural) -> Natural {
+        self.add_digits(other.digits.len(), |p| other.coefficient(p))
+    }
+}
+
+impl PartialOrd for NaturalView<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// With leading zeros skipped, a longer view is larger, and views of the
+/// same length compare like their bytes.
+impl Ord for NaturalView<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.digits.len().cmp(&other.digits.len()).then_with(|| self.digits.cmp(other.digits))
+    }
+}
+
+impl std::ops::Add for &NaturalView<'_> {
+    type Output = Natural;
+
+    fn add(self, other: Self) -> Self::Output {
+        self.add_digits(other.digits.len(), |p| other.coefficient(p))
+    }
+}
+
+impl std::fmt::Display for NaturalView<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Checked to be ASCII digits on construction
+        write!(f, "{}", std::str::from_utf8(self.digits).unwrap())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn n(s: &str) -> Natural {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn construction() {
+        let view = NaturalView::new(b"000123").unwrap();
+        assert_eq!(view.digit_count(), 3);
+        assert_eq!(view.coefficient(0), Digit::Three);
+        assert_eq!(view.coefficient(5), Digit::Zero);
+        assert_eq!(view.to_natural(), n("123"));
+        assert_eq!(NaturalView::new(b"000").unwrap().to_string(), "0");
+        assert_eq!(NaturalView::new(b""), Err(ParseNaturalError::Empty));
+        assert_eq!(NaturalView::new(b"12\n"), Err(ParseNaturalError::InvalidCharacter('\n')));
+    }
+
+    #[test]
+    fn comparison() {
+        let a = NaturalView::new(b"0999").unwrap();
+        let b = NaturalView::new(b"1000").unwrap();
+        assert!(a < b);
+        assert_eq!(a, NaturalView::new(b"999").unwrap());
+        assert_eq!(b.cmp_natural(&n("1000")), Ordering::Equal);
+        assert_eq!(b.cmp_natural(&n("0999")), Ordering::Greater);
+        assert_eq!(NaturalView::new(b"0").unwrap().cmp_natural(&n("0")), Ordering::Equal);
+    }
+
+    #[test]
+    fn addition() {
+        let a = NaturalView::new(b"99999999999999999999").unwrap();
+        let b = NaturalView::new(b"1").unwrap();
+        assert_eq!(&a + &b, n("100000000000000000000"));
+        assert_eq!(b.add_natural(&n("41")), n("42"));
+    }
+}
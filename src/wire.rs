@@ -0,0 +1,110 @@
+//! Length-prefixed Wire Formats
+//!
+//! SSH's `mpint` (RFC 4251, section 5) is a four-byte big-endian length
+//! followed by the value in minimal two's complement, so a positive value
+//! whose top bit is set gains a leading zero byte and zero has no bytes at
+//! all. TLS carries big numbers such as Diffie-Hellman parameters (RFC
+//! 5246, section 7.4.3) as opaque vectors with a two-byte length, and
+//! strips leading zeros. Decoders return the value and the number of bytes
+//! consumed, so they can walk a message of consecutive fields.
+
+use crate::error::WireError;
+use crate::natural::Natural;
+
+// Split off a big-endian length prefix of `width` bytes and its payload
+fn framed(bytes: &[u8], width: usize) -> Result<(&[u8], usize), WireError> {
+    if bytes.len() < width {
+        return Err(WireError::Truncated);
+    }
+    let len = bytes[..width].iter().fold(0_usize, |acc, b| (acc << 8) | usize::from(*b));
+    let end = width.checked_add(len).filter(|end| *end <= bytes.len()).ok_or(WireError::Truncated)?;
+    Ok((&bytes[width..end], end))
+}
+
+impl Natural {
+    /// Panics if the value needs more than 2^32 - 1 bytes.
+    pub fn to_ssh_mpint(&self) -> Vec<u8> {
+        let mut body = self.to_bytes_be();
+        if body.first().is_some_and(|b| b & 0x80 != 0) {
+            body.insert(0, 0);
+        }
+        let len = u32::try_from(body.len()).expect("mpint longer than 2^32 - 1 bytes");
+        let mut bytes = len.to_be_bytes().to_vec();
+        bytes.append(&mut body);
+        bytes
+    }
+
+    pub fn from_ssh_mpint(bytes: &[u8]) -> Result<(Natural, usize), WireError> {
+        let (body, used) = framed(bytes, 4)?;
+        match body {
+            [first, ..] if first & 0x80 != 0 => Err(WireError::Negative),
+            [0] => Err(WireError::NonMinimal),
+            [0, second, ..] if second & 0x80 == 0 => Err(WireError::NonMinimal),
+            _ => Ok((Natural::from_bytes_be(body), used))
+        }
+    }
+
+    /// Zero is sent as a single zero byte, since TLS vectors of big
+    /// numbers must not be empty.
+    pub fn to_tls_bignum(&self) -> Result<Vec<u8>, WireError> {
+        let mut body = self.to_bytes_be();
+        if body.is_empty() {
+            body.push(0);
+        }
+        let len = u16::try_from(body.len()).map_err(|_| WireError::TooLong)?;
+        let mut bytes = len.to_be_bytes().to_vec();
+        bytes.append(&mut body);
+        Ok(bytes)
+    }
+
+    /// Leading zeros are accepted, since peers do not all strip them.
+    pub fn from_tls_bignum(bytes: &[u8]) -> Result<(Natural, usize), WireError> {
+        let (body, used) = framed(bytes, 2)?;
+        Ok((Natural::from_bytes_be(body), used))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn n(s: &str) -> Natural {
+        s.parse().unwrap()
+    }
+
+    // The examples from RFC 4251, section 5
+    #[test]
+    fn rfc4251_examples() {
+        assert_eq!(Natural::zero().to_ssh_mpint(), vec![0, 0, 0, 0]);
+        assert_eq!(n("2309737967").to_ssh_mpint(), vec![0, 0, 0, 5, 0, 0x89, 0xab, 0xcd, 0xef]);
+        assert_eq!(n("128").to_ssh_mpint(), vec![0, 0, 0, 2, 0, 0x80]);
+        assert_eq!(n("127").to_ssh_mpint(), vec![0, 0, 0, 1, 0x7f]);
+    }
+
+    #[test]
+    fn ssh_round_trip() {
+        let x = n("123456789012345678901234567890");
+        let mut stream = x.to_ssh_mpint();
+        stream.extend(Natural::zero().to_ssh_mpint());
+        let (first, used) = Natural::from_ssh_mpint(&stream).unwrap();
+        assert_eq!(first, x);
+        assert_eq!(Natural::from_ssh_mpint(&stream[used..]), Ok((Natural::zero(), 4)));
+    }
+
+    #[test]
+    fn ssh_rejects() {
+        assert_eq!(Natural::from_ssh_mpint(&[0, 0, 0, 1, 0x80]), Err(WireError::Negative));
+        assert_eq!(Natural::from_ssh_mpint(&[0, 0, 0, 1, 0]), Err(WireError::NonMinimal));
+        assert_eq!(Natural::from_ssh_mpint(&[0, 0, 0, 2, 0, 0x7f]), Err(WireError::NonMinimal));
+        assert_eq!(Natural::from_ssh_mpint(&[0, 0, 0, 2, 1]), Err(WireError::Truncated));
+        assert_eq!(Natural::from_ssh_mpint(&[0, 0]), Err(WireError::Truncated));
+    }
+
+    #[test]
+    fn tls() {
+        assert_eq!(Natural::zero().to_tls_bignum(), Ok(vec![0, 1, 0]));
+        assert_eq!(n("65537").to_tls_bignum(), Ok(vec![0, 3, 1, 0, 1]));
+        assert_eq!(Natural::from_tls_bignum(&[0, 3, 0, 1, 0, 0xff]), Ok((n("256"), 5)));
+        assert_eq!(Natural::from_tls_bignum(&[0, 3, 1]), Err(WireError::Truncated));
+    }
+}
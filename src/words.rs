@@ -0,0 +1,248 @@
+//! Spelled-out Number Words
+
+use crate::natural::Natural;
+
+/// A spoken language capable of naming any natural number.
+///
+/// Implement this to plug in another language; `English` is provided.
+pub trait Language {
+    fn cardinal(&self, n: &Natural) -> String;
+    fn ordinal(&self, n: &Natural) -> String;
+}
+
+/// Split a number into groups of three digits, least significant first.
+///
+/// Most languages name numbers one thousands-group at a time, so this is
+/// exposed for implementors of `Language`.
+pub fn thousands(n: &Natural) -> Vec<u16> {
+    n.digits
+        .chunks(3)
+        .map(|chunk| {
+            chunk.iter().rev().fold(0, |acc, d| acc * 10 + u16::from(d.as_u8()))
+        })
+        .collect()
+}
+
+/// American English, short scale, without "and" ("one hundred twenty-three").
+///
+/// Scale words beyond the common ones follow the Conway-Wechsler system, so
+/// every group of digits has a name.
+#[derive(Clone,Copy,Debug,Default)]
+pub struct English;
+
+const ONES: [&str; 20] = [
+    "zero", "one", "two", "three", "four", "five", "six", "seven", "eight",
+    "nine", "ten", "eleven", "twelve", "thirteen", "fourteen", "fifteen",
+    "sixteen", "seventeen", "eighteen", "nineteen"
+];
+
+const TENS: [&str; 10] = [
+    "", "", "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty",
+    "ninety"
+];
+
+impl English {
+    fn below_thousand(n: u16) -> String {
+        let mut words = vec![];
+        let hundreds = n / 100;
+        let rest = n % 100;
+        if hundreds > 0 {
+            words.push(format!("{} hundred", ONES[hundreds as usize]));
+        }
+        if rest >= 20 {
+            let ones = rest % 10;
+            if ones == 0 {
+                words.push(TENS[(rest / 10) as usize].to_string());
+            } else {
+                words.push(format!("{}-{}", TENS[(rest / 10) as usize], ONES[ones as usize]));
+            }
+        } else if rest > 0 {
+            words.push(ONES[rest as usize].to_string());
+        }
+        words.join(" ")
+    }
+
+    // The name of 10^(3 * group), for group >= 1
+    pub(crate) fn scale(group: usize) -> String {
+        if group == 1 {
+            return "thousand".to_string();
+        }
+        let mut n = group - 1;
+        let mut parts = vec![];
+        while n > 0 {
+            parts.insert(0, latin_prefix(n % 1000));
+            n /= 1000;
+        }
+        let mut name = String::new();
+        for part in parts {
+            name.push_str(&part);
+            name.push_str("illi");
+        }
+        name.push_str("on");
+        name
+    }
+
+    fn ordinal_word(word: &str) -> String {
+        match word {
+            "one" => "first".to_string(),
+            "two" => "second".to_string(),
+            "three" => "third".to_string(),
+            "five" => "fifth".to_string(),
+            "eight" => "eighth".to_string(),
+            "nine" => "ninth".to_string(),
+            "twelve" => "twelfth".to_string(),
+            _ => match word.strip_suffix('y') {
+                Some(stem) => format!("{}ieth", stem),
+                None => format!("{}th", word)
+            }
+        }
+    }
+}
+
+// The Conway-Wechsler prefix for 0..999, ready to have "illi" appended
+fn latin_prefix(n: usize) -> String {
+    const SMALL: [&str; 10] = [
+        "n", "m", "b", "tr", "quadr", "quint", "sext", "sept", "oct", "non"
+    ];
+    const UNITS: [&str; 10] = [
+        "", "un", "duo", "tre", "quattuor", "quinqua", "se", "septe", "octo", "nove"
+    ];
+    // Each tens and hundreds prefix carries the marks that inflect the unit
+    const TENS: [(&str, &str); 10] = [
+        ("", ""), ("deci", "N"), ("viginti", "MS"), ("triginta", "NS"),
+        ("quadraginta", "NS"), ("quinquaginta", "NS"), ("sexaginta", "N"),
+        ("septuaginta", "N"), ("octoginta", "MX"), ("nonaginta", "")
+    ];
+    const HUNDREDS: [(&str, &str); 10] = [
+        ("", ""), ("centi", "NX"), ("ducenti", "N"), ("trecenti", "NS"),
+        ("quadringenti", "NS"), ("quingenti", "NS"), ("sescenti", "N"),
+        ("septingenti", "N"), ("octingenti", "MX"), ("nongenti", "")
+    ];
+
+    if n < 10 {
+        return SMALL[n].to_string();
+    }
+
+    let (u, t, h) = (n % 10, (n / 10) % 10, n / 100);
+    let marks = if t > 0 { TENS[t].1 } else { HUNDREDS[h].1 };
+    let unit = match u {
+        3 if marks.contains('S') || marks.contains('X') => "tres",
+        6 if marks.contains('X') => "sex",
+        6 if marks.contains('S') => "ses",
+        7 if marks.contains('M') => "septem",
+        7 if marks.contains('N') => "septen",
+        9 if marks.contains('M') => "novem",
+        9 if marks.contains('N') => "noven",
+        _ => UNITS[u]
+    };
+
+    let mut prefix = format!("{}{}{}", unit, TENS[t].0, HUNDREDS[h].0);
+    if prefix.ends_with(['a', 'e', 'i', 'o', 'u']) {
+        prefix.pop();
+    }
+    prefix
+}
+
+impl Language for English {
+    fn cardinal(&self, n: &Natural) -> String {
+        let groups = thousands(n);
+        let mut words = vec![];
+        for (power, group) in groups.iter().enumerate().rev() {
+            if *group == 0 {
+                continue;
+            }
+            words.push(Self::below_thousand(*group));
+            if power > 0 {
+                words.push(Self::scale(power));
+            }
+        }
+
+        if words.is_empty() {
+            return ONES[0].to_string();
+        }
+        words.join(" ")
+    }
+
+    fn ordinal(&self, n: &Natural) -> String {
+        let cardinal = self.cardinal(n);
+        let split = cardinal.rfind([' ', '-']).map_or(0, |i| i + 1);
+        let (head, last) = cardinal.split_at(split);
+        format!("{}{}", head, Self::ordinal_word(last))
+    }
+}
+
+impl Natural {
+    pub fn to_words(&self) -> String {
+        English.cardinal(self)
+    }
+
+    pub fn to_ordinal_words(&self) -> String {
+        English.ordinal(self)
+    }
+
+    pub fn to_words_in<L: Language>(&self, language: &L) -> String {
+        language.cardinal(self)
+    }
+
+    pub fn to_ordinal_words_in<L: Language>(&self, language: &L) -> String {
+        language.ordinal(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn n(s: &str) -> Natural {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn zero() {
+        assert_eq!(n("0").to_words(), "zero");
+        assert_eq!(n("0").to_ordinal_words(), "zeroth");
+    }
+
+    #[test]
+    fn cardinal() {
+        assert_eq!(n("123").to_words(), "one hundred twenty-three");
+        assert_eq!(n("1000010").to_words(), "one million ten");
+        assert_eq!(n("90000000000").to_words(), "ninety billion");
+    }
+
+    #[test]
+    fn ordinal() {
+        assert_eq!(n("123").to_ordinal_words(), "one hundred twenty-third");
+        assert_eq!(n("12").to_ordinal_words(), "twelfth");
+        assert_eq!(n("40").to_ordinal_words(), "fortieth");
+        assert_eq!(n("2000").to_ordinal_words(), "two thousandth");
+    }
+
+    #[test]
+    fn large_scales() {
+        let decillion = format!("1{}", "0".repeat(33));
+        assert_eq!(n(&decillion).to_words(), "one decillion");
+        let tresvigintillion = format!("1{}", "0".repeat(72));
+        assert_eq!(n(&tresvigintillion).to_words(), "one tresvigintillion");
+        let millinillion = format!("1{}", "0".repeat(3003));
+        assert_eq!(n(&millinillion).to_words(), "one millinillion");
+    }
+
+    struct Digits;
+
+    impl Language for Digits {
+        fn cardinal(&self, n: &Natural) -> String {
+            n.to_string()
+        }
+
+        fn ordinal(&self, n: &Natural) -> String {
+            format!("#{}", n)
+        }
+    }
+
+    #[test]
+    fn other_language() {
+        assert_eq!(n("42").to_words_in(&Digits), "42");
+        assert_eq!(n("42").to_ordinal_words_in(&Digits), "#42");
+    }
+}
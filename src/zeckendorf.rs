@@ -0,0 +1,110 @@
+//! Zeckendorf Representation and Fibonacci Codes
+//!
+//! Every natural number is uniquely a sum of non-consecutive Fibonacci
+//! numbers. Indices follow F(1) = F(2) = 1, F(3) = 2, so a representation
+//! only ever uses indices of 2 or more.
+
+use crate::natural::Natural;
+
+// F(2), F(3), ... up to the largest not exceeding `limit`
+fn fibonacci_up_to(limit: &Natural) -> Vec<Natural> {
+    let mut fibs = vec![Natural::one()];
+    let mut next = Natural::one().add_small(1);
+    while next <= *limit {
+        let after = fibs[fibs.len() - 1].clone() + next.clone();
+        fibs.push(next);
+        next = after;
+    }
+    fibs
+}
+
+impl Natural {
+    /// Indices of the Fibonacci numbers summing to this value, largest
+    /// first. Zero is the empty sum.
+    pub fn to_zeckendorf(&self) -> Vec<usize> {
+        let fibs = fibonacci_up_to(self);
+        let mut rest = self.clone();
+        let mut indices = vec![];
+        let mut i = fibs.len();
+        while i > 0 && rest != Natural::zero() {
+            i -= 1;
+            if fibs[i] <= rest {
+                rest -= &fibs[i];
+                indices.push(i + 2);
+                // The next smaller Fibonacci number can never be needed
+                i = i.saturating_sub(1);
+            }
+        }
+        indices
+    }
+
+    /// The sum of F(i) over `indices`.
+    ///
+    /// This inverts `to_zeckendorf`, but accepts any indices, including
+    /// consecutive or repeated ones.
+    pub fn from_zeckendorf(indices: &[usize]) -> Natural {
+        let (mut a, mut b) = (Natural::zero(), Natural::one());
+        let mut fibs = vec![];
+        let top = indices.iter().copied().max().unwrap_or(0);
+        for _ in 0..=top {
+            fibs.push(a.clone());
+            (a, b) = (b.clone(), a + b);
+        }
+        indices.iter().map(|i| &fibs[*i]).sum()
+    }
+
+    /// The Fibonacci code: one bit per index from 2 upward, terminated by an
+    /// extra 1 so that "11" marks the end of each codeword. Only defined for
+    /// positive values.
+    pub fn to_fibonacci_code(&self) -> Vec<bool> {
+        assert!(*self != Natural::zero(), "the Fibonacci code has no codeword for zero");
+        let indices = self.to_zeckendorf();
+        let mut bits = vec![false; indices[0] - 1];
+        for i in indices {
+            bits[i - 2] = true;
+        }
+        bits.push(true);
+        bits
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn n(s: &str) -> Natural {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn small() {
+        // 100 = 89 + 8 + 3
+        assert_eq!(n("100").to_zeckendorf(), vec![11, 6, 4]);
+        assert_eq!(n("1").to_zeckendorf(), vec![2]);
+        assert!(Natural::zero().to_zeckendorf().is_empty());
+    }
+
+    #[test]
+    fn round_trip() {
+        for i in 0..300 {
+            let x = n(&i.to_string());
+            let z = x.to_zeckendorf();
+            assert!(z.windows(2).all(|w| w[0] >= w[1] + 2));
+            assert_eq!(Natural::from_zeckendorf(&z), x);
+        }
+    }
+
+    #[test]
+    fn large() {
+        let x = n("123456789012345678901234567890");
+        assert_eq!(Natural::from_zeckendorf(&x.to_zeckendorf()), x);
+    }
+
+    #[test]
+    fn fibonacci_code() {
+        let bits: String = n("11").to_fibonacci_code().iter()
+            .map(|b| if *b { '1' } else { '0' })
+            .collect();
+        assert_eq!(bits, "001011");
+    }
+}
@@ -0,0 +1,15 @@
+//! The parse limit is process-wide, so it is tested in a process of its
+//! own rather than alongside the unit tests that parse in parallel.
+
+use bignum::types::{Limits, Natural};
+
+#[test]
+fn constants_ignore_the_parse_limit() {
+    Limits{ max_parse_len: Some(0) }.install();
+    assert!("1".parse::<Natural>().is_err());
+    assert_eq!(Natural::zero(), Natural::from(0u32));
+    assert_eq!(Natural::one(), Natural::from(1u32));
+    #[cfg(feature = "primes")]
+    assert!(Natural::from(1000003u32).is_probable_prime(5));
+    Limits::default().install();
+}